@@ -1,8 +1,20 @@
 use std::io::Result;
 
 fn main() -> Result<()> {
-    const PAYMENT_PROTOS: &[&str] =
-        &["service-apis/proto/sited_io/payment/v1/stripe.proto"];
+    const PAYMENT_PROTOS: &[&str] = &[
+        "service-apis/proto/sited_io/payment/v1/stripe.proto",
+        "service-apis/proto/sited_io/payment/v1/abandoned_checkout.proto",
+        "service-apis/proto/sited_io/payment/v1/billing_portal.proto",
+        "service-apis/proto/sited_io/payment/v1/coupon.proto",
+        "service-apis/proto/sited_io/payment/v1/entitlement.proto",
+        "service-apis/proto/sited_io/payment/v1/license_key.proto",
+        "service-apis/proto/sited_io/payment/v1/offer_settings.proto",
+        "service-apis/proto/sited_io/payment/v1/order.proto",
+        "service-apis/proto/sited_io/payment/v1/payment.proto",
+        "service-apis/proto/sited_io/payment/v1/shipping_option.proto",
+        "service-apis/proto/sited_io/payment/v1/stock.proto",
+        "service-apis/proto/sited_io/payment/v1/tax.proto",
+    ];
 
     const COMMERCE_PROTOS: &[&str] = &[
         "service-apis/proto/sited_io/commerce/v1/shop.proto",
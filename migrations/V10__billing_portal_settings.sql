@@ -0,0 +1,10 @@
+CREATE TABLE billing_portal_settings (
+  shop_id UUID NOT NULL PRIMARY KEY,
+  allow_cancel BOOL NOT NULL DEFAULT false,
+  allow_plan_switch BOOL NOT NULL DEFAULT false,
+  stripe_configuration_id VARCHAR NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+ALTER TABLE payments ADD COLUMN stripe_customer_id VARCHAR;
@@ -0,0 +1,8 @@
+CREATE TABLE customers (
+  user_id VARCHAR NOT NULL,
+  stripe_account_id VARCHAR NOT NULL,
+  stripe_customer_id VARCHAR NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW(),
+  PRIMARY KEY (user_id, stripe_account_id)
+);
@@ -0,0 +1,14 @@
+CREATE TABLE subscription_cancellations (
+  subscription_cancellation_id UUID NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  stripe_subscription_id VARCHAR NOT NULL,
+  cancellation_mode VARCHAR NOT NULL,
+  refund_policy VARCHAR NOT NULL,
+  amount_refunded INT8 NOT NULL DEFAULT 0,
+  reason VARCHAR,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE UNIQUE INDEX subscription_cancellations_stripe_subscription_id_idx
+  ON subscription_cancellations (stripe_subscription_id, cancellation_mode);
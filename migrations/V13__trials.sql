@@ -0,0 +1,4 @@
+ALTER TABLE offer_settings
+  ADD COLUMN trial_without_payment_method BOOL NOT NULL DEFAULT false;
+
+ALTER TABLE payments ADD COLUMN trial_ends_at TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,2 @@
+ALTER TABLE offer_settings
+  ADD COLUMN allow_multiple_subscriptions BOOL NOT NULL DEFAULT false;
@@ -0,0 +1,15 @@
+CREATE TABLE entitlements (
+  entitlement_id UUID NOT NULL PRIMARY KEY,
+  user_id VARCHAR NOT NULL,
+  offer_id UUID NOT NULL,
+  shop_id UUID NOT NULL,
+  payment_id UUID UNIQUE,
+  stripe_subscription_id VARCHAR UNIQUE,
+  valid_from TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  valid_until TIMESTAMP WITH TIME ZONE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX entitlements_user_id_offer_id_idx
+  ON entitlements (user_id, offer_id);
@@ -0,0 +1,28 @@
+CREATE TABLE license_key_pools (
+  offer_id UUID NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  key_format VARCHAR,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX license_key_pools_shop_id_idx ON license_key_pools (shop_id);
+
+CREATE TABLE license_keys (
+  license_key_id UUID NOT NULL PRIMARY KEY,
+  offer_id UUID NOT NULL,
+  shop_id UUID NOT NULL,
+  license_key VARCHAR NOT NULL,
+  payment_id UUID UNIQUE,
+  user_id VARCHAR,
+  assigned_at TIMESTAMP WITH TIME ZONE,
+  revoked_at TIMESTAMP WITH TIME ZONE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW(),
+  UNIQUE (offer_id, license_key)
+);
+
+CREATE INDEX license_keys_offer_id_assigned_at_idx
+  ON license_keys (offer_id, assigned_at);
+
+CREATE INDEX license_keys_user_id_idx ON license_keys (user_id);
@@ -0,0 +1,6 @@
+ALTER TABLE payments ADD COLUMN buyer_email VARCHAR;
+
+CREATE INDEX payments_buyer_email_idx ON payments (buyer_email);
+
+CREATE UNIQUE INDEX payments_free_buyer_email_idx ON payments (offer_id, buyer_email)
+    WHERE stripe_checkout_session_id IS NULL AND buyer_email IS NOT NULL;
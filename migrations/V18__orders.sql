@@ -0,0 +1,34 @@
+CREATE TABLE orders (
+  order_id UUID NOT NULL PRIMARY KEY,
+  payment_id UUID NOT NULL UNIQUE,
+  shop_id UUID NOT NULL,
+  offer_id UUID NOT NULL,
+  status VARCHAR NOT NULL,
+  shipping_name VARCHAR,
+  shipping_line1 VARCHAR,
+  shipping_line2 VARCHAR,
+  shipping_postal_code VARCHAR,
+  shipping_city VARCHAR,
+  shipping_state VARCHAR,
+  shipping_country VARCHAR,
+  carrier VARCHAR,
+  tracking_number VARCHAR,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX orders_shop_id_status_idx ON orders (shop_id, status);
+
+CREATE TABLE order_events (
+  order_event_id UUID NOT NULL PRIMARY KEY,
+  order_id UUID NOT NULL,
+  shop_id UUID NOT NULL,
+  from_status VARCHAR,
+  to_status VARCHAR NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  published_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX order_events_order_id_idx ON order_events (order_id);
+
+CREATE INDEX order_events_unpublished_idx ON order_events (created_at) WHERE published_at IS NULL;
@@ -0,0 +1,20 @@
+CREATE TABLE offer_stocks (
+  offer_id UUID NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  quantity INT8 NOT NULL,
+  reserved INT8 NOT NULL DEFAULT 0,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX offer_stocks_shop_id_idx ON offer_stocks (shop_id);
+
+CREATE TABLE stock_reservations (
+  stripe_checkout_session_id VARCHAR NOT NULL PRIMARY KEY,
+  offer_id UUID NOT NULL,
+  quantity INT8 NOT NULL,
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX stock_reservations_offer_id_idx ON stock_reservations (offer_id);
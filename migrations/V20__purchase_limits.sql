@@ -0,0 +1,18 @@
+ALTER TABLE offer_settings
+  ADD COLUMN max_quantity_per_session INT8,
+  ADD COLUMN max_quantity_per_buyer INT8;
+
+ALTER TABLE payments ADD COLUMN quantity INT8;
+
+CREATE TABLE purchase_reservations (
+  stripe_checkout_session_id VARCHAR NOT NULL PRIMARY KEY,
+  offer_id UUID NOT NULL,
+  user_id VARCHAR,
+  buyer_email VARCHAR,
+  quantity INT8 NOT NULL,
+  expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX purchase_reservations_offer_id_idx
+  ON purchase_reservations (offer_id);
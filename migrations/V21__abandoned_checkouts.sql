@@ -0,0 +1,13 @@
+CREATE TABLE abandoned_checkouts (
+  stripe_checkout_session_id VARCHAR NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  offer_id UUID NOT NULL,
+  buyer_email VARCHAR NOT NULL,
+  recovery_url VARCHAR NOT NULL,
+  recovery_expires_at TIMESTAMP WITH TIME ZONE,
+  seller_notified_at TIMESTAMP WITH TIME ZONE,
+  recovered_at TIMESTAMP WITH TIME ZONE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX abandoned_checkouts_shop_id_idx ON abandoned_checkouts (shop_id);
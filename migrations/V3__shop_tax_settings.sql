@@ -0,0 +1,11 @@
+CREATE TABLE shop_tax_settings (
+  shop_id UUID NOT NULL PRIMARY KEY,
+  automatic_tax_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+  tax_behavior VARCHAR NOT NULL DEFAULT 'unspecified',
+  physical_tax_code VARCHAR,
+  digital_tax_code VARCHAR,
+  service_tax_code VARCHAR,
+  tax_id_collection_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
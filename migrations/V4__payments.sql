@@ -0,0 +1,21 @@
+CREATE TABLE payments (
+  payment_id UUID NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  offer_id UUID NOT NULL,
+  user_id VARCHAR,
+  stripe_checkout_session_id VARCHAR NOT NULL UNIQUE,
+  stripe_payment_intent_id VARCHAR,
+  stripe_subscription_id VARCHAR,
+  payment_status VARCHAR NOT NULL,
+  currency VARCHAR NOT NULL,
+  amount_subtotal BIGINT NOT NULL,
+  amount_discount BIGINT NOT NULL,
+  amount_shipping BIGINT NOT NULL,
+  amount_tax BIGINT NOT NULL,
+  amount_total BIGINT NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX payments_shop_id_idx ON payments (shop_id);
+CREATE INDEX payments_user_id_idx ON payments (user_id);
@@ -0,0 +1,16 @@
+CREATE TABLE shipping_options (
+  shipping_option_id UUID NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  display_name VARCHAR NOT NULL,
+  currency VARCHAR NOT NULL,
+  amount BIGINT NOT NULL,
+  domestic_country VARCHAR,
+  international_amount BIGINT,
+  min_delivery_days BIGINT,
+  max_delivery_days BIGINT,
+  free_shipping_threshold BIGINT,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX shipping_options_shop_id_idx ON shipping_options (shop_id);
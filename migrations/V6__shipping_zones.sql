@@ -0,0 +1,26 @@
+CREATE TABLE shipping_zones (
+  shipping_zone_id UUID NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  name VARCHAR NOT NULL,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX shipping_zones_shop_id_idx ON shipping_zones (shop_id);
+
+CREATE TABLE shipping_zone_countries (
+  shop_id UUID NOT NULL,
+  country VARCHAR NOT NULL,
+  shipping_zone_id UUID NOT NULL REFERENCES shipping_zones (shipping_zone_id) ON DELETE CASCADE,
+  PRIMARY KEY (shop_id, country)
+);
+
+CREATE TABLE shipping_zone_rates (
+  shipping_option_id UUID NOT NULL REFERENCES shipping_options (shipping_option_id) ON DELETE CASCADE,
+  shipping_zone_id UUID NOT NULL REFERENCES shipping_zones (shipping_zone_id) ON DELETE CASCADE,
+  shop_id UUID NOT NULL,
+  amount BIGINT NOT NULL,
+  PRIMARY KEY (shipping_option_id, shipping_zone_id)
+);
+
+CREATE INDEX shipping_zone_rates_shop_id_idx ON shipping_zone_rates (shop_id);
@@ -0,0 +1,4 @@
+ALTER TABLE payments ALTER COLUMN stripe_checkout_session_id DROP NOT NULL;
+
+CREATE UNIQUE INDEX payments_free_user_id_idx ON payments (offer_id, user_id)
+    WHERE stripe_checkout_session_id IS NULL AND user_id IS NOT NULL;
@@ -0,0 +1,9 @@
+CREATE TABLE offer_settings (
+  offer_id UUID NOT NULL PRIMARY KEY,
+  shop_id UUID NOT NULL,
+  manual_capture BOOL NOT NULL DEFAULT false,
+  created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+  updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW() ON UPDATE NOW()
+);
+
+CREATE INDEX offer_settings_shop_id_idx ON offer_settings (shop_id);
@@ -0,0 +1,3 @@
+ALTER TABLE payments ADD COLUMN authorization_expires_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX payments_stripe_payment_intent_id_idx ON payments (stripe_payment_intent_id);
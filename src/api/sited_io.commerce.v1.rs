@@ -850,8 +850,30 @@ pub struct Recurring {
     pub interval_count: u32,
     #[prost(uint32, optional, tag = "3")]
     pub trial_period_days: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "4")]
+    pub setup_fee: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "5")]
+    pub billing_cycles: ::core::option::Option<u32>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PriceTier {
+    #[prost(uint32, optional, tag = "1")]
+    pub up_to: ::core::option::Option<u32>,
+    #[prost(uint32, tag = "2")]
+    pub unit_amount: u32,
+    #[prost(uint32, optional, tag = "3")]
+    pub flat_amount: ::core::option::Option<u32>,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CustomUnitAmount {
+    #[prost(uint32, optional, tag = "1")]
+    pub minimum: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "2")]
+    pub maximum: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "3")]
+    pub preset: ::core::option::Option<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Price {
     #[prost(enumeration = "Currency", tag = "1")]
     pub currency: i32,
@@ -863,12 +885,150 @@ pub struct Price {
     pub unit_amount: u32,
     #[prost(message, optional, tag = "5")]
     pub recurring: ::core::option::Option<Recurring>,
+    #[prost(enumeration = "PriceTiersMode", tag = "6")]
+    pub tiers_mode: i32,
+    #[prost(message, repeated, tag = "7")]
+    pub tiers: ::prost::alloc::vec::Vec<PriceTier>,
+    #[prost(message, optional, tag = "8")]
+    pub custom_unit_amount: ::core::option::Option<CustomUnitAmount>,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum Currency {
     Unspecified = 0,
     Eur = 1,
+    Aed = 2,
+    Afn = 3,
+    All = 4,
+    Amd = 5,
+    Ang = 6,
+    Aoa = 7,
+    Ars = 8,
+    Aud = 9,
+    Awg = 10,
+    Azn = 11,
+    Bam = 12,
+    Bbd = 13,
+    Bdt = 14,
+    Bgn = 15,
+    Bif = 16,
+    Bmd = 17,
+    Bnd = 18,
+    Bob = 19,
+    Brl = 20,
+    Bsd = 21,
+    Bwp = 22,
+    Byn = 23,
+    Bzd = 24,
+    Cad = 25,
+    Cdf = 26,
+    Chf = 27,
+    Clp = 28,
+    Cny = 29,
+    Cop = 30,
+    Crc = 31,
+    Cve = 32,
+    Czk = 33,
+    Djf = 34,
+    Dkk = 35,
+    Dop = 36,
+    Dzd = 37,
+    Egp = 39,
+    Etb = 40,
+    Fjd = 41,
+    Fkp = 42,
+    Gbp = 43,
+    Gel = 44,
+    Gip = 45,
+    Gmd = 46,
+    Gnf = 47,
+    Gtq = 48,
+    Gyd = 49,
+    Hkd = 50,
+    Hnl = 51,
+    Htg = 53,
+    Huf = 54,
+    Idr = 55,
+    Ils = 56,
+    Inr = 57,
+    Isk = 58,
+    Jmd = 59,
+    Jpy = 60,
+    Kes = 61,
+    Kgs = 62,
+    Khr = 63,
+    Kmf = 64,
+    Krw = 65,
+    Kyd = 66,
+    Kzt = 67,
+    Lak = 68,
+    Lbp = 69,
+    Lkr = 70,
+    Lrd = 71,
+    Lsl = 72,
+    Mad = 75,
+    Mdl = 76,
+    Mga = 77,
+    Mkd = 78,
+    Mmk = 79,
+    Mnt = 80,
+    Mop = 81,
+    Mur = 83,
+    Mvr = 84,
+    Mwk = 85,
+    Mxn = 86,
+    Myr = 87,
+    Mzn = 88,
+    Nad = 89,
+    Ngn = 90,
+    Nio = 91,
+    Nok = 92,
+    Npr = 93,
+    Nzd = 94,
+    Pab = 95,
+    Pen = 96,
+    Pgk = 97,
+    Php = 98,
+    Pkr = 99,
+    Pln = 100,
+    Pyg = 101,
+    Qar = 102,
+    Ron = 103,
+    Rsd = 104,
+    Rub = 105,
+    Rwf = 106,
+    Sar = 107,
+    Sbd = 108,
+    Scr = 109,
+    Sek = 110,
+    Sgd = 111,
+    Shp = 112,
+    Sos = 114,
+    Srd = 115,
+    Svc = 117,
+    Szl = 118,
+    Thb = 119,
+    Tjs = 120,
+    Top = 121,
+    Try = 122,
+    Ttd = 123,
+    Twd = 124,
+    Tzs = 125,
+    Uah = 126,
+    Ugx = 127,
+    Usd = 128,
+    Uyu = 129,
+    Uzs = 130,
+    Vnd = 132,
+    Vuv = 133,
+    Wst = 134,
+    Xaf = 135,
+    Xcd = 136,
+    Xof = 137,
+    Xpf = 138,
+    Yer = 139,
+    Zar = 140,
+    Zmw = 141,
 }
 impl Currency {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -879,6 +1039,138 @@ impl Currency {
         match self {
             Currency::Unspecified => "CURRENCY_UNSPECIFIED",
             Currency::Eur => "CURRENCY_EUR",
+            Currency::Aed => "CURRENCY_AED",
+            Currency::Afn => "CURRENCY_AFN",
+            Currency::All => "CURRENCY_ALL",
+            Currency::Amd => "CURRENCY_AMD",
+            Currency::Ang => "CURRENCY_ANG",
+            Currency::Aoa => "CURRENCY_AOA",
+            Currency::Ars => "CURRENCY_ARS",
+            Currency::Aud => "CURRENCY_AUD",
+            Currency::Awg => "CURRENCY_AWG",
+            Currency::Azn => "CURRENCY_AZN",
+            Currency::Bam => "CURRENCY_BAM",
+            Currency::Bbd => "CURRENCY_BBD",
+            Currency::Bdt => "CURRENCY_BDT",
+            Currency::Bgn => "CURRENCY_BGN",
+            Currency::Bif => "CURRENCY_BIF",
+            Currency::Bmd => "CURRENCY_BMD",
+            Currency::Bnd => "CURRENCY_BND",
+            Currency::Bob => "CURRENCY_BOB",
+            Currency::Brl => "CURRENCY_BRL",
+            Currency::Bsd => "CURRENCY_BSD",
+            Currency::Bwp => "CURRENCY_BWP",
+            Currency::Byn => "CURRENCY_BYN",
+            Currency::Bzd => "CURRENCY_BZD",
+            Currency::Cad => "CURRENCY_CAD",
+            Currency::Cdf => "CURRENCY_CDF",
+            Currency::Chf => "CURRENCY_CHF",
+            Currency::Clp => "CURRENCY_CLP",
+            Currency::Cny => "CURRENCY_CNY",
+            Currency::Cop => "CURRENCY_COP",
+            Currency::Crc => "CURRENCY_CRC",
+            Currency::Cve => "CURRENCY_CVE",
+            Currency::Czk => "CURRENCY_CZK",
+            Currency::Djf => "CURRENCY_DJF",
+            Currency::Dkk => "CURRENCY_DKK",
+            Currency::Dop => "CURRENCY_DOP",
+            Currency::Dzd => "CURRENCY_DZD",
+            Currency::Egp => "CURRENCY_EGP",
+            Currency::Etb => "CURRENCY_ETB",
+            Currency::Fjd => "CURRENCY_FJD",
+            Currency::Fkp => "CURRENCY_FKP",
+            Currency::Gbp => "CURRENCY_GBP",
+            Currency::Gel => "CURRENCY_GEL",
+            Currency::Gip => "CURRENCY_GIP",
+            Currency::Gmd => "CURRENCY_GMD",
+            Currency::Gnf => "CURRENCY_GNF",
+            Currency::Gtq => "CURRENCY_GTQ",
+            Currency::Gyd => "CURRENCY_GYD",
+            Currency::Hkd => "CURRENCY_HKD",
+            Currency::Hnl => "CURRENCY_HNL",
+            Currency::Htg => "CURRENCY_HTG",
+            Currency::Huf => "CURRENCY_HUF",
+            Currency::Idr => "CURRENCY_IDR",
+            Currency::Ils => "CURRENCY_ILS",
+            Currency::Inr => "CURRENCY_INR",
+            Currency::Isk => "CURRENCY_ISK",
+            Currency::Jmd => "CURRENCY_JMD",
+            Currency::Jpy => "CURRENCY_JPY",
+            Currency::Kes => "CURRENCY_KES",
+            Currency::Kgs => "CURRENCY_KGS",
+            Currency::Khr => "CURRENCY_KHR",
+            Currency::Kmf => "CURRENCY_KMF",
+            Currency::Krw => "CURRENCY_KRW",
+            Currency::Kyd => "CURRENCY_KYD",
+            Currency::Kzt => "CURRENCY_KZT",
+            Currency::Lak => "CURRENCY_LAK",
+            Currency::Lbp => "CURRENCY_LBP",
+            Currency::Lkr => "CURRENCY_LKR",
+            Currency::Lrd => "CURRENCY_LRD",
+            Currency::Lsl => "CURRENCY_LSL",
+            Currency::Mad => "CURRENCY_MAD",
+            Currency::Mdl => "CURRENCY_MDL",
+            Currency::Mga => "CURRENCY_MGA",
+            Currency::Mkd => "CURRENCY_MKD",
+            Currency::Mmk => "CURRENCY_MMK",
+            Currency::Mnt => "CURRENCY_MNT",
+            Currency::Mop => "CURRENCY_MOP",
+            Currency::Mur => "CURRENCY_MUR",
+            Currency::Mvr => "CURRENCY_MVR",
+            Currency::Mwk => "CURRENCY_MWK",
+            Currency::Mxn => "CURRENCY_MXN",
+            Currency::Myr => "CURRENCY_MYR",
+            Currency::Mzn => "CURRENCY_MZN",
+            Currency::Nad => "CURRENCY_NAD",
+            Currency::Ngn => "CURRENCY_NGN",
+            Currency::Nio => "CURRENCY_NIO",
+            Currency::Nok => "CURRENCY_NOK",
+            Currency::Npr => "CURRENCY_NPR",
+            Currency::Nzd => "CURRENCY_NZD",
+            Currency::Pab => "CURRENCY_PAB",
+            Currency::Pen => "CURRENCY_PEN",
+            Currency::Pgk => "CURRENCY_PGK",
+            Currency::Php => "CURRENCY_PHP",
+            Currency::Pkr => "CURRENCY_PKR",
+            Currency::Pln => "CURRENCY_PLN",
+            Currency::Pyg => "CURRENCY_PYG",
+            Currency::Qar => "CURRENCY_QAR",
+            Currency::Ron => "CURRENCY_RON",
+            Currency::Rsd => "CURRENCY_RSD",
+            Currency::Rub => "CURRENCY_RUB",
+            Currency::Rwf => "CURRENCY_RWF",
+            Currency::Sar => "CURRENCY_SAR",
+            Currency::Sbd => "CURRENCY_SBD",
+            Currency::Scr => "CURRENCY_SCR",
+            Currency::Sek => "CURRENCY_SEK",
+            Currency::Sgd => "CURRENCY_SGD",
+            Currency::Shp => "CURRENCY_SHP",
+            Currency::Sos => "CURRENCY_SOS",
+            Currency::Srd => "CURRENCY_SRD",
+            Currency::Svc => "CURRENCY_SVC",
+            Currency::Szl => "CURRENCY_SZL",
+            Currency::Thb => "CURRENCY_THB",
+            Currency::Tjs => "CURRENCY_TJS",
+            Currency::Top => "CURRENCY_TOP",
+            Currency::Try => "CURRENCY_TRY",
+            Currency::Ttd => "CURRENCY_TTD",
+            Currency::Twd => "CURRENCY_TWD",
+            Currency::Tzs => "CURRENCY_TZS",
+            Currency::Uah => "CURRENCY_UAH",
+            Currency::Ugx => "CURRENCY_UGX",
+            Currency::Usd => "CURRENCY_USD",
+            Currency::Uyu => "CURRENCY_UYU",
+            Currency::Uzs => "CURRENCY_UZS",
+            Currency::Vnd => "CURRENCY_VND",
+            Currency::Vuv => "CURRENCY_VUV",
+            Currency::Wst => "CURRENCY_WST",
+            Currency::Xaf => "CURRENCY_XAF",
+            Currency::Xcd => "CURRENCY_XCD",
+            Currency::Xof => "CURRENCY_XOF",
+            Currency::Xpf => "CURRENCY_XPF",
+            Currency::Yer => "CURRENCY_YER",
+            Currency::Zar => "CURRENCY_ZAR",
+            Currency::Zmw => "CURRENCY_ZMW",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -886,6 +1178,138 @@ impl Currency {
         match value {
             "CURRENCY_UNSPECIFIED" => Some(Self::Unspecified),
             "CURRENCY_EUR" => Some(Self::Eur),
+            "CURRENCY_AED" => Some(Self::Aed),
+            "CURRENCY_AFN" => Some(Self::Afn),
+            "CURRENCY_ALL" => Some(Self::All),
+            "CURRENCY_AMD" => Some(Self::Amd),
+            "CURRENCY_ANG" => Some(Self::Ang),
+            "CURRENCY_AOA" => Some(Self::Aoa),
+            "CURRENCY_ARS" => Some(Self::Ars),
+            "CURRENCY_AUD" => Some(Self::Aud),
+            "CURRENCY_AWG" => Some(Self::Awg),
+            "CURRENCY_AZN" => Some(Self::Azn),
+            "CURRENCY_BAM" => Some(Self::Bam),
+            "CURRENCY_BBD" => Some(Self::Bbd),
+            "CURRENCY_BDT" => Some(Self::Bdt),
+            "CURRENCY_BGN" => Some(Self::Bgn),
+            "CURRENCY_BIF" => Some(Self::Bif),
+            "CURRENCY_BMD" => Some(Self::Bmd),
+            "CURRENCY_BND" => Some(Self::Bnd),
+            "CURRENCY_BOB" => Some(Self::Bob),
+            "CURRENCY_BRL" => Some(Self::Brl),
+            "CURRENCY_BSD" => Some(Self::Bsd),
+            "CURRENCY_BWP" => Some(Self::Bwp),
+            "CURRENCY_BYN" => Some(Self::Byn),
+            "CURRENCY_BZD" => Some(Self::Bzd),
+            "CURRENCY_CAD" => Some(Self::Cad),
+            "CURRENCY_CDF" => Some(Self::Cdf),
+            "CURRENCY_CHF" => Some(Self::Chf),
+            "CURRENCY_CLP" => Some(Self::Clp),
+            "CURRENCY_CNY" => Some(Self::Cny),
+            "CURRENCY_COP" => Some(Self::Cop),
+            "CURRENCY_CRC" => Some(Self::Crc),
+            "CURRENCY_CVE" => Some(Self::Cve),
+            "CURRENCY_CZK" => Some(Self::Czk),
+            "CURRENCY_DJF" => Some(Self::Djf),
+            "CURRENCY_DKK" => Some(Self::Dkk),
+            "CURRENCY_DOP" => Some(Self::Dop),
+            "CURRENCY_DZD" => Some(Self::Dzd),
+            "CURRENCY_EGP" => Some(Self::Egp),
+            "CURRENCY_ETB" => Some(Self::Etb),
+            "CURRENCY_FJD" => Some(Self::Fjd),
+            "CURRENCY_FKP" => Some(Self::Fkp),
+            "CURRENCY_GBP" => Some(Self::Gbp),
+            "CURRENCY_GEL" => Some(Self::Gel),
+            "CURRENCY_GIP" => Some(Self::Gip),
+            "CURRENCY_GMD" => Some(Self::Gmd),
+            "CURRENCY_GNF" => Some(Self::Gnf),
+            "CURRENCY_GTQ" => Some(Self::Gtq),
+            "CURRENCY_GYD" => Some(Self::Gyd),
+            "CURRENCY_HKD" => Some(Self::Hkd),
+            "CURRENCY_HNL" => Some(Self::Hnl),
+            "CURRENCY_HTG" => Some(Self::Htg),
+            "CURRENCY_HUF" => Some(Self::Huf),
+            "CURRENCY_IDR" => Some(Self::Idr),
+            "CURRENCY_ILS" => Some(Self::Ils),
+            "CURRENCY_INR" => Some(Self::Inr),
+            "CURRENCY_ISK" => Some(Self::Isk),
+            "CURRENCY_JMD" => Some(Self::Jmd),
+            "CURRENCY_JPY" => Some(Self::Jpy),
+            "CURRENCY_KES" => Some(Self::Kes),
+            "CURRENCY_KGS" => Some(Self::Kgs),
+            "CURRENCY_KHR" => Some(Self::Khr),
+            "CURRENCY_KMF" => Some(Self::Kmf),
+            "CURRENCY_KRW" => Some(Self::Krw),
+            "CURRENCY_KYD" => Some(Self::Kyd),
+            "CURRENCY_KZT" => Some(Self::Kzt),
+            "CURRENCY_LAK" => Some(Self::Lak),
+            "CURRENCY_LBP" => Some(Self::Lbp),
+            "CURRENCY_LKR" => Some(Self::Lkr),
+            "CURRENCY_LRD" => Some(Self::Lrd),
+            "CURRENCY_LSL" => Some(Self::Lsl),
+            "CURRENCY_MAD" => Some(Self::Mad),
+            "CURRENCY_MDL" => Some(Self::Mdl),
+            "CURRENCY_MGA" => Some(Self::Mga),
+            "CURRENCY_MKD" => Some(Self::Mkd),
+            "CURRENCY_MMK" => Some(Self::Mmk),
+            "CURRENCY_MNT" => Some(Self::Mnt),
+            "CURRENCY_MOP" => Some(Self::Mop),
+            "CURRENCY_MUR" => Some(Self::Mur),
+            "CURRENCY_MVR" => Some(Self::Mvr),
+            "CURRENCY_MWK" => Some(Self::Mwk),
+            "CURRENCY_MXN" => Some(Self::Mxn),
+            "CURRENCY_MYR" => Some(Self::Myr),
+            "CURRENCY_MZN" => Some(Self::Mzn),
+            "CURRENCY_NAD" => Some(Self::Nad),
+            "CURRENCY_NGN" => Some(Self::Ngn),
+            "CURRENCY_NIO" => Some(Self::Nio),
+            "CURRENCY_NOK" => Some(Self::Nok),
+            "CURRENCY_NPR" => Some(Self::Npr),
+            "CURRENCY_NZD" => Some(Self::Nzd),
+            "CURRENCY_PAB" => Some(Self::Pab),
+            "CURRENCY_PEN" => Some(Self::Pen),
+            "CURRENCY_PGK" => Some(Self::Pgk),
+            "CURRENCY_PHP" => Some(Self::Php),
+            "CURRENCY_PKR" => Some(Self::Pkr),
+            "CURRENCY_PLN" => Some(Self::Pln),
+            "CURRENCY_PYG" => Some(Self::Pyg),
+            "CURRENCY_QAR" => Some(Self::Qar),
+            "CURRENCY_RON" => Some(Self::Ron),
+            "CURRENCY_RSD" => Some(Self::Rsd),
+            "CURRENCY_RUB" => Some(Self::Rub),
+            "CURRENCY_RWF" => Some(Self::Rwf),
+            "CURRENCY_SAR" => Some(Self::Sar),
+            "CURRENCY_SBD" => Some(Self::Sbd),
+            "CURRENCY_SCR" => Some(Self::Scr),
+            "CURRENCY_SEK" => Some(Self::Sek),
+            "CURRENCY_SGD" => Some(Self::Sgd),
+            "CURRENCY_SHP" => Some(Self::Shp),
+            "CURRENCY_SOS" => Some(Self::Sos),
+            "CURRENCY_SRD" => Some(Self::Srd),
+            "CURRENCY_SVC" => Some(Self::Svc),
+            "CURRENCY_SZL" => Some(Self::Szl),
+            "CURRENCY_THB" => Some(Self::Thb),
+            "CURRENCY_TJS" => Some(Self::Tjs),
+            "CURRENCY_TOP" => Some(Self::Top),
+            "CURRENCY_TRY" => Some(Self::Try),
+            "CURRENCY_TTD" => Some(Self::Ttd),
+            "CURRENCY_TWD" => Some(Self::Twd),
+            "CURRENCY_TZS" => Some(Self::Tzs),
+            "CURRENCY_UAH" => Some(Self::Uah),
+            "CURRENCY_UGX" => Some(Self::Ugx),
+            "CURRENCY_USD" => Some(Self::Usd),
+            "CURRENCY_UYU" => Some(Self::Uyu),
+            "CURRENCY_UZS" => Some(Self::Uzs),
+            "CURRENCY_VND" => Some(Self::Vnd),
+            "CURRENCY_VUV" => Some(Self::Vuv),
+            "CURRENCY_WST" => Some(Self::Wst),
+            "CURRENCY_XAF" => Some(Self::Xaf),
+            "CURRENCY_XCD" => Some(Self::Xcd),
+            "CURRENCY_XOF" => Some(Self::Xof),
+            "CURRENCY_XPF" => Some(Self::Xpf),
+            "CURRENCY_YER" => Some(Self::Yer),
+            "CURRENCY_ZAR" => Some(Self::Zar),
+            "CURRENCY_ZMW" => Some(Self::Zmw),
             _ => None,
         }
     }
@@ -924,6 +1348,7 @@ impl PriceType {
 pub enum PriceBillingScheme {
     Unspecified = 0,
     PerUnit = 1,
+    Tiered = 2,
 }
 impl PriceBillingScheme {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -934,6 +1359,7 @@ impl PriceBillingScheme {
         match self {
             PriceBillingScheme::Unspecified => "PRICE_BILLING_SCHEME_UNSPECIFIED",
             PriceBillingScheme::PerUnit => "PRICE_BILLING_SCHEME_PER_UNIT",
+            PriceBillingScheme::Tiered => "PRICE_BILLING_SCHEME_TIERED",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -941,6 +1367,36 @@ impl PriceBillingScheme {
         match value {
             "PRICE_BILLING_SCHEME_UNSPECIFIED" => Some(Self::Unspecified),
             "PRICE_BILLING_SCHEME_PER_UNIT" => Some(Self::PerUnit),
+            "PRICE_BILLING_SCHEME_TIERED" => Some(Self::Tiered),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PriceTiersMode {
+    Unspecified = 0,
+    Graduated = 1,
+    Volume = 2,
+}
+impl PriceTiersMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PriceTiersMode::Unspecified => "PRICE_TIERS_MODE_UNSPECIFIED",
+            PriceTiersMode::Graduated => "PRICE_TIERS_MODE_GRADUATED",
+            PriceTiersMode::Volume => "PRICE_TIERS_MODE_VOLUME",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PRICE_TIERS_MODE_UNSPECIFIED" => Some(Self::Unspecified),
+            "PRICE_TIERS_MODE_GRADUATED" => Some(Self::Graduated),
+            "PRICE_TIERS_MODE_VOLUME" => Some(Self::Volume),
             _ => None,
         }
     }
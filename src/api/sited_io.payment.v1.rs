@@ -1,164 +1,217 @@
 // This file is @generated by prost-build.
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct StripeAccount {
-    #[prost(string, tag = "1")]
-    pub shop_id: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
-    pub stripe_account_id: ::prost::alloc::string::String,
-    #[prost(bool, tag = "3")]
-    pub enabled: bool,
-}
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct StripeAccountDetails {
-    #[prost(bool, tag = "1")]
-    pub charges_enabled: bool,
-    #[prost(bool, tag = "2")]
-    pub details_submitted: bool,
+pub struct ShippingAddress {
+    #[prost(string, optional, tag = "1")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "2")]
+    pub line1: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub line2: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub postal_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub city: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub state: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "7")]
+    pub country: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateAccountRequest {
+pub struct OrderResponse {
     #[prost(string, tag = "1")]
+    pub order_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub payment_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
     pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "OrderStatus", tag = "5")]
+    pub status: i32,
+    #[prost(message, optional, tag = "6")]
+    pub shipping_address: ::core::option::Option<ShippingAddress>,
+    #[prost(string, optional, tag = "7")]
+    pub carrier: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "8")]
+    pub tracking_number: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, tag = "9")]
+    pub created_at: i64,
+    #[prost(int64, tag = "10")]
+    pub updated_at: i64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateAccountResponse {
-    #[prost(message, optional, tag = "1")]
-    pub account: ::core::option::Option<StripeAccount>,
+pub struct OrderEventResponse {
+    #[prost(string, tag = "1")]
+    pub order_event_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "OrderStatus", tag = "2")]
+    pub from_status: i32,
+    #[prost(enumeration = "OrderStatus", tag = "3")]
+    pub to_status: i32,
+    #[prost(int64, tag = "4")]
+    pub created_at: i64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateAccountLinkRequest {
+pub struct ListOrdersRequest {
     #[prost(string, tag = "1")]
     pub shop_id: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
-    pub refresh_url: ::prost::alloc::string::String,
-    #[prost(string, tag = "3")]
-    pub return_url: ::prost::alloc::string::String,
+    #[prost(enumeration = "OrderStatus", optional, tag = "2")]
+    pub status: ::core::option::Option<i32>,
+    #[prost(uint64, optional, tag = "3")]
+    pub limit: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub offset: ::core::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateAccountLinkResponse {
-    #[prost(string, tag = "1")]
-    pub link: ::prost::alloc::string::String,
+pub struct ListOrdersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub orders: ::prost::alloc::vec::Vec<OrderResponse>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetAccountRequest {
+pub struct GetOrderRequest {
     #[prost(string, tag = "1")]
     pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub order_id: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetAccountResponse {
+pub struct GetOrderResponse {
     #[prost(message, optional, tag = "1")]
-    pub account: ::core::option::Option<StripeAccount>,
+    pub order: ::core::option::Option<OrderResponse>,
+    #[prost(message, repeated, tag = "2")]
+    pub events: ::prost::alloc::vec::Vec<OrderEventResponse>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetAccountDetailsRequest {
+pub struct UpdateOrderStatusRequest {
     #[prost(string, tag = "1")]
     pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub order_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "OrderStatus", tag = "3")]
+    pub status: i32,
+    #[prost(string, optional, tag = "4")]
+    pub carrier: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub tracking_number: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GetAccountDetailsResponse {
+pub struct UpdateOrderStatusResponse {
     #[prost(message, optional, tag = "1")]
-    pub account: ::core::option::Option<StripeAccount>,
-    #[prost(message, optional, tag = "2")]
-    pub details: ::core::option::Option<StripeAccountDetails>,
+    pub order: ::core::option::Option<OrderResponse>,
 }
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateCheckoutSessionRequest {
-    #[prost(string, tag = "1")]
-    pub offer_id: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
-    pub success_url: ::prost::alloc::string::String,
-    #[prost(string, tag = "3")]
-    pub cancel_url: ::prost::alloc::string::String,
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListPurchasedOrdersRequest {
+    #[prost(uint64, optional, tag = "1")]
+    pub limit: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub offset: ::core::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CreateCheckoutSessionResponse {
-    #[prost(string, tag = "1")]
-    pub link: ::prost::alloc::string::String,
+pub struct ListPurchasedOrdersResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub orders: ::prost::alloc::vec::Vec<OrderResponse>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CancelSubscriptionRequest {
+pub struct GetPurchasedOrderRequest {
     #[prost(string, tag = "1")]
-    pub stripe_subscription_id: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
-    pub shop_id: ::prost::alloc::string::String,
+    pub order_id: ::prost::alloc::string::String,
 }
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct CancelSubscriptionResponse {}
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ResumeSubscriptionRequest {
-    #[prost(string, tag = "1")]
-    pub stripe_subscription_id: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
-    pub shop_id: ::prost::alloc::string::String,
+pub struct GetPurchasedOrderResponse {
+    #[prost(message, optional, tag = "1")]
+    pub order: ::core::option::Option<OrderResponse>,
+    #[prost(message, repeated, tag = "2")]
+    pub events: ::prost::alloc::vec::Vec<OrderEventResponse>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum OrderStatus {
+    Unspecified = 0,
+    Paid = 1,
+    Processing = 2,
+    Shipped = 3,
+    Delivered = 4,
+    Returned = 5,
+}
+impl OrderStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            OrderStatus::Unspecified => "ORDER_STATUS_UNSPECIFIED",
+            OrderStatus::Paid => "ORDER_STATUS_PAID",
+            OrderStatus::Processing => "ORDER_STATUS_PROCESSING",
+            OrderStatus::Shipped => "ORDER_STATUS_SHIPPED",
+            OrderStatus::Delivered => "ORDER_STATUS_DELIVERED",
+            OrderStatus::Returned => "ORDER_STATUS_RETURNED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ORDER_STATUS_UNSPECIFIED" => Some(Self::Unspecified),
+            "ORDER_STATUS_PAID" => Some(Self::Paid),
+            "ORDER_STATUS_PROCESSING" => Some(Self::Processing),
+            "ORDER_STATUS_SHIPPED" => Some(Self::Shipped),
+            "ORDER_STATUS_DELIVERED" => Some(Self::Delivered),
+            "ORDER_STATUS_RETURNED" => Some(Self::Returned),
+            _ => None,
+        }
+    }
 }
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct ResumeSubscriptionResponse {}
 /// Generated server implementations.
-pub mod stripe_service_server {
+pub mod order_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with StripeServiceServer.
+    /// Generated trait containing gRPC methods that should be implemented for use with OrderServiceServer.
     #[async_trait]
-    pub trait StripeService: std::marker::Send + std::marker::Sync + 'static {
-        async fn create_account(
-            &self,
-            request: tonic::Request<super::CreateAccountRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CreateAccountResponse>,
-            tonic::Status,
-        >;
-        async fn create_account_link(
-            &self,
-            request: tonic::Request<super::CreateAccountLinkRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::CreateAccountLinkResponse>,
-            tonic::Status,
-        >;
-        async fn get_account(
+    pub trait OrderService: std::marker::Send + std::marker::Sync + 'static {
+        async fn list_orders(
             &self,
-            request: tonic::Request<super::GetAccountRequest>,
+            request: tonic::Request<super::ListOrdersRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetAccountResponse>,
+            tonic::Response<super::ListOrdersResponse>,
             tonic::Status,
         >;
-        async fn get_account_details(
+        async fn get_order(
             &self,
-            request: tonic::Request<super::GetAccountDetailsRequest>,
+            request: tonic::Request<super::GetOrderRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::GetAccountDetailsResponse>,
+            tonic::Response<super::GetOrderResponse>,
             tonic::Status,
         >;
-        async fn create_checkout_session(
+        async fn update_order_status(
             &self,
-            request: tonic::Request<super::CreateCheckoutSessionRequest>,
+            request: tonic::Request<super::UpdateOrderStatusRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::CreateCheckoutSessionResponse>,
+            tonic::Response<super::UpdateOrderStatusResponse>,
             tonic::Status,
         >;
-        async fn cancel_subscription(
+        async fn list_purchased_orders(
             &self,
-            request: tonic::Request<super::CancelSubscriptionRequest>,
+            request: tonic::Request<super::ListPurchasedOrdersRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::CancelSubscriptionResponse>,
+            tonic::Response<super::ListPurchasedOrdersResponse>,
             tonic::Status,
         >;
-        async fn resume_subscription(
+        async fn get_purchased_order(
             &self,
-            request: tonic::Request<super::ResumeSubscriptionRequest>,
+            request: tonic::Request<super::GetPurchasedOrderRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ResumeSubscriptionResponse>,
+            tonic::Response<super::GetPurchasedOrderResponse>,
             tonic::Status,
         >;
     }
     #[derive(Debug)]
-    pub struct StripeServiceServer<T> {
+    pub struct OrderServiceServer<T> {
         inner: Arc<T>,
         accept_compression_encodings: EnabledCompressionEncodings,
         send_compression_encodings: EnabledCompressionEncodings,
         max_decoding_message_size: Option<usize>,
         max_encoding_message_size: Option<usize>,
     }
-    impl<T> StripeServiceServer<T> {
+    impl<T> OrderServiceServer<T> {
         pub fn new(inner: T) -> Self {
             Self::from_arc(Arc::new(inner))
         }
@@ -209,9 +262,9 @@ pub mod stripe_service_server {
             self
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for StripeServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for OrderServiceServer<T>
     where
-        T: StripeService,
+        T: OrderService,
         B: Body + std::marker::Send + 'static,
         B::Error: Into<StdError> + std::marker::Send + 'static,
     {
@@ -226,116 +279,25 @@ pub mod stripe_service_server {
         }
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             match req.uri().path() {
-                "/sited_io.payment.v1.StripeService/CreateAccount" => {
-                    #[allow(non_camel_case_types)]
-                    struct CreateAccountSvc<T: StripeService>(pub Arc<T>);
-                    impl<
-                        T: StripeService,
-                    > tonic::server::UnaryService<super::CreateAccountRequest>
-                    for CreateAccountSvc<T> {
-                        type Response = super::CreateAccountResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::CreateAccountRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as StripeService>::create_account(&inner, request).await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let method = CreateAccountSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/sited_io.payment.v1.StripeService/CreateAccountLink" => {
-                    #[allow(non_camel_case_types)]
-                    struct CreateAccountLinkSvc<T: StripeService>(pub Arc<T>);
-                    impl<
-                        T: StripeService,
-                    > tonic::server::UnaryService<super::CreateAccountLinkRequest>
-                    for CreateAccountLinkSvc<T> {
-                        type Response = super::CreateAccountLinkResponse;
-                        type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
-                            tonic::Status,
-                        >;
-                        fn call(
-                            &mut self,
-                            request: tonic::Request<super::CreateAccountLinkRequest>,
-                        ) -> Self::Future {
-                            let inner = Arc::clone(&self.0);
-                            let fut = async move {
-                                <T as StripeService>::create_account_link(&inner, request)
-                                    .await
-                            };
-                            Box::pin(fut)
-                        }
-                    }
-                    let accept_compression_encodings = self.accept_compression_encodings;
-                    let send_compression_encodings = self.send_compression_encodings;
-                    let max_decoding_message_size = self.max_decoding_message_size;
-                    let max_encoding_message_size = self.max_encoding_message_size;
-                    let inner = self.inner.clone();
-                    let fut = async move {
-                        let method = CreateAccountLinkSvc(inner);
-                        let codec = tonic::codec::ProstCodec::default();
-                        let mut grpc = tonic::server::Grpc::new(codec)
-                            .apply_compression_config(
-                                accept_compression_encodings,
-                                send_compression_encodings,
-                            )
-                            .apply_max_message_size_config(
-                                max_decoding_message_size,
-                                max_encoding_message_size,
-                            );
-                        let res = grpc.unary(method, req).await;
-                        Ok(res)
-                    };
-                    Box::pin(fut)
-                }
-                "/sited_io.payment.v1.StripeService/GetAccount" => {
+                "/sited_io.payment.v1.OrderService/ListOrders" => {
                     #[allow(non_camel_case_types)]
-                    struct GetAccountSvc<T: StripeService>(pub Arc<T>);
+                    struct ListOrdersSvc<T: OrderService>(pub Arc<T>);
                     impl<
-                        T: StripeService,
-                    > tonic::server::UnaryService<super::GetAccountRequest>
-                    for GetAccountSvc<T> {
-                        type Response = super::GetAccountResponse;
+                        T: OrderService,
+                    > tonic::server::UnaryService<super::ListOrdersRequest>
+                    for ListOrdersSvc<T> {
+                        type Response = super::ListOrdersResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetAccountRequest>,
+                            request: tonic::Request<super::ListOrdersRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StripeService>::get_account(&inner, request).await
+                                <T as OrderService>::list_orders(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -346,7 +308,7 @@ pub mod stripe_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetAccountSvc(inner);
+                        let method = ListOrdersSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -362,26 +324,25 @@ pub mod stripe_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sited_io.payment.v1.StripeService/GetAccountDetails" => {
+                "/sited_io.payment.v1.OrderService/GetOrder" => {
                     #[allow(non_camel_case_types)]
-                    struct GetAccountDetailsSvc<T: StripeService>(pub Arc<T>);
+                    struct GetOrderSvc<T: OrderService>(pub Arc<T>);
                     impl<
-                        T: StripeService,
-                    > tonic::server::UnaryService<super::GetAccountDetailsRequest>
-                    for GetAccountDetailsSvc<T> {
-                        type Response = super::GetAccountDetailsResponse;
+                        T: OrderService,
+                    > tonic::server::UnaryService<super::GetOrderRequest>
+                    for GetOrderSvc<T> {
+                        type Response = super::GetOrderResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetAccountDetailsRequest>,
+                            request: tonic::Request<super::GetOrderRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StripeService>::get_account_details(&inner, request)
-                                    .await
+                                <T as OrderService>::get_order(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -392,7 +353,7 @@ pub mod stripe_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = GetAccountDetailsSvc(inner);
+                        let method = GetOrderSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -408,28 +369,25 @@ pub mod stripe_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sited_io.payment.v1.StripeService/CreateCheckoutSession" => {
+                "/sited_io.payment.v1.OrderService/UpdateOrderStatus" => {
                     #[allow(non_camel_case_types)]
-                    struct CreateCheckoutSessionSvc<T: StripeService>(pub Arc<T>);
+                    struct UpdateOrderStatusSvc<T: OrderService>(pub Arc<T>);
                     impl<
-                        T: StripeService,
-                    > tonic::server::UnaryService<super::CreateCheckoutSessionRequest>
-                    for CreateCheckoutSessionSvc<T> {
-                        type Response = super::CreateCheckoutSessionResponse;
+                        T: OrderService,
+                    > tonic::server::UnaryService<super::UpdateOrderStatusRequest>
+                    for UpdateOrderStatusSvc<T> {
+                        type Response = super::UpdateOrderStatusResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CreateCheckoutSessionRequest>,
+                            request: tonic::Request<super::UpdateOrderStatusRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StripeService>::create_checkout_session(
-                                        &inner,
-                                        request,
-                                    )
+                                <T as OrderService>::update_order_status(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -441,7 +399,7 @@ pub mod stripe_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = CreateCheckoutSessionSvc(inner);
+                        let method = UpdateOrderStatusSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -457,25 +415,25 @@ pub mod stripe_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sited_io.payment.v1.StripeService/CancelSubscription" => {
+                "/sited_io.payment.v1.OrderService/ListPurchasedOrders" => {
                     #[allow(non_camel_case_types)]
-                    struct CancelSubscriptionSvc<T: StripeService>(pub Arc<T>);
+                    struct ListPurchasedOrdersSvc<T: OrderService>(pub Arc<T>);
                     impl<
-                        T: StripeService,
-                    > tonic::server::UnaryService<super::CancelSubscriptionRequest>
-                    for CancelSubscriptionSvc<T> {
-                        type Response = super::CancelSubscriptionResponse;
+                        T: OrderService,
+                    > tonic::server::UnaryService<super::ListPurchasedOrdersRequest>
+                    for ListPurchasedOrdersSvc<T> {
+                        type Response = super::ListPurchasedOrdersResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CancelSubscriptionRequest>,
+                            request: tonic::Request<super::ListPurchasedOrdersRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StripeService>::cancel_subscription(&inner, request)
+                                <T as OrderService>::list_purchased_orders(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -487,7 +445,7 @@ pub mod stripe_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = CancelSubscriptionSvc(inner);
+                        let method = ListPurchasedOrdersSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -503,25 +461,25 @@ pub mod stripe_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/sited_io.payment.v1.StripeService/ResumeSubscription" => {
+                "/sited_io.payment.v1.OrderService/GetPurchasedOrder" => {
                     #[allow(non_camel_case_types)]
-                    struct ResumeSubscriptionSvc<T: StripeService>(pub Arc<T>);
+                    struct GetPurchasedOrderSvc<T: OrderService>(pub Arc<T>);
                     impl<
-                        T: StripeService,
-                    > tonic::server::UnaryService<super::ResumeSubscriptionRequest>
-                    for ResumeSubscriptionSvc<T> {
-                        type Response = super::ResumeSubscriptionResponse;
+                        T: OrderService,
+                    > tonic::server::UnaryService<super::GetPurchasedOrderRequest>
+                    for GetPurchasedOrderSvc<T> {
+                        type Response = super::GetPurchasedOrderResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ResumeSubscriptionRequest>,
+                            request: tonic::Request<super::GetPurchasedOrderRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StripeService>::resume_subscription(&inner, request)
+                                <T as OrderService>::get_purchased_order(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -533,7 +491,7 @@ pub mod stripe_service_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = ResumeSubscriptionSvc(inner);
+                        let method = GetPurchasedOrderSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -567,7 +525,7 @@ pub mod stripe_service_server {
             }
         }
     }
-    impl<T> Clone for StripeServiceServer<T> {
+    impl<T> Clone for OrderServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -580,8 +538,4936 @@ pub mod stripe_service_server {
         }
     }
     /// Generated gRPC service name
-    pub const SERVICE_NAME: &str = "sited_io.payment.v1.StripeService";
-    impl<T> tonic::server::NamedService for StripeServiceServer<T> {
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.OrderService";
+    impl<T> tonic::server::NamedService for OrderServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StripeAccount {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub stripe_account_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub enabled: bool,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StripeAccountDetails {
+    #[prost(bool, tag = "1")]
+    pub charges_enabled: bool,
+    #[prost(bool, tag = "2")]
+    pub details_submitted: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateAccountRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateAccountResponse {
+    #[prost(message, optional, tag = "1")]
+    pub account: ::core::option::Option<StripeAccount>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateAccountLinkRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub refresh_url: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub return_url: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateAccountLinkResponse {
+    #[prost(string, tag = "1")]
+    pub link: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountResponse {
+    #[prost(message, optional, tag = "1")]
+    pub account: ::core::option::Option<StripeAccount>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountDetailsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAccountDetailsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub account: ::core::option::Option<StripeAccount>,
+    #[prost(message, optional, tag = "2")]
+    pub details: ::core::option::Option<StripeAccountDetails>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateCheckoutSessionRequest {
+    #[prost(string, tag = "1")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub success_url: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub cancel_url: ::prost::alloc::string::String,
+    #[prost(bool, optional, tag = "4")]
+    pub allow_promotion_codes: ::core::option::Option<bool>,
+    #[prost(string, optional, tag = "5")]
+    pub promotion_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub shipping_country: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag = "7")]
+    pub quantity: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "8")]
+    pub shipping_address: ::core::option::Option<ShippingAddress>,
+    #[prost(string, optional, tag = "9")]
+    pub buyer_email: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateCheckoutSessionResponse {
+    #[prost(string, tag = "1")]
+    pub link: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSubscriptionRequest {
+    #[prost(string, tag = "1")]
+    pub stripe_subscription_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "CancellationMode", tag = "3")]
+    pub cancellation_mode: i32,
+    #[prost(enumeration = "RefundPolicy", tag = "4")]
+    pub refund_policy: i32,
+    #[prost(string, optional, tag = "5")]
+    pub reason: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CancelSubscriptionResponse {
+    #[prost(int64, tag = "1")]
+    pub amount_refunded: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PauseSubscriptionRequest {
+    #[prost(string, tag = "1")]
+    pub stripe_subscription_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "PauseBehavior", tag = "3")]
+    pub behavior: i32,
+    #[prost(int64, tag = "4")]
+    pub resumes_at: i64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PauseSubscriptionResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResumeSubscriptionRequest {
+    #[prost(string, tag = "1")]
+    pub stripe_subscription_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ResumeSubscriptionResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeSubscriptionPlanRequest {
+    #[prost(string, tag = "1")]
+    pub stripe_subscription_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "ProrationBehavior", tag = "4")]
+    pub proration_behavior: i32,
+    #[prost(int64, optional, tag = "5")]
+    pub proration_date: ::core::option::Option<i64>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ChangeSubscriptionPlanResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewSubscriptionPlanChangeRequest {
+    #[prost(string, tag = "1")]
+    pub stripe_subscription_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "ProrationBehavior", tag = "4")]
+    pub proration_behavior: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewSubscriptionPlanChangeResponse {
+    #[prost(string, tag = "1")]
+    pub currency: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub amount_due: i64,
+    #[prost(int64, tag = "3")]
+    pub total: i64,
+    #[prost(int64, tag = "4")]
+    pub proration_date: i64,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CancellationMode {
+    Unspecified = 0,
+    AtPeriodEnd = 1,
+    Immediately = 2,
+}
+impl CancellationMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CancellationMode::Unspecified => "CANCELLATION_MODE_UNSPECIFIED",
+            CancellationMode::AtPeriodEnd => "CANCELLATION_MODE_AT_PERIOD_END",
+            CancellationMode::Immediately => "CANCELLATION_MODE_IMMEDIATELY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CANCELLATION_MODE_UNSPECIFIED" => Some(Self::Unspecified),
+            "CANCELLATION_MODE_AT_PERIOD_END" => Some(Self::AtPeriodEnd),
+            "CANCELLATION_MODE_IMMEDIATELY" => Some(Self::Immediately),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RefundPolicy {
+    Unspecified = 0,
+    None = 1,
+    Prorated = 2,
+    Full = 3,
+}
+impl RefundPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RefundPolicy::Unspecified => "REFUND_POLICY_UNSPECIFIED",
+            RefundPolicy::None => "REFUND_POLICY_NONE",
+            RefundPolicy::Prorated => "REFUND_POLICY_PRORATED",
+            RefundPolicy::Full => "REFUND_POLICY_FULL",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "REFUND_POLICY_UNSPECIFIED" => Some(Self::Unspecified),
+            "REFUND_POLICY_NONE" => Some(Self::None),
+            "REFUND_POLICY_PRORATED" => Some(Self::Prorated),
+            "REFUND_POLICY_FULL" => Some(Self::Full),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PauseBehavior {
+    Unspecified = 0,
+    Void = 1,
+    KeepAsDraft = 2,
+    MarkUncollectible = 3,
+}
+impl PauseBehavior {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PauseBehavior::Unspecified => "PAUSE_BEHAVIOR_UNSPECIFIED",
+            PauseBehavior::Void => "PAUSE_BEHAVIOR_VOID",
+            PauseBehavior::KeepAsDraft => "PAUSE_BEHAVIOR_KEEP_AS_DRAFT",
+            PauseBehavior::MarkUncollectible => "PAUSE_BEHAVIOR_MARK_UNCOLLECTIBLE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PAUSE_BEHAVIOR_UNSPECIFIED" => Some(Self::Unspecified),
+            "PAUSE_BEHAVIOR_VOID" => Some(Self::Void),
+            "PAUSE_BEHAVIOR_KEEP_AS_DRAFT" => Some(Self::KeepAsDraft),
+            "PAUSE_BEHAVIOR_MARK_UNCOLLECTIBLE" => Some(Self::MarkUncollectible),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ProrationBehavior {
+    Unspecified = 0,
+    CreateProrations = 1,
+    AlwaysInvoice = 2,
+    None = 3,
+}
+impl ProrationBehavior {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ProrationBehavior::Unspecified => "PRORATION_BEHAVIOR_UNSPECIFIED",
+            ProrationBehavior::CreateProrations => "PRORATION_BEHAVIOR_CREATE_PRORATIONS",
+            ProrationBehavior::AlwaysInvoice => "PRORATION_BEHAVIOR_ALWAYS_INVOICE",
+            ProrationBehavior::None => "PRORATION_BEHAVIOR_NONE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PRORATION_BEHAVIOR_UNSPECIFIED" => Some(Self::Unspecified),
+            "PRORATION_BEHAVIOR_CREATE_PRORATIONS" => Some(Self::CreateProrations),
+            "PRORATION_BEHAVIOR_ALWAYS_INVOICE" => Some(Self::AlwaysInvoice),
+            "PRORATION_BEHAVIOR_NONE" => Some(Self::None),
+            _ => None,
+        }
+    }
+}
+/// Generated server implementations.
+pub mod stripe_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with StripeServiceServer.
+    #[async_trait]
+    pub trait StripeService: std::marker::Send + std::marker::Sync + 'static {
+        async fn create_account(
+            &self,
+            request: tonic::Request<super::CreateAccountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateAccountResponse>,
+            tonic::Status,
+        >;
+        async fn create_account_link(
+            &self,
+            request: tonic::Request<super::CreateAccountLinkRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateAccountLinkResponse>,
+            tonic::Status,
+        >;
+        async fn get_account(
+            &self,
+            request: tonic::Request<super::GetAccountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAccountResponse>,
+            tonic::Status,
+        >;
+        async fn get_account_details(
+            &self,
+            request: tonic::Request<super::GetAccountDetailsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAccountDetailsResponse>,
+            tonic::Status,
+        >;
+        async fn create_checkout_session(
+            &self,
+            request: tonic::Request<super::CreateCheckoutSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateCheckoutSessionResponse>,
+            tonic::Status,
+        >;
+        async fn cancel_subscription(
+            &self,
+            request: tonic::Request<super::CancelSubscriptionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelSubscriptionResponse>,
+            tonic::Status,
+        >;
+        async fn pause_subscription(
+            &self,
+            request: tonic::Request<super::PauseSubscriptionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PauseSubscriptionResponse>,
+            tonic::Status,
+        >;
+        async fn resume_subscription(
+            &self,
+            request: tonic::Request<super::ResumeSubscriptionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ResumeSubscriptionResponse>,
+            tonic::Status,
+        >;
+        async fn change_subscription_plan(
+            &self,
+            request: tonic::Request<super::ChangeSubscriptionPlanRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ChangeSubscriptionPlanResponse>,
+            tonic::Status,
+        >;
+        async fn preview_subscription_plan_change(
+            &self,
+            request: tonic::Request<super::PreviewSubscriptionPlanChangeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PreviewSubscriptionPlanChangeResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct StripeServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> StripeServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for StripeServiceServer<T>
+    where
+        T: StripeService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.StripeService/CreateAccount" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateAccountSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::CreateAccountRequest>
+                    for CreateAccountSvc<T> {
+                        type Response = super::CreateAccountResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateAccountRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::create_account(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateAccountSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/CreateAccountLink" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateAccountLinkSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::CreateAccountLinkRequest>
+                    for CreateAccountLinkSvc<T> {
+                        type Response = super::CreateAccountLinkResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateAccountLinkRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::create_account_link(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateAccountLinkSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/GetAccount" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAccountSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::GetAccountRequest>
+                    for GetAccountSvc<T> {
+                        type Response = super::GetAccountResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetAccountRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::get_account(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetAccountSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/GetAccountDetails" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAccountDetailsSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::GetAccountDetailsRequest>
+                    for GetAccountDetailsSvc<T> {
+                        type Response = super::GetAccountDetailsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetAccountDetailsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::get_account_details(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetAccountDetailsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/CreateCheckoutSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateCheckoutSessionSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::CreateCheckoutSessionRequest>
+                    for CreateCheckoutSessionSvc<T> {
+                        type Response = super::CreateCheckoutSessionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateCheckoutSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::create_checkout_session(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateCheckoutSessionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/CancelSubscription" => {
+                    #[allow(non_camel_case_types)]
+                    struct CancelSubscriptionSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::CancelSubscriptionRequest>
+                    for CancelSubscriptionSvc<T> {
+                        type Response = super::CancelSubscriptionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelSubscriptionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::cancel_subscription(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CancelSubscriptionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/PauseSubscription" => {
+                    #[allow(non_camel_case_types)]
+                    struct PauseSubscriptionSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::PauseSubscriptionRequest>
+                    for PauseSubscriptionSvc<T> {
+                        type Response = super::PauseSubscriptionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PauseSubscriptionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::pause_subscription(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PauseSubscriptionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/ResumeSubscription" => {
+                    #[allow(non_camel_case_types)]
+                    struct ResumeSubscriptionSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::ResumeSubscriptionRequest>
+                    for ResumeSubscriptionSvc<T> {
+                        type Response = super::ResumeSubscriptionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ResumeSubscriptionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::resume_subscription(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ResumeSubscriptionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/ChangeSubscriptionPlan" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChangeSubscriptionPlanSvc<T: StripeService>(pub Arc<T>);
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<super::ChangeSubscriptionPlanRequest>
+                    for ChangeSubscriptionPlanSvc<T> {
+                        type Response = super::ChangeSubscriptionPlanResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ChangeSubscriptionPlanRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::change_subscription_plan(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ChangeSubscriptionPlanSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StripeService/PreviewSubscriptionPlanChange" => {
+                    #[allow(non_camel_case_types)]
+                    struct PreviewSubscriptionPlanChangeSvc<T: StripeService>(
+                        pub Arc<T>,
+                    );
+                    impl<
+                        T: StripeService,
+                    > tonic::server::UnaryService<
+                        super::PreviewSubscriptionPlanChangeRequest,
+                    > for PreviewSubscriptionPlanChangeSvc<T> {
+                        type Response = super::PreviewSubscriptionPlanChangeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::PreviewSubscriptionPlanChangeRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StripeService>::preview_subscription_plan_change(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PreviewSubscriptionPlanChangeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for StripeServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.StripeService";
+    impl<T> tonic::server::NamedService for StripeServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbandonedCheckoutResponse {
+    #[prost(string, tag = "1")]
+    pub stripe_checkout_session_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub buyer_email: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub recovery_url: ::prost::alloc::string::String,
+    #[prost(int64, optional, tag = "6")]
+    pub recovery_expires_at: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "7")]
+    pub seller_notified_at: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "8")]
+    pub recovered_at: ::core::option::Option<i64>,
+    #[prost(int64, tag = "9")]
+    pub created_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAbandonedCheckoutsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag = "2")]
+    pub limit: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub offset: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAbandonedCheckoutsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub abandoned_checkouts: ::prost::alloc::vec::Vec<AbandonedCheckoutResponse>,
+}
+/// Generated server implementations.
+pub mod abandoned_checkout_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with AbandonedCheckoutServiceServer.
+    #[async_trait]
+    pub trait AbandonedCheckoutService: std::marker::Send + std::marker::Sync + 'static {
+        async fn list_abandoned_checkouts(
+            &self,
+            request: tonic::Request<super::ListAbandonedCheckoutsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListAbandonedCheckoutsResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct AbandonedCheckoutServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> AbandonedCheckoutServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>>
+    for AbandonedCheckoutServiceServer<T>
+    where
+        T: AbandonedCheckoutService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.AbandonedCheckoutService/ListAbandonedCheckouts" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListAbandonedCheckoutsSvc<T: AbandonedCheckoutService>(
+                        pub Arc<T>,
+                    );
+                    impl<
+                        T: AbandonedCheckoutService,
+                    > tonic::server::UnaryService<super::ListAbandonedCheckoutsRequest>
+                    for ListAbandonedCheckoutsSvc<T> {
+                        type Response = super::ListAbandonedCheckoutsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListAbandonedCheckoutsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AbandonedCheckoutService>::list_abandoned_checkouts(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListAbandonedCheckoutsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for AbandonedCheckoutServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.AbandonedCheckoutService";
+    impl<T> tonic::server::NamedService for AbandonedCheckoutServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BillingPortalSettingsResponse {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub allow_cancel: bool,
+    #[prost(bool, tag = "3")]
+    pub allow_plan_switch: bool,
+    #[prost(int64, tag = "4")]
+    pub created_at: i64,
+    #[prost(int64, tag = "5")]
+    pub updated_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutBillingPortalSettingsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub allow_cancel: bool,
+    #[prost(bool, tag = "3")]
+    pub allow_plan_switch: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutBillingPortalSettingsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub billing_portal_settings: ::core::option::Option<BillingPortalSettingsResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBillingPortalSettingsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBillingPortalSettingsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub billing_portal_settings: ::core::option::Option<BillingPortalSettingsResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateBillingPortalSessionRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub return_url: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateBillingPortalSessionResponse {
+    #[prost(string, tag = "1")]
+    pub link: ::prost::alloc::string::String,
+}
+/// Generated server implementations.
+pub mod billing_portal_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with BillingPortalServiceServer.
+    #[async_trait]
+    pub trait BillingPortalService: std::marker::Send + std::marker::Sync + 'static {
+        async fn put_billing_portal_settings(
+            &self,
+            request: tonic::Request<super::PutBillingPortalSettingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PutBillingPortalSettingsResponse>,
+            tonic::Status,
+        >;
+        async fn get_billing_portal_settings(
+            &self,
+            request: tonic::Request<super::GetBillingPortalSettingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBillingPortalSettingsResponse>,
+            tonic::Status,
+        >;
+        async fn create_billing_portal_session(
+            &self,
+            request: tonic::Request<super::CreateBillingPortalSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateBillingPortalSessionResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct BillingPortalServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> BillingPortalServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>>
+    for BillingPortalServiceServer<T>
+    where
+        T: BillingPortalService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.BillingPortalService/PutBillingPortalSettings" => {
+                    #[allow(non_camel_case_types)]
+                    struct PutBillingPortalSettingsSvc<T: BillingPortalService>(
+                        pub Arc<T>,
+                    );
+                    impl<
+                        T: BillingPortalService,
+                    > tonic::server::UnaryService<super::PutBillingPortalSettingsRequest>
+                    for PutBillingPortalSettingsSvc<T> {
+                        type Response = super::PutBillingPortalSettingsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::PutBillingPortalSettingsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BillingPortalService>::put_billing_portal_settings(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PutBillingPortalSettingsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.BillingPortalService/GetBillingPortalSettings" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBillingPortalSettingsSvc<T: BillingPortalService>(
+                        pub Arc<T>,
+                    );
+                    impl<
+                        T: BillingPortalService,
+                    > tonic::server::UnaryService<super::GetBillingPortalSettingsRequest>
+                    for GetBillingPortalSettingsSvc<T> {
+                        type Response = super::GetBillingPortalSettingsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::GetBillingPortalSettingsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BillingPortalService>::get_billing_portal_settings(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetBillingPortalSettingsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.BillingPortalService/CreateBillingPortalSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateBillingPortalSessionSvc<T: BillingPortalService>(
+                        pub Arc<T>,
+                    );
+                    impl<
+                        T: BillingPortalService,
+                    > tonic::server::UnaryService<
+                        super::CreateBillingPortalSessionRequest,
+                    > for CreateBillingPortalSessionSvc<T> {
+                        type Response = super::CreateBillingPortalSessionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::CreateBillingPortalSessionRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as BillingPortalService>::create_billing_portal_session(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateBillingPortalSessionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for BillingPortalServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.BillingPortalService";
+    impl<T> tonic::server::NamedService for BillingPortalServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CouponResponse {
+    #[prost(string, tag = "1")]
+    pub coupon_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(double, optional, tag = "4")]
+    pub percent_off: ::core::option::Option<f64>,
+    #[prost(uint32, optional, tag = "5")]
+    pub amount_off: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "6")]
+    pub currency: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "CouponDuration", tag = "7")]
+    pub duration: i32,
+    #[prost(uint32, optional, tag = "8")]
+    pub duration_in_months: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "9")]
+    pub max_redemptions: ::core::option::Option<u32>,
+    #[prost(int64, optional, tag = "10")]
+    pub redeem_by: ::core::option::Option<i64>,
+    #[prost(string, repeated, tag = "11")]
+    pub offer_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint32, tag = "12")]
+    pub times_redeemed: u32,
+    #[prost(bool, tag = "13")]
+    pub valid: bool,
+    #[prost(int64, tag = "14")]
+    pub created_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PromotionCodeResponse {
+    #[prost(string, tag = "1")]
+    pub promotion_code_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub coupon_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub code: ::prost::alloc::string::String,
+    #[prost(bool, tag = "4")]
+    pub active: bool,
+    #[prost(uint32, optional, tag = "5")]
+    pub max_redemptions: ::core::option::Option<u32>,
+    #[prost(int64, optional, tag = "6")]
+    pub expires_at: ::core::option::Option<i64>,
+    #[prost(uint32, tag = "7")]
+    pub times_redeemed: u32,
+    #[prost(int64, tag = "8")]
+    pub created_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateCouponRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(double, optional, tag = "3")]
+    pub percent_off: ::core::option::Option<f64>,
+    #[prost(uint32, optional, tag = "4")]
+    pub amount_off: ::core::option::Option<u32>,
+    #[prost(string, optional, tag = "5")]
+    pub currency: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "CouponDuration", tag = "6")]
+    pub duration: i32,
+    #[prost(uint32, optional, tag = "7")]
+    pub duration_in_months: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "8")]
+    pub max_redemptions: ::core::option::Option<u32>,
+    #[prost(int64, optional, tag = "9")]
+    pub redeem_by: ::core::option::Option<i64>,
+    #[prost(string, repeated, tag = "10")]
+    pub offer_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateCouponResponse {
+    #[prost(message, optional, tag = "1")]
+    pub coupon: ::core::option::Option<CouponResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListCouponsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListCouponsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub coupons: ::prost::alloc::vec::Vec<CouponResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeactivateCouponRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub coupon_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeactivateCouponResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreatePromotionCodeRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub coupon_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub code: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag = "4")]
+    pub max_redemptions: ::core::option::Option<u32>,
+    #[prost(int64, optional, tag = "5")]
+    pub expires_at: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreatePromotionCodeResponse {
+    #[prost(message, optional, tag = "1")]
+    pub promotion_code: ::core::option::Option<PromotionCodeResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPromotionCodesRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub coupon_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPromotionCodesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub promotion_codes: ::prost::alloc::vec::Vec<PromotionCodeResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeactivatePromotionCodeRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub promotion_code_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeactivatePromotionCodeResponse {}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CouponDuration {
+    Unspecified = 0,
+    Once = 1,
+    Repeating = 2,
+    Forever = 3,
+}
+impl CouponDuration {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CouponDuration::Unspecified => "COUPON_DURATION_UNSPECIFIED",
+            CouponDuration::Once => "COUPON_DURATION_ONCE",
+            CouponDuration::Repeating => "COUPON_DURATION_REPEATING",
+            CouponDuration::Forever => "COUPON_DURATION_FOREVER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COUPON_DURATION_UNSPECIFIED" => Some(Self::Unspecified),
+            "COUPON_DURATION_ONCE" => Some(Self::Once),
+            "COUPON_DURATION_REPEATING" => Some(Self::Repeating),
+            "COUPON_DURATION_FOREVER" => Some(Self::Forever),
+            _ => None,
+        }
+    }
+}
+/// Generated server implementations.
+pub mod coupon_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with CouponServiceServer.
+    #[async_trait]
+    pub trait CouponService: std::marker::Send + std::marker::Sync + 'static {
+        async fn create_coupon(
+            &self,
+            request: tonic::Request<super::CreateCouponRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateCouponResponse>,
+            tonic::Status,
+        >;
+        async fn list_coupons(
+            &self,
+            request: tonic::Request<super::ListCouponsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListCouponsResponse>,
+            tonic::Status,
+        >;
+        async fn deactivate_coupon(
+            &self,
+            request: tonic::Request<super::DeactivateCouponRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeactivateCouponResponse>,
+            tonic::Status,
+        >;
+        async fn create_promotion_code(
+            &self,
+            request: tonic::Request<super::CreatePromotionCodeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreatePromotionCodeResponse>,
+            tonic::Status,
+        >;
+        async fn list_promotion_codes(
+            &self,
+            request: tonic::Request<super::ListPromotionCodesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPromotionCodesResponse>,
+            tonic::Status,
+        >;
+        async fn deactivate_promotion_code(
+            &self,
+            request: tonic::Request<super::DeactivatePromotionCodeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeactivatePromotionCodeResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct CouponServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> CouponServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for CouponServiceServer<T>
+    where
+        T: CouponService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.CouponService/CreateCoupon" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateCouponSvc<T: CouponService>(pub Arc<T>);
+                    impl<
+                        T: CouponService,
+                    > tonic::server::UnaryService<super::CreateCouponRequest>
+                    for CreateCouponSvc<T> {
+                        type Response = super::CreateCouponResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateCouponRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CouponService>::create_coupon(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateCouponSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.CouponService/ListCoupons" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListCouponsSvc<T: CouponService>(pub Arc<T>);
+                    impl<
+                        T: CouponService,
+                    > tonic::server::UnaryService<super::ListCouponsRequest>
+                    for ListCouponsSvc<T> {
+                        type Response = super::ListCouponsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListCouponsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CouponService>::list_coupons(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListCouponsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.CouponService/DeactivateCoupon" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeactivateCouponSvc<T: CouponService>(pub Arc<T>);
+                    impl<
+                        T: CouponService,
+                    > tonic::server::UnaryService<super::DeactivateCouponRequest>
+                    for DeactivateCouponSvc<T> {
+                        type Response = super::DeactivateCouponResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeactivateCouponRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CouponService>::deactivate_coupon(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeactivateCouponSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.CouponService/CreatePromotionCode" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreatePromotionCodeSvc<T: CouponService>(pub Arc<T>);
+                    impl<
+                        T: CouponService,
+                    > tonic::server::UnaryService<super::CreatePromotionCodeRequest>
+                    for CreatePromotionCodeSvc<T> {
+                        type Response = super::CreatePromotionCodeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreatePromotionCodeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CouponService>::create_promotion_code(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreatePromotionCodeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.CouponService/ListPromotionCodes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPromotionCodesSvc<T: CouponService>(pub Arc<T>);
+                    impl<
+                        T: CouponService,
+                    > tonic::server::UnaryService<super::ListPromotionCodesRequest>
+                    for ListPromotionCodesSvc<T> {
+                        type Response = super::ListPromotionCodesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPromotionCodesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CouponService>::list_promotion_codes(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListPromotionCodesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.CouponService/DeactivatePromotionCode" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeactivatePromotionCodeSvc<T: CouponService>(pub Arc<T>);
+                    impl<
+                        T: CouponService,
+                    > tonic::server::UnaryService<super::DeactivatePromotionCodeRequest>
+                    for DeactivatePromotionCodeSvc<T> {
+                        type Response = super::DeactivatePromotionCodeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::DeactivatePromotionCodeRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CouponService>::deactivate_promotion_code(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeactivatePromotionCodeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for CouponServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.CouponService";
+    impl<T> tonic::server::NamedService for CouponServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EntitlementResponse {
+    #[prost(string, tag = "1")]
+    pub entitlement_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "5")]
+    pub payment_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub stripe_subscription_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, tag = "7")]
+    pub valid_from: i64,
+    #[prost(int64, optional, tag = "8")]
+    pub valid_until: ::core::option::Option<i64>,
+    #[prost(int64, tag = "9")]
+    pub created_at: i64,
+    #[prost(int64, tag = "10")]
+    pub updated_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckEntitlementRequest {
+    #[prost(string, tag = "1")]
+    pub user_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckEntitlementResponse {
+    #[prost(bool, tag = "1")]
+    pub entitled: bool,
+    #[prost(message, optional, tag = "2")]
+    pub entitlement: ::core::option::Option<EntitlementResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEntitlementsRequest {
+    #[prost(string, tag = "1")]
+    pub user_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub shop_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag = "3")]
+    pub limit: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub offset: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEntitlementsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entitlements: ::prost::alloc::vec::Vec<EntitlementResponse>,
+}
+/// Generated server implementations.
+pub mod entitlement_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with EntitlementServiceServer.
+    #[async_trait]
+    pub trait EntitlementService: std::marker::Send + std::marker::Sync + 'static {
+        async fn check_entitlement(
+            &self,
+            request: tonic::Request<super::CheckEntitlementRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CheckEntitlementResponse>,
+            tonic::Status,
+        >;
+        async fn list_entitlements(
+            &self,
+            request: tonic::Request<super::ListEntitlementsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListEntitlementsResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct EntitlementServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> EntitlementServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for EntitlementServiceServer<T>
+    where
+        T: EntitlementService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.EntitlementService/CheckEntitlement" => {
+                    #[allow(non_camel_case_types)]
+                    struct CheckEntitlementSvc<T: EntitlementService>(pub Arc<T>);
+                    impl<
+                        T: EntitlementService,
+                    > tonic::server::UnaryService<super::CheckEntitlementRequest>
+                    for CheckEntitlementSvc<T> {
+                        type Response = super::CheckEntitlementResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CheckEntitlementRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as EntitlementService>::check_entitlement(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CheckEntitlementSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.EntitlementService/ListEntitlements" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListEntitlementsSvc<T: EntitlementService>(pub Arc<T>);
+                    impl<
+                        T: EntitlementService,
+                    > tonic::server::UnaryService<super::ListEntitlementsRequest>
+                    for ListEntitlementsSvc<T> {
+                        type Response = super::ListEntitlementsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListEntitlementsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as EntitlementService>::list_entitlements(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListEntitlementsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for EntitlementServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.EntitlementService";
+    impl<T> tonic::server::NamedService for EntitlementServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LicenseKeyPoolResponse {
+    #[prost(string, tag = "1")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub key_format: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, tag = "4")]
+    pub available_keys: i64,
+    #[prost(int64, tag = "5")]
+    pub created_at: i64,
+    #[prost(int64, tag = "6")]
+    pub updated_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutLicenseKeyPoolRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub key_format: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutLicenseKeyPoolResponse {
+    #[prost(message, optional, tag = "1")]
+    pub pool: ::core::option::Option<LicenseKeyPoolResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetLicenseKeyPoolRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetLicenseKeyPoolResponse {
+    #[prost(message, optional, tag = "1")]
+    pub pool: ::core::option::Option<LicenseKeyPoolResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AddLicenseKeysRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "3")]
+    pub license_keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct AddLicenseKeysResponse {
+    #[prost(uint64, tag = "1")]
+    pub added_keys: u64,
+    #[prost(int64, tag = "2")]
+    pub available_keys: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LicenseKeyResponse {
+    #[prost(string, tag = "1")]
+    pub license_key_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub license_key: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "5")]
+    pub payment_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, optional, tag = "6")]
+    pub assigned_at: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPurchasedLicenseKeysRequest {
+    #[prost(string, optional, tag = "1")]
+    pub offer_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPurchasedLicenseKeysResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub license_keys: ::prost::alloc::vec::Vec<LicenseKeyResponse>,
+}
+/// Generated server implementations.
+pub mod license_key_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with LicenseKeyServiceServer.
+    #[async_trait]
+    pub trait LicenseKeyService: std::marker::Send + std::marker::Sync + 'static {
+        async fn put_license_key_pool(
+            &self,
+            request: tonic::Request<super::PutLicenseKeyPoolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PutLicenseKeyPoolResponse>,
+            tonic::Status,
+        >;
+        async fn get_license_key_pool(
+            &self,
+            request: tonic::Request<super::GetLicenseKeyPoolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetLicenseKeyPoolResponse>,
+            tonic::Status,
+        >;
+        async fn add_license_keys(
+            &self,
+            request: tonic::Request<super::AddLicenseKeysRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AddLicenseKeysResponse>,
+            tonic::Status,
+        >;
+        async fn list_purchased_license_keys(
+            &self,
+            request: tonic::Request<super::ListPurchasedLicenseKeysRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPurchasedLicenseKeysResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct LicenseKeyServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> LicenseKeyServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for LicenseKeyServiceServer<T>
+    where
+        T: LicenseKeyService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.LicenseKeyService/PutLicenseKeyPool" => {
+                    #[allow(non_camel_case_types)]
+                    struct PutLicenseKeyPoolSvc<T: LicenseKeyService>(pub Arc<T>);
+                    impl<
+                        T: LicenseKeyService,
+                    > tonic::server::UnaryService<super::PutLicenseKeyPoolRequest>
+                    for PutLicenseKeyPoolSvc<T> {
+                        type Response = super::PutLicenseKeyPoolResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PutLicenseKeyPoolRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LicenseKeyService>::put_license_key_pool(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PutLicenseKeyPoolSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.LicenseKeyService/GetLicenseKeyPool" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetLicenseKeyPoolSvc<T: LicenseKeyService>(pub Arc<T>);
+                    impl<
+                        T: LicenseKeyService,
+                    > tonic::server::UnaryService<super::GetLicenseKeyPoolRequest>
+                    for GetLicenseKeyPoolSvc<T> {
+                        type Response = super::GetLicenseKeyPoolResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetLicenseKeyPoolRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LicenseKeyService>::get_license_key_pool(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetLicenseKeyPoolSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.LicenseKeyService/AddLicenseKeys" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddLicenseKeysSvc<T: LicenseKeyService>(pub Arc<T>);
+                    impl<
+                        T: LicenseKeyService,
+                    > tonic::server::UnaryService<super::AddLicenseKeysRequest>
+                    for AddLicenseKeysSvc<T> {
+                        type Response = super::AddLicenseKeysResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddLicenseKeysRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LicenseKeyService>::add_license_keys(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AddLicenseKeysSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.LicenseKeyService/ListPurchasedLicenseKeys" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPurchasedLicenseKeysSvc<T: LicenseKeyService>(pub Arc<T>);
+                    impl<
+                        T: LicenseKeyService,
+                    > tonic::server::UnaryService<super::ListPurchasedLicenseKeysRequest>
+                    for ListPurchasedLicenseKeysSvc<T> {
+                        type Response = super::ListPurchasedLicenseKeysResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::ListPurchasedLicenseKeysRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as LicenseKeyService>::list_purchased_license_keys(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListPurchasedLicenseKeysSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for LicenseKeyServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.LicenseKeyService";
+    impl<T> tonic::server::NamedService for LicenseKeyServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OfferSettingsResponse {
+    #[prost(string, tag = "1")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub manual_capture: bool,
+    #[prost(int64, tag = "4")]
+    pub created_at: i64,
+    #[prost(int64, tag = "5")]
+    pub updated_at: i64,
+    #[prost(bool, tag = "6")]
+    pub trial_without_payment_method: bool,
+    #[prost(bool, tag = "7")]
+    pub allow_multiple_subscriptions: bool,
+    #[prost(int64, optional, tag = "8")]
+    pub max_quantity_per_session: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "9")]
+    pub max_quantity_per_buyer: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutOfferSettingsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub manual_capture: bool,
+    #[prost(bool, tag = "4")]
+    pub trial_without_payment_method: bool,
+    #[prost(bool, tag = "5")]
+    pub allow_multiple_subscriptions: bool,
+    #[prost(int64, optional, tag = "6")]
+    pub max_quantity_per_session: ::core::option::Option<i64>,
+    #[prost(int64, optional, tag = "7")]
+    pub max_quantity_per_buyer: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutOfferSettingsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub offer_settings: ::core::option::Option<OfferSettingsResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetOfferSettingsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetOfferSettingsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub offer_settings: ::core::option::Option<OfferSettingsResponse>,
+}
+/// Generated server implementations.
+pub mod offer_settings_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with OfferSettingsServiceServer.
+    #[async_trait]
+    pub trait OfferSettingsService: std::marker::Send + std::marker::Sync + 'static {
+        async fn put_offer_settings(
+            &self,
+            request: tonic::Request<super::PutOfferSettingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PutOfferSettingsResponse>,
+            tonic::Status,
+        >;
+        async fn get_offer_settings(
+            &self,
+            request: tonic::Request<super::GetOfferSettingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetOfferSettingsResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct OfferSettingsServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> OfferSettingsServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>>
+    for OfferSettingsServiceServer<T>
+    where
+        T: OfferSettingsService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.OfferSettingsService/PutOfferSettings" => {
+                    #[allow(non_camel_case_types)]
+                    struct PutOfferSettingsSvc<T: OfferSettingsService>(pub Arc<T>);
+                    impl<
+                        T: OfferSettingsService,
+                    > tonic::server::UnaryService<super::PutOfferSettingsRequest>
+                    for PutOfferSettingsSvc<T> {
+                        type Response = super::PutOfferSettingsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PutOfferSettingsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OfferSettingsService>::put_offer_settings(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PutOfferSettingsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.OfferSettingsService/GetOfferSettings" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetOfferSettingsSvc<T: OfferSettingsService>(pub Arc<T>);
+                    impl<
+                        T: OfferSettingsService,
+                    > tonic::server::UnaryService<super::GetOfferSettingsRequest>
+                    for GetOfferSettingsSvc<T> {
+                        type Response = super::GetOfferSettingsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetOfferSettingsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OfferSettingsService>::get_offer_settings(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetOfferSettingsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for OfferSettingsServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.OfferSettingsService";
+    impl<T> tonic::server::NamedService for OfferSettingsServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaymentResponse {
+    #[prost(string, tag = "1")]
+    pub payment_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "4")]
+    pub user_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub stripe_checkout_session_id: ::core::option::Option<
+        ::prost::alloc::string::String,
+    >,
+    #[prost(string, optional, tag = "6")]
+    pub stripe_payment_intent_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "7")]
+    pub stripe_subscription_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag = "8")]
+    pub payment_status: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub currency: ::prost::alloc::string::String,
+    #[prost(int64, tag = "10")]
+    pub amount_subtotal: i64,
+    #[prost(int64, tag = "11")]
+    pub amount_discount: i64,
+    #[prost(int64, tag = "12")]
+    pub amount_shipping: i64,
+    #[prost(int64, tag = "13")]
+    pub amount_tax: i64,
+    #[prost(int64, tag = "14")]
+    pub amount_total: i64,
+    #[prost(int64, tag = "15")]
+    pub created_at: i64,
+    #[prost(int64, tag = "16")]
+    pub updated_at: i64,
+    #[prost(int64, optional, tag = "17")]
+    pub authorization_expires_at: ::core::option::Option<i64>,
+    #[prost(string, optional, tag = "18")]
+    pub stripe_customer_id: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, optional, tag = "19")]
+    pub trial_ends_at: ::core::option::Option<i64>,
+    #[prost(string, optional, tag = "20")]
+    pub buyer_email: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, optional, tag = "21")]
+    pub quantity: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPaymentsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(uint64, optional, tag = "2")]
+    pub limit: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub offset: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPaymentsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub payments: ::prost::alloc::vec::Vec<PaymentResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CapturePaymentRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub payment_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CapturePaymentResponse {
+    #[prost(message, optional, tag = "1")]
+    pub payment: ::core::option::Option<PaymentResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelAuthorizationRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub payment_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelAuthorizationResponse {
+    #[prost(message, optional, tag = "1")]
+    pub payment: ::core::option::Option<PaymentResponse>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ClaimPurchasesRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimPurchasesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub payments: ::prost::alloc::vec::Vec<PaymentResponse>,
+}
+/// Generated server implementations.
+pub mod payment_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with PaymentServiceServer.
+    #[async_trait]
+    pub trait PaymentService: std::marker::Send + std::marker::Sync + 'static {
+        async fn list_payments(
+            &self,
+            request: tonic::Request<super::ListPaymentsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListPaymentsResponse>,
+            tonic::Status,
+        >;
+        async fn capture_payment(
+            &self,
+            request: tonic::Request<super::CapturePaymentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CapturePaymentResponse>,
+            tonic::Status,
+        >;
+        async fn cancel_authorization(
+            &self,
+            request: tonic::Request<super::CancelAuthorizationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelAuthorizationResponse>,
+            tonic::Status,
+        >;
+        async fn claim_purchases(
+            &self,
+            request: tonic::Request<super::ClaimPurchasesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ClaimPurchasesResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct PaymentServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> PaymentServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for PaymentServiceServer<T>
+    where
+        T: PaymentService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.PaymentService/ListPayments" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPaymentsSvc<T: PaymentService>(pub Arc<T>);
+                    impl<
+                        T: PaymentService,
+                    > tonic::server::UnaryService<super::ListPaymentsRequest>
+                    for ListPaymentsSvc<T> {
+                        type Response = super::ListPaymentsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPaymentsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PaymentService>::list_payments(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListPaymentsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.PaymentService/CapturePayment" => {
+                    #[allow(non_camel_case_types)]
+                    struct CapturePaymentSvc<T: PaymentService>(pub Arc<T>);
+                    impl<
+                        T: PaymentService,
+                    > tonic::server::UnaryService<super::CapturePaymentRequest>
+                    for CapturePaymentSvc<T> {
+                        type Response = super::CapturePaymentResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CapturePaymentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PaymentService>::capture_payment(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CapturePaymentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.PaymentService/CancelAuthorization" => {
+                    #[allow(non_camel_case_types)]
+                    struct CancelAuthorizationSvc<T: PaymentService>(pub Arc<T>);
+                    impl<
+                        T: PaymentService,
+                    > tonic::server::UnaryService<super::CancelAuthorizationRequest>
+                    for CancelAuthorizationSvc<T> {
+                        type Response = super::CancelAuthorizationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelAuthorizationRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PaymentService>::cancel_authorization(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CancelAuthorizationSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.PaymentService/ClaimPurchases" => {
+                    #[allow(non_camel_case_types)]
+                    struct ClaimPurchasesSvc<T: PaymentService>(pub Arc<T>);
+                    impl<
+                        T: PaymentService,
+                    > tonic::server::UnaryService<super::ClaimPurchasesRequest>
+                    for ClaimPurchasesSvc<T> {
+                        type Response = super::ClaimPurchasesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ClaimPurchasesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PaymentService>::claim_purchases(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ClaimPurchasesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for PaymentServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.PaymentService";
+    impl<T> tonic::server::NamedService for PaymentServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShippingZoneRate {
+    #[prost(string, tag = "1")]
+    pub shipping_zone_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub amount: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShippingOptionResponse {
+    #[prost(string, tag = "1")]
+    pub shipping_option_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub display_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub currency: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "5")]
+    pub amount: u32,
+    #[prost(string, optional, tag = "6")]
+    pub domestic_country: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag = "7")]
+    pub international_amount: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "8")]
+    pub min_delivery_days: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "9")]
+    pub max_delivery_days: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "10")]
+    pub free_shipping_threshold: ::core::option::Option<u32>,
+    #[prost(int64, tag = "11")]
+    pub created_at: i64,
+    #[prost(int64, tag = "12")]
+    pub updated_at: i64,
+    #[prost(message, repeated, tag = "13")]
+    pub zone_rates: ::prost::alloc::vec::Vec<ShippingZoneRate>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateShippingOptionRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub display_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub currency: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "4")]
+    pub amount: u32,
+    #[prost(string, optional, tag = "5")]
+    pub domestic_country: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag = "6")]
+    pub international_amount: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "7")]
+    pub min_delivery_days: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "8")]
+    pub max_delivery_days: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "9")]
+    pub free_shipping_threshold: ::core::option::Option<u32>,
+    #[prost(message, repeated, tag = "10")]
+    pub zone_rates: ::prost::alloc::vec::Vec<ShippingZoneRate>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateShippingOptionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub shipping_option: ::core::option::Option<ShippingOptionResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateShippingOptionRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shipping_option_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub display_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub currency: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "5")]
+    pub amount: u32,
+    #[prost(string, optional, tag = "6")]
+    pub domestic_country: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(uint32, optional, tag = "7")]
+    pub international_amount: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "8")]
+    pub min_delivery_days: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "9")]
+    pub max_delivery_days: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "10")]
+    pub free_shipping_threshold: ::core::option::Option<u32>,
+    #[prost(message, repeated, tag = "11")]
+    pub zone_rates: ::prost::alloc::vec::Vec<ShippingZoneRate>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateShippingOptionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub shipping_option: ::core::option::Option<ShippingOptionResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListShippingOptionsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListShippingOptionsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub shipping_options: ::prost::alloc::vec::Vec<ShippingOptionResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteShippingOptionRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shipping_option_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteShippingOptionResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShippingZoneResponse {
+    #[prost(string, tag = "1")]
+    pub shipping_zone_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "4")]
+    pub countries: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(int64, tag = "5")]
+    pub created_at: i64,
+    #[prost(int64, tag = "6")]
+    pub updated_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateShippingZoneRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "3")]
+    pub countries: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateShippingZoneResponse {
+    #[prost(message, optional, tag = "1")]
+    pub shipping_zone: ::core::option::Option<ShippingZoneResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateShippingZoneRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shipping_zone_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "4")]
+    pub countries: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateShippingZoneResponse {
+    #[prost(message, optional, tag = "1")]
+    pub shipping_zone: ::core::option::Option<ShippingZoneResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListShippingZonesRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListShippingZonesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub shipping_zones: ::prost::alloc::vec::Vec<ShippingZoneResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteShippingZoneRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shipping_zone_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteShippingZoneResponse {}
+/// Generated server implementations.
+pub mod shipping_option_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ShippingOptionServiceServer.
+    #[async_trait]
+    pub trait ShippingOptionService: std::marker::Send + std::marker::Sync + 'static {
+        async fn create_shipping_option(
+            &self,
+            request: tonic::Request<super::CreateShippingOptionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateShippingOptionResponse>,
+            tonic::Status,
+        >;
+        async fn update_shipping_option(
+            &self,
+            request: tonic::Request<super::UpdateShippingOptionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateShippingOptionResponse>,
+            tonic::Status,
+        >;
+        async fn list_shipping_options(
+            &self,
+            request: tonic::Request<super::ListShippingOptionsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListShippingOptionsResponse>,
+            tonic::Status,
+        >;
+        async fn delete_shipping_option(
+            &self,
+            request: tonic::Request<super::DeleteShippingOptionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteShippingOptionResponse>,
+            tonic::Status,
+        >;
+        async fn create_shipping_zone(
+            &self,
+            request: tonic::Request<super::CreateShippingZoneRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CreateShippingZoneResponse>,
+            tonic::Status,
+        >;
+        async fn update_shipping_zone(
+            &self,
+            request: tonic::Request<super::UpdateShippingZoneRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateShippingZoneResponse>,
+            tonic::Status,
+        >;
+        async fn list_shipping_zones(
+            &self,
+            request: tonic::Request<super::ListShippingZonesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListShippingZonesResponse>,
+            tonic::Status,
+        >;
+        async fn delete_shipping_zone(
+            &self,
+            request: tonic::Request<super::DeleteShippingZoneRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteShippingZoneResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct ShippingOptionServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> ShippingOptionServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>>
+    for ShippingOptionServiceServer<T>
+    where
+        T: ShippingOptionService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.ShippingOptionService/CreateShippingOption" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateShippingOptionSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::CreateShippingOptionRequest>
+                    for CreateShippingOptionSvc<T> {
+                        type Response = super::CreateShippingOptionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateShippingOptionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::create_shipping_option(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateShippingOptionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.ShippingOptionService/UpdateShippingOption" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateShippingOptionSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::UpdateShippingOptionRequest>
+                    for UpdateShippingOptionSvc<T> {
+                        type Response = super::UpdateShippingOptionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateShippingOptionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::update_shipping_option(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateShippingOptionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.ShippingOptionService/ListShippingOptions" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListShippingOptionsSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::ListShippingOptionsRequest>
+                    for ListShippingOptionsSvc<T> {
+                        type Response = super::ListShippingOptionsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListShippingOptionsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::list_shipping_options(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListShippingOptionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.ShippingOptionService/DeleteShippingOption" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteShippingOptionSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::DeleteShippingOptionRequest>
+                    for DeleteShippingOptionSvc<T> {
+                        type Response = super::DeleteShippingOptionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteShippingOptionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::delete_shipping_option(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteShippingOptionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.ShippingOptionService/CreateShippingZone" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateShippingZoneSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::CreateShippingZoneRequest>
+                    for CreateShippingZoneSvc<T> {
+                        type Response = super::CreateShippingZoneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateShippingZoneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::create_shipping_zone(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateShippingZoneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.ShippingOptionService/UpdateShippingZone" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateShippingZoneSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::UpdateShippingZoneRequest>
+                    for UpdateShippingZoneSvc<T> {
+                        type Response = super::UpdateShippingZoneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateShippingZoneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::update_shipping_zone(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateShippingZoneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.ShippingOptionService/ListShippingZones" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListShippingZonesSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::ListShippingZonesRequest>
+                    for ListShippingZonesSvc<T> {
+                        type Response = super::ListShippingZonesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListShippingZonesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::list_shipping_zones(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListShippingZonesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.ShippingOptionService/DeleteShippingZone" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteShippingZoneSvc<T: ShippingOptionService>(pub Arc<T>);
+                    impl<
+                        T: ShippingOptionService,
+                    > tonic::server::UnaryService<super::DeleteShippingZoneRequest>
+                    for DeleteShippingZoneSvc<T> {
+                        type Response = super::DeleteShippingZoneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteShippingZoneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShippingOptionService>::delete_shipping_zone(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteShippingZoneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for ShippingOptionServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.ShippingOptionService";
+    impl<T> tonic::server::NamedService for ShippingOptionServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OfferStockResponse {
+    #[prost(string, tag = "1")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(int64, tag = "3")]
+    pub quantity: i64,
+    #[prost(int64, tag = "4")]
+    pub reserved: i64,
+    #[prost(int64, tag = "5")]
+    pub available: i64,
+    #[prost(int64, tag = "6")]
+    pub created_at: i64,
+    #[prost(int64, tag = "7")]
+    pub updated_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutOfferStockRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+    #[prost(int64, tag = "3")]
+    pub quantity: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutOfferStockResponse {
+    #[prost(message, optional, tag = "1")]
+    pub stock: ::core::option::Option<OfferStockResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetOfferStockRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetOfferStockResponse {
+    #[prost(message, optional, tag = "1")]
+    pub stock: ::core::option::Option<OfferStockResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteOfferStockRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub offer_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteOfferStockResponse {}
+/// Generated server implementations.
+pub mod stock_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with StockServiceServer.
+    #[async_trait]
+    pub trait StockService: std::marker::Send + std::marker::Sync + 'static {
+        async fn put_offer_stock(
+            &self,
+            request: tonic::Request<super::PutOfferStockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PutOfferStockResponse>,
+            tonic::Status,
+        >;
+        async fn get_offer_stock(
+            &self,
+            request: tonic::Request<super::GetOfferStockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetOfferStockResponse>,
+            tonic::Status,
+        >;
+        async fn delete_offer_stock(
+            &self,
+            request: tonic::Request<super::DeleteOfferStockRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::DeleteOfferStockResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct StockServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> StockServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for StockServiceServer<T>
+    where
+        T: StockService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.StockService/PutOfferStock" => {
+                    #[allow(non_camel_case_types)]
+                    struct PutOfferStockSvc<T: StockService>(pub Arc<T>);
+                    impl<
+                        T: StockService,
+                    > tonic::server::UnaryService<super::PutOfferStockRequest>
+                    for PutOfferStockSvc<T> {
+                        type Response = super::PutOfferStockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PutOfferStockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StockService>::put_offer_stock(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PutOfferStockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StockService/GetOfferStock" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetOfferStockSvc<T: StockService>(pub Arc<T>);
+                    impl<
+                        T: StockService,
+                    > tonic::server::UnaryService<super::GetOfferStockRequest>
+                    for GetOfferStockSvc<T> {
+                        type Response = super::GetOfferStockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetOfferStockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StockService>::get_offer_stock(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetOfferStockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.StockService/DeleteOfferStock" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteOfferStockSvc<T: StockService>(pub Arc<T>);
+                    impl<
+                        T: StockService,
+                    > tonic::server::UnaryService<super::DeleteOfferStockRequest>
+                    for DeleteOfferStockSvc<T> {
+                        type Response = super::DeleteOfferStockResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteOfferStockRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StockService>::delete_offer_stock(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteOfferStockSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for StockServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.StockService";
+    impl<T> tonic::server::NamedService for StockServiceServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaxSettingsResponse {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub automatic_tax_enabled: bool,
+    #[prost(enumeration = "TaxBehavior", tag = "3")]
+    pub tax_behavior: i32,
+    #[prost(string, optional, tag = "4")]
+    pub physical_tax_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub digital_tax_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub service_tax_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "7")]
+    pub tax_id_collection_enabled: bool,
+    #[prost(int64, tag = "8")]
+    pub created_at: i64,
+    #[prost(int64, tag = "9")]
+    pub updated_at: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutTaxSettingsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub automatic_tax_enabled: bool,
+    #[prost(enumeration = "TaxBehavior", tag = "3")]
+    pub tax_behavior: i32,
+    #[prost(string, optional, tag = "4")]
+    pub physical_tax_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub digital_tax_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub service_tax_code: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "7")]
+    pub tax_id_collection_enabled: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PutTaxSettingsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub tax_settings: ::core::option::Option<TaxSettingsResponse>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTaxSettingsRequest {
+    #[prost(string, tag = "1")]
+    pub shop_id: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTaxSettingsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub tax_settings: ::core::option::Option<TaxSettingsResponse>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TaxBehavior {
+    Unspecified = 0,
+    Inclusive = 1,
+    Exclusive = 2,
+}
+impl TaxBehavior {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            TaxBehavior::Unspecified => "TAX_BEHAVIOR_UNSPECIFIED",
+            TaxBehavior::Inclusive => "TAX_BEHAVIOR_INCLUSIVE",
+            TaxBehavior::Exclusive => "TAX_BEHAVIOR_EXCLUSIVE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TAX_BEHAVIOR_UNSPECIFIED" => Some(Self::Unspecified),
+            "TAX_BEHAVIOR_INCLUSIVE" => Some(Self::Inclusive),
+            "TAX_BEHAVIOR_EXCLUSIVE" => Some(Self::Exclusive),
+            _ => None,
+        }
+    }
+}
+/// Generated server implementations.
+pub mod tax_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with TaxServiceServer.
+    #[async_trait]
+    pub trait TaxService: std::marker::Send + std::marker::Sync + 'static {
+        async fn put_tax_settings(
+            &self,
+            request: tonic::Request<super::PutTaxSettingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::PutTaxSettingsResponse>,
+            tonic::Status,
+        >;
+        async fn get_tax_settings(
+            &self,
+            request: tonic::Request<super::GetTaxSettingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetTaxSettingsResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct TaxServiceServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> TaxServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for TaxServiceServer<T>
+    where
+        T: TaxService,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/sited_io.payment.v1.TaxService/PutTaxSettings" => {
+                    #[allow(non_camel_case_types)]
+                    struct PutTaxSettingsSvc<T: TaxService>(pub Arc<T>);
+                    impl<
+                        T: TaxService,
+                    > tonic::server::UnaryService<super::PutTaxSettingsRequest>
+                    for PutTaxSettingsSvc<T> {
+                        type Response = super::PutTaxSettingsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PutTaxSettingsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TaxService>::put_tax_settings(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PutTaxSettingsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sited_io.payment.v1.TaxService/GetTaxSettings" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetTaxSettingsSvc<T: TaxService>(pub Arc<T>);
+                    impl<
+                        T: TaxService,
+                    > tonic::server::UnaryService<super::GetTaxSettingsRequest>
+                    for GetTaxSettingsSvc<T> {
+                        type Response = super::GetTaxSettingsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetTaxSettingsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TaxService>::get_tax_settings(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetTaxSettingsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", tonic::Code::Unimplemented as i32)
+                                .header(
+                                    http::header::CONTENT_TYPE,
+                                    tonic::metadata::GRPC_CONTENT_TYPE,
+                                )
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for TaxServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "sited_io.payment.v1.TaxService";
+    impl<T> tonic::server::NamedService for TaxServiceServer<T> {
         const NAME: &'static str = SERVICE_NAME;
     }
 }
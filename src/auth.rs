@@ -59,6 +59,51 @@ pub async fn get_user_id(
         .ok_or_else(|| Status::unauthenticated(""))
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct EmailClaims {
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+}
+
+/// Returns the user id and, if contained in the token and verified by the
+/// identity provider, the email address of the user.
+pub async fn get_user_id_and_email(
+    metadata: &MetadataMap,
+    verifier: &RemoteJwksVerifier,
+) -> Result<(String, Option<String>), Status> {
+    let token = get_token(metadata)?;
+
+    let verified = verifier
+        .verify::<EmailClaims>(&token)
+        .await
+        .map_err(|err| Status::unauthenticated(err.to_string()))?;
+    let claims = verified.claims();
+
+    let user_id = claims
+        .sub
+        .clone()
+        .ok_or_else(|| Status::unauthenticated(""))?;
+
+    let email = match (&claims.extra.email, claims.extra.email_verified) {
+        (Some(email), Some(true)) => Some(email.clone()),
+        _ => None,
+    };
+
+    Ok((user_id, email))
+}
+
+/// Returns the user id and the email address of the user, which must be
+/// verified by the identity provider.
+pub async fn get_user_id_and_verified_email(
+    metadata: &MetadataMap,
+    verifier: &RemoteJwksVerifier,
+) -> Result<(String, String), Status> {
+    match get_user_id_and_email(metadata, verifier).await? {
+        (user_id, Some(email)) => Ok((user_id, email)),
+        _ => Err(Status::failed_precondition("email is not verified")),
+    }
+}
+
 pub async fn verify_service_user(
     metadata: &MetadataMap,
     verifier: &RemoteJwksVerifier,
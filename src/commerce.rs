@@ -1,7 +1,7 @@
 use http::header::AUTHORIZATION;
 use tonic::metadata::MetadataMap;
 use tonic::transport::Channel;
-use tonic::{Request, Status};
+use tonic::{Code, Request, Status};
 
 use crate::api::sited_io::commerce::v1::offer_service_client::OfferServiceClient;
 use crate::api::sited_io::commerce::v1::shipping_rate_service_client::ShippingRateServiceClient;
@@ -11,6 +11,7 @@ use crate::api::sited_io::commerce::v1::{
     ShippingRateResponse, ShopResponse,
 };
 
+#[derive(Clone)]
 pub struct CommerceService {
     shop_client: ShopServiceClient<Channel>,
     offer_client: OfferServiceClient<Channel>,
@@ -78,6 +79,28 @@ impl CommerceService {
             .ok_or_else(|| Status::not_found(""))
     }
 
+    /// Returns `None` if the offer does not exist (anymore).
+    pub async fn find_offer(
+        &self,
+        offer_id: &String,
+    ) -> Result<Option<OfferResponse>, Status> {
+        let mut client = self.offer_client.clone();
+
+        match client
+            .get_offer(Request::new(GetOfferRequest {
+                offer_id: offer_id.to_owned(),
+            }))
+            .await
+        {
+            Ok(response) => Ok(response.into_inner().offer),
+            Err(err) if err.code() == Code::NotFound => Ok(None),
+            Err(err) => {
+                tracing::log::error!("{err}");
+                Err(Status::unavailable("offer"))
+            }
+        }
+    }
+
     pub async fn get_shipping_rate(
         &self,
         offer_id: &String,
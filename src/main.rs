@@ -1,15 +1,31 @@
 use http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
 use http::{HeaderName, Method};
 use stripe::Client;
+use tonic::service::Routes;
 use tonic::transport::Server;
 use tower_http::cors::{AllowOrigin, CorsLayer};
 use tower_http::trace::TraceLayer;
 
+use payment::api::sited_io::payment::v1::abandoned_checkout_service_server::AbandonedCheckoutServiceServer;
+use payment::api::sited_io::payment::v1::billing_portal_service_server::BillingPortalServiceServer;
+use payment::api::sited_io::payment::v1::coupon_service_server::CouponServiceServer;
+use payment::api::sited_io::payment::v1::entitlement_service_server::EntitlementServiceServer;
+use payment::api::sited_io::payment::v1::license_key_service_server::LicenseKeyServiceServer;
+use payment::api::sited_io::payment::v1::offer_settings_service_server::OfferSettingsServiceServer;
+use payment::api::sited_io::payment::v1::order_service_server::OrderServiceServer;
+use payment::api::sited_io::payment::v1::payment_service_server::PaymentServiceServer;
+use payment::api::sited_io::payment::v1::shipping_option_service_server::ShippingOptionServiceServer;
+use payment::api::sited_io::payment::v1::stock_service_server::StockServiceServer;
 use payment::api::sited_io::payment::v1::stripe_service_server::StripeServiceServer;
+use payment::api::sited_io::payment::v1::tax_service_server::TaxServiceServer;
 use payment::db::{init_db_pool, migrate};
 use payment::logging::{LogOnFailure, LogOnRequest, LogOnResponse};
 use payment::{
-    get_env_var, init_jwks_verifier, CommerceService, StripeService,
+    get_env_var, init_jwks_verifier, AbandonedCheckoutService,
+    BillingPortalService, CommerceService, CouponService, EntitlementService,
+    LicenseKeyService, OfferSettingsService, OrderService, PaymentService,
+    ShippingOptionService, StockService, StripeService, StripeWebhook,
+    TaxService,
 };
 
 #[tokio::main(flavor = "current_thread")]
@@ -35,6 +51,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // initialize stripe client
     let stripe_client = Client::new(get_env_var("STRIPE_SECRET_KEY"));
+    let stripe_webhook_secret = get_env_var("STRIPE_WEBHOOK_SECRET");
 
     // initialize commerce service client
     let commerce_service =
@@ -46,6 +63,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     health_reporter
         .set_serving::<StripeServiceServer<StripeService>>()
         .await;
+    health_reporter
+        .set_serving::<CouponServiceServer<CouponService>>()
+        .await;
+    health_reporter
+        .set_serving::<PaymentServiceServer<PaymentService>>()
+        .await;
+    health_reporter
+        .set_serving::<ShippingOptionServiceServer<ShippingOptionService>>()
+        .await;
+    health_reporter
+        .set_serving::<TaxServiceServer<TaxService>>()
+        .await;
+    health_reporter
+        .set_serving::<OfferSettingsServiceServer<OfferSettingsService>>()
+        .await;
+    health_reporter
+        .set_serving::<BillingPortalServiceServer<BillingPortalService>>()
+        .await;
+    health_reporter
+        .set_serving::<EntitlementServiceServer<EntitlementService>>()
+        .await;
+    health_reporter
+        .set_serving::<LicenseKeyServiceServer<LicenseKeyService>>()
+        .await;
+    health_reporter
+        .set_serving::<OrderServiceServer<OrderService>>()
+        .await;
+    health_reporter
+        .set_serving::<StockServiceServer<StockService>>()
+        .await;
+    health_reporter
+        .set_serving::<AbandonedCheckoutServiceServer<AbandonedCheckoutService>>()
+        .await;
 
     // configure gRPC reflection service
     let reflection_service = tonic_reflection::server::Builder::configure()
@@ -60,12 +110,87 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // initialize grpc services
     let stripe_service = StripeService::build(
-        db_pool,
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        stripe_client.clone(),
+        commerce_service.clone(),
+        std::env::var("COLLECT_PROMOTIONS_CONSENT").is_ok_and(|c| c == "true"),
+    );
+    let coupon_service = CouponService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        stripe_client.clone(),
+        commerce_service.clone(),
+    );
+    let payment_service = PaymentService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        stripe_client.clone(),
+        commerce_service.clone(),
+    );
+    let shipping_option_service = ShippingOptionService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        commerce_service.clone(),
+    );
+    let tax_service = TaxService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        commerce_service.clone(),
+    );
+    let offer_settings_service = OfferSettingsService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        commerce_service.clone(),
+    );
+    let billing_portal_service = BillingPortalService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        stripe_client.clone(),
+        commerce_service.clone(),
+        get_env_var("PLATFORM_DOMAIN"),
+    );
+    let entitlement_service = EntitlementService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+    );
+    let license_key_service = LicenseKeyService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        commerce_service.clone(),
+    );
+    let order_service = OrderService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        commerce_service.clone(),
+    );
+    let stock_service = StockService::build(
+        db_pool.clone(),
+        init_jwks_verifier(&jwks_host, &jwks_url)?,
+        commerce_service.clone(),
+    );
+    let abandoned_checkout_service = AbandonedCheckoutService::build(
+        db_pool.clone(),
         init_jwks_verifier(&jwks_host, &jwks_url)?,
-        stripe_client,
         commerce_service,
     );
 
+    // notify sellers about abandoned checkouts in the background
+    tokio::spawn(AbandonedCheckoutService::run_seller_notifications(
+        db_pool.clone(),
+        std::env::var("SELLER_NOTIFICATION_URL").ok(),
+    ));
+
+    // publish status changes of orders in the background
+    tokio::spawn(OrderService::run_event_publishing(
+        db_pool.clone(),
+        std::env::var("SELLER_NOTIFICATION_URL").ok(),
+    ));
+
+    // initialize stripe webhook
+    let stripe_webhook =
+        StripeWebhook::build(db_pool, stripe_client, stripe_webhook_secret);
+
     tracing::log::info!("gRPC+web server listening on {}", host);
 
     Server::builder()
@@ -91,9 +216,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .allow_private_network(true),
         )
         .accept_http1(true)
+        .add_routes(Routes::from(stripe_webhook))
         .add_service(tonic_web::enable(reflection_service))
         .add_service(tonic_web::enable(health_service))
         .add_service(tonic_web::enable(stripe_service))
+        .add_service(tonic_web::enable(coupon_service))
+        .add_service(tonic_web::enable(payment_service))
+        .add_service(tonic_web::enable(shipping_option_service))
+        .add_service(tonic_web::enable(tax_service))
+        .add_service(tonic_web::enable(offer_settings_service))
+        .add_service(tonic_web::enable(billing_portal_service))
+        .add_service(tonic_web::enable(entitlement_service))
+        .add_service(tonic_web::enable(license_key_service))
+        .add_service(tonic_web::enable(order_service))
+        .add_service(tonic_web::enable(stock_service))
+        .add_service(tonic_web::enable(abandoned_checkout_service))
         .serve(host.parse().unwrap())
         .await?;
 
@@ -0,0 +1,244 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{
+    Asterisk, Cond, Expr, Iden, OnConflict, Order, PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "abandoned_checkouts")]
+pub enum AbandonedCheckoutIden {
+    Table,
+    StripeCheckoutSessionId,
+    ShopId,
+    OfferId,
+    BuyerEmail,
+    RecoveryUrl,
+    RecoveryExpiresAt,
+    SellerNotifiedAt,
+    RecoveredAt,
+    CreatedAt,
+}
+
+/// Checkout session which expired without payment, stored if the buyer
+/// consented to be contacted.
+pub struct AbandonedCheckout {
+    pub stripe_checkout_session_id: String,
+    pub shop_id: Uuid,
+    pub offer_id: Uuid,
+    pub buyer_email: String,
+    pub recovery_url: String,
+    pub recovery_expires_at: Option<DateTime<Utc>>,
+    pub seller_notified_at: Option<DateTime<Utc>>,
+    pub recovered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AbandonedCheckout {
+    /// Stores an expired checkout session. Stripe may deliver webhook events
+    /// more than once, so checkout sessions stored before are skipped.
+    pub async fn create(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+        shop_id: &Uuid,
+        offer_id: &Uuid,
+        buyer_email: &str,
+        recovery_url: &String,
+        recovery_expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(AbandonedCheckoutIden::Table)
+            .columns([
+                AbandonedCheckoutIden::StripeCheckoutSessionId,
+                AbandonedCheckoutIden::ShopId,
+                AbandonedCheckoutIden::OfferId,
+                AbandonedCheckoutIden::BuyerEmail,
+                AbandonedCheckoutIden::RecoveryUrl,
+                AbandonedCheckoutIden::RecoveryExpiresAt,
+            ])
+            .values([
+                stripe_checkout_session_id.into(),
+                (*shop_id).into(),
+                (*offer_id).into(),
+                buyer_email.to_lowercase().into(),
+                recovery_url.into(),
+                recovery_expires_at.into(),
+            ])?
+            .on_conflict(
+                OnConflict::column(
+                    AbandonedCheckoutIden::StripeCheckoutSessionId,
+                )
+                .do_nothing()
+                .to_owned(),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    pub async fn list(
+        pool: &Pool,
+        shop_id: &Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(AbandonedCheckoutIden::Table)
+            .and_where(Expr::col(AbandonedCheckoutIden::ShopId).eq(*shop_id))
+            .order_by(AbandonedCheckoutIden::CreatedAt, Order::Desc)
+            .limit(limit)
+            .offset(offset)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    /// Returns the oldest abandoned checkouts the seller was not notified
+    /// about, which can still be recovered.
+    pub async fn list_unnotified(
+        pool: &Pool,
+        limit: u64,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(AbandonedCheckoutIden::Table)
+            .cond_where(
+                Cond::all()
+                    .add(
+                        Expr::col(AbandonedCheckoutIden::SellerNotifiedAt)
+                            .is_null(),
+                    )
+                    .add(
+                        Expr::col(AbandonedCheckoutIden::RecoveredAt).is_null(),
+                    )
+                    .add(
+                        Cond::any()
+                            .add(
+                                Expr::col(
+                                    AbandonedCheckoutIden::RecoveryExpiresAt,
+                                )
+                                .is_null(),
+                            )
+                            .add(
+                                Expr::col(
+                                    AbandonedCheckoutIden::RecoveryExpiresAt,
+                                )
+                                .gt(Expr::current_timestamp()),
+                            ),
+                    ),
+            )
+            .order_by(AbandonedCheckoutIden::CreatedAt, Order::Asc)
+            .limit(limit)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    pub async fn mark_notified(
+        pool: &Pool,
+        stripe_checkout_session_ids: &[String],
+    ) -> Result<(), DbError> {
+        if stripe_checkout_session_ids.is_empty() {
+            return Ok(());
+        }
+
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(AbandonedCheckoutIden::Table)
+            .value(
+                AbandonedCheckoutIden::SellerNotifiedAt,
+                Expr::current_timestamp(),
+            )
+            .and_where(
+                Expr::col(AbandonedCheckoutIden::StripeCheckoutSessionId)
+                    .is_in(stripe_checkout_session_ids.iter().cloned()),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Marks the abandoned checkout a completed checkout session was
+    /// recovered from.
+    pub async fn mark_recovered(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(AbandonedCheckoutIden::Table)
+            .value(
+                AbandonedCheckoutIden::RecoveredAt,
+                Expr::current_timestamp(),
+            )
+            .and_where(
+                Expr::col(AbandonedCheckoutIden::StripeCheckoutSessionId)
+                    .eq(stripe_checkout_session_id),
+            )
+            .and_where(Expr::col(AbandonedCheckoutIden::RecoveredAt).is_null())
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+}
+
+impl From<&Row> for AbandonedCheckout {
+    fn from(row: &Row) -> Self {
+        Self {
+            stripe_checkout_session_id: row.get(
+                AbandonedCheckoutIden::StripeCheckoutSessionId
+                    .to_string()
+                    .as_str(),
+            ),
+            shop_id: row
+                .get(AbandonedCheckoutIden::ShopId.to_string().as_str()),
+            offer_id: row
+                .get(AbandonedCheckoutIden::OfferId.to_string().as_str()),
+            buyer_email: row
+                .get(AbandonedCheckoutIden::BuyerEmail.to_string().as_str()),
+            recovery_url: row
+                .get(AbandonedCheckoutIden::RecoveryUrl.to_string().as_str()),
+            recovery_expires_at: row.get(
+                AbandonedCheckoutIden::RecoveryExpiresAt
+                    .to_string()
+                    .as_str(),
+            ),
+            seller_notified_at: row.get(
+                AbandonedCheckoutIden::SellerNotifiedAt.to_string().as_str(),
+            ),
+            recovered_at: row
+                .get(AbandonedCheckoutIden::RecoveredAt.to_string().as_str()),
+            created_at: row
+                .get(AbandonedCheckoutIden::CreatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for AbandonedCheckout {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{
+    Asterisk, Expr, Iden, OnConflict, PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "billing_portal_settings")]
+pub enum BillingPortalSettingsIden {
+    Table,
+    ShopId,
+    AllowCancel,
+    AllowPlanSwitch,
+    StripeConfigurationId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+pub struct BillingPortalSettings {
+    pub shop_id: Uuid,
+    pub allow_cancel: bool,
+    pub allow_plan_switch: bool,
+    pub stripe_configuration_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BillingPortalSettings {
+    pub async fn put(
+        pool: &Pool,
+        shop_id: &Uuid,
+        allow_cancel: bool,
+        allow_plan_switch: bool,
+        stripe_configuration_id: &String,
+    ) -> Result<Self, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(BillingPortalSettingsIden::Table)
+            .columns([
+                BillingPortalSettingsIden::ShopId,
+                BillingPortalSettingsIden::AllowCancel,
+                BillingPortalSettingsIden::AllowPlanSwitch,
+                BillingPortalSettingsIden::StripeConfigurationId,
+            ])
+            .values([
+                (*shop_id).into(),
+                allow_cancel.into(),
+                allow_plan_switch.into(),
+                stripe_configuration_id.into(),
+            ])?
+            .on_conflict(
+                OnConflict::column(BillingPortalSettingsIden::ShopId)
+                    .update_columns([
+                        BillingPortalSettingsIden::AllowCancel,
+                        BillingPortalSettingsIden::AllowPlanSwitch,
+                        BillingPortalSettingsIden::StripeConfigurationId,
+                    ])
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(Self::from(row))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        shop_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(BillingPortalSettingsIden::Table)
+            .and_where(
+                Expr::col(BillingPortalSettingsIden::ShopId).eq(*shop_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+}
+
+impl From<&Row> for BillingPortalSettings {
+    fn from(row: &Row) -> Self {
+        Self {
+            shop_id: row
+                .get(BillingPortalSettingsIden::ShopId.to_string().as_str()),
+            allow_cancel: row.get(
+                BillingPortalSettingsIden::AllowCancel.to_string().as_str(),
+            ),
+            allow_plan_switch: row.get(
+                BillingPortalSettingsIden::AllowPlanSwitch
+                    .to_string()
+                    .as_str(),
+            ),
+            stripe_configuration_id: row.get(
+                BillingPortalSettingsIden::StripeConfigurationId
+                    .to_string()
+                    .as_str(),
+            ),
+            created_at: row
+                .get(BillingPortalSettingsIden::CreatedAt.to_string().as_str()),
+            updated_at: row
+                .get(BillingPortalSettingsIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for BillingPortalSettings {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
@@ -0,0 +1,98 @@
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{Expr, Iden, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_postgres::PostgresBinder;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "customers")]
+pub enum CustomerIden {
+    Table,
+    UserId,
+    StripeAccountId,
+    StripeCustomerId,
+}
+
+/// Stripe customer of a user on the connected account of a shop.
+pub struct Customer {
+    pub stripe_customer_id: String,
+}
+
+impl Customer {
+    /// Stores the Stripe customer of a user. Returns `None` if another
+    /// request stored a customer for the user in the meantime.
+    pub async fn create(
+        pool: &Pool,
+        user_id: &String,
+        stripe_account_id: &String,
+        stripe_customer_id: &String,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(CustomerIden::Table)
+            .columns([
+                CustomerIden::UserId,
+                CustomerIden::StripeAccountId,
+                CustomerIden::StripeCustomerId,
+            ])
+            .values([
+                user_id.into(),
+                stripe_account_id.into(),
+                stripe_customer_id.into(),
+            ])?
+            .on_conflict(
+                OnConflict::columns([
+                    CustomerIden::UserId,
+                    CustomerIden::StripeAccountId,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        user_id: &String,
+        stripe_account_id: &String,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(CustomerIden::StripeCustomerId)
+            .from(CustomerIden::Table)
+            .and_where(Expr::col(CustomerIden::UserId).eq(user_id))
+            .and_where(
+                Expr::col(CustomerIden::StripeAccountId).eq(stripe_account_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+}
+
+impl From<&Row> for Customer {
+    fn from(row: &Row) -> Self {
+        Self {
+            stripe_customer_id: row
+                .get(CustomerIden::StripeCustomerId.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for Customer {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
@@ -0,0 +1,343 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::{Pool, Transaction};
+use sea_query::{
+    Asterisk, Cond, Expr, Iden, OnConflict, Order, PostgresQueryBuilder, Query,
+    SelectStatement,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+use super::payment::{Payment, PaymentIden};
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "entitlements")]
+pub enum EntitlementIden {
+    Table,
+    EntitlementId,
+    UserId,
+    OfferId,
+    ShopId,
+    PaymentId,
+    StripeSubscriptionId,
+    ValidFrom,
+    ValidUntil,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Access of a user to a digital offer granted by a payment or
+/// subscription.
+pub struct Entitlement {
+    pub entitlement_id: Uuid,
+    pub user_id: String,
+    pub offer_id: Uuid,
+    pub shop_id: Uuid,
+    pub payment_id: Option<Uuid>,
+    pub stripe_subscription_id: Option<String>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Entitlement {
+    /// Creates the entitlement of a payment. Returns `None` if the payment
+    /// already grants an entitlement.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &Pool,
+        user_id: &String,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+        payment_id: &Uuid,
+        stripe_subscription_id: Option<String>,
+        valid_until: Option<DateTime<Utc>>,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(EntitlementIden::Table)
+            .columns([
+                EntitlementIden::EntitlementId,
+                EntitlementIden::UserId,
+                EntitlementIden::OfferId,
+                EntitlementIden::ShopId,
+                EntitlementIden::PaymentId,
+                EntitlementIden::StripeSubscriptionId,
+                EntitlementIden::ValidUntil,
+            ])
+            .values([
+                Uuid::new_v4().into(),
+                user_id.into(),
+                (*offer_id).into(),
+                (*shop_id).into(),
+                (*payment_id).into(),
+                stripe_subscription_id.into(),
+                valid_until.into(),
+            ])?
+            .on_conflict(
+                OnConflict::column(EntitlementIden::PaymentId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Creates the entitlements of claimed payments. Payments which already
+    /// grant an entitlement are skipped.
+    pub async fn create_for_payments(
+        transaction: &Transaction<'_>,
+        user_id: &String,
+        payments: &[&Payment],
+    ) -> Result<(), DbError> {
+        if payments.is_empty() {
+            return Ok(());
+        }
+
+        let (sql, values) = {
+            let mut query = Query::insert();
+            query.into_table(EntitlementIden::Table).columns([
+                EntitlementIden::EntitlementId,
+                EntitlementIden::UserId,
+                EntitlementIden::OfferId,
+                EntitlementIden::ShopId,
+                EntitlementIden::PaymentId,
+            ]);
+            for payment in payments {
+                query.values([
+                    Uuid::new_v4().into(),
+                    user_id.into(),
+                    payment.offer_id.into(),
+                    payment.shop_id.into(),
+                    payment.payment_id.into(),
+                ])?;
+            }
+            query
+                .on_conflict(
+                    OnConflict::column(EntitlementIden::PaymentId)
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .build_postgres(PostgresQueryBuilder)
+        };
+
+        transaction
+            .execute(sql.as_str(), &values.as_params())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Updates the entitlement of a subscription, which may have been
+    /// changed to another offer.
+    pub async fn update_for_subscription(
+        pool: &Pool,
+        stripe_subscription_id: &String,
+        offer_id: Option<Uuid>,
+        valid_until: Option<DateTime<Utc>>,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = {
+            let mut query = Query::update();
+            query
+                .table(EntitlementIden::Table)
+                .values([(EntitlementIden::ValidUntil, valid_until.into())])
+                .and_where(
+                    Expr::col(EntitlementIden::StripeSubscriptionId)
+                        .eq(stripe_subscription_id),
+                );
+            if let Some(offer_id) = offer_id {
+                query.values([(EntitlementIden::OfferId, offer_id.into())]);
+            }
+            query.build_postgres(PostgresQueryBuilder)
+        };
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Ends the entitlements granted by a payment.
+    pub async fn revoke_for_payment(
+        pool: &Pool,
+        payment_id: &Uuid,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(EntitlementIden::Table)
+            .value(EntitlementIden::ValidUntil, Expr::current_timestamp())
+            .and_where(Expr::col(EntitlementIden::PaymentId).eq(*payment_id))
+            .cond_where(Self::valid_condition())
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Ends the entitlements granted by the payment of a payment intent.
+    pub async fn revoke_for_payment_intent(
+        pool: &Pool,
+        stripe_payment_intent_id: &String,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(EntitlementIden::Table)
+            .value(EntitlementIden::ValidUntil, Expr::current_timestamp())
+            .and_where(
+                Expr::col(EntitlementIden::PaymentId).in_subquery(
+                    Query::select()
+                        .column(PaymentIden::PaymentId)
+                        .from(PaymentIden::Table)
+                        .and_where(
+                            Expr::col(PaymentIden::StripePaymentIntentId)
+                                .eq(stripe_payment_intent_id),
+                        )
+                        .to_owned(),
+                ),
+            )
+            .cond_where(Self::valid_condition())
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Returns a currently valid entitlement of the user for the offer.
+    pub async fn get_valid(
+        pool: &Pool,
+        user_id: &String,
+        offer_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Self::select_valid(user_id)
+            .and_where(Expr::col(EntitlementIden::OfferId).eq(*offer_id))
+            .limit(1)
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns a currently valid entitlement of the user granted by a
+    /// subscription of the offer, which may have been changed to the offer.
+    pub async fn get_valid_subscription(
+        pool: &Pool,
+        user_id: &String,
+        offer_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Self::select_valid(user_id)
+            .and_where(Expr::col(EntitlementIden::OfferId).eq(*offer_id))
+            .and_where(
+                Expr::col(EntitlementIden::StripeSubscriptionId).is_not_null(),
+            )
+            .limit(1)
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns the currently valid entitlements of the user.
+    pub async fn list_valid(
+        pool: &Pool,
+        user_id: &String,
+        shop_id: Option<Uuid>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = {
+            let mut query = Self::select_valid(user_id);
+            if let Some(shop_id) = shop_id {
+                query.and_where(Expr::col(EntitlementIden::ShopId).eq(shop_id));
+            }
+            query
+                .limit(limit)
+                .offset(offset)
+                .build_postgres(PostgresQueryBuilder)
+        };
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    fn select_valid(user_id: &String) -> SelectStatement {
+        Query::select()
+            .column(Asterisk)
+            .from(EntitlementIden::Table)
+            .and_where(Expr::col(EntitlementIden::UserId).eq(user_id))
+            .cond_where(Self::valid_condition())
+            .order_by(EntitlementIden::ValidFrom, Order::Desc)
+            .to_owned()
+    }
+
+    fn valid_condition() -> Cond {
+        Cond::all()
+            .add(
+                Expr::col(EntitlementIden::ValidFrom)
+                    .lte(Expr::current_timestamp()),
+            )
+            .add(
+                Cond::any()
+                    .add(Expr::col(EntitlementIden::ValidUntil).is_null())
+                    .add(
+                        Expr::col(EntitlementIden::ValidUntil)
+                            .gt(Expr::current_timestamp()),
+                    ),
+            )
+    }
+}
+
+impl From<&Row> for Entitlement {
+    fn from(row: &Row) -> Self {
+        Self {
+            entitlement_id: row
+                .get(EntitlementIden::EntitlementId.to_string().as_str()),
+            user_id: row.get(EntitlementIden::UserId.to_string().as_str()),
+            offer_id: row.get(EntitlementIden::OfferId.to_string().as_str()),
+            shop_id: row.get(EntitlementIden::ShopId.to_string().as_str()),
+            payment_id: row
+                .get(EntitlementIden::PaymentId.to_string().as_str()),
+            stripe_subscription_id: row.get(
+                EntitlementIden::StripeSubscriptionId.to_string().as_str(),
+            ),
+            valid_from: row
+                .get(EntitlementIden::ValidFrom.to_string().as_str()),
+            valid_until: row
+                .get(EntitlementIden::ValidUntil.to_string().as_str()),
+            created_at: row
+                .get(EntitlementIden::CreatedAt.to_string().as_str()),
+            updated_at: row
+                .get(EntitlementIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for Entitlement {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
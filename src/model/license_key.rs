@@ -0,0 +1,426 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::{Pool, Transaction};
+use sea_query::{
+    Asterisk, Expr, Func, Iden, LockBehavior, LockType, OnConflict, Order,
+    PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+use super::payment::PaymentIden;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "license_key_pools")]
+pub enum LicenseKeyPoolIden {
+    Table,
+    OfferId,
+    ShopId,
+    KeyFormat,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "license_keys")]
+pub enum LicenseKeyIden {
+    Table,
+    LicenseKeyId,
+    OfferId,
+    ShopId,
+    LicenseKey,
+    PaymentId,
+    UserId,
+    AssignedAt,
+    RevokedAt,
+    CreatedAt,
+}
+
+/// License keys of an offer are taken from uploaded keys first. Keys are
+/// generated with the key format once no uploaded keys are left.
+pub struct LicenseKeyPool {
+    pub offer_id: Uuid,
+    pub shop_id: Uuid,
+    pub key_format: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct LicenseKey {
+    pub license_key_id: Uuid,
+    pub offer_id: Uuid,
+    pub shop_id: Uuid,
+    pub license_key: String,
+    pub payment_id: Option<Uuid>,
+    pub assigned_at: Option<DateTime<Utc>>,
+}
+
+impl LicenseKeyPool {
+    pub async fn put(
+        pool: &Pool,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+        key_format: Option<String>,
+    ) -> Result<Self, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(LicenseKeyPoolIden::Table)
+            .columns([
+                LicenseKeyPoolIden::OfferId,
+                LicenseKeyPoolIden::ShopId,
+                LicenseKeyPoolIden::KeyFormat,
+            ])
+            .values([(*offer_id).into(), (*shop_id).into(), key_format.into()])?
+            .on_conflict(
+                OnConflict::column(LicenseKeyPoolIden::OfferId)
+                    .update_columns([LicenseKeyPoolIden::KeyFormat])
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(Self::from(row))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        offer_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(LicenseKeyPoolIden::Table)
+            .and_where(Expr::col(LicenseKeyPoolIden::OfferId).eq(*offer_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+}
+
+impl From<&Row> for LicenseKeyPool {
+    fn from(row: &Row) -> Self {
+        Self {
+            offer_id: row.get(LicenseKeyPoolIden::OfferId.to_string().as_str()),
+            shop_id: row.get(LicenseKeyPoolIden::ShopId.to_string().as_str()),
+            key_format: row
+                .get(LicenseKeyPoolIden::KeyFormat.to_string().as_str()),
+            created_at: row
+                .get(LicenseKeyPoolIden::CreatedAt.to_string().as_str()),
+            updated_at: row
+                .get(LicenseKeyPoolIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for LicenseKeyPool {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
+
+impl LicenseKey {
+    /// Adds uploaded license keys to the pool of an offer. Keys already in
+    /// the pool are skipped. Returns the number of added keys.
+    pub async fn add(
+        pool: &Pool,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+        license_keys: &[String],
+    ) -> Result<u64, DbError> {
+        if license_keys.is_empty() {
+            return Ok(0);
+        }
+
+        let client = pool.get().await?;
+
+        let (sql, values) = {
+            let mut query = Query::insert();
+            query.into_table(LicenseKeyIden::Table).columns([
+                LicenseKeyIden::LicenseKeyId,
+                LicenseKeyIden::OfferId,
+                LicenseKeyIden::ShopId,
+                LicenseKeyIden::LicenseKey,
+            ]);
+            for license_key in license_keys {
+                query.values([
+                    Uuid::new_v4().into(),
+                    (*offer_id).into(),
+                    (*shop_id).into(),
+                    license_key.into(),
+                ])?;
+            }
+            query
+                .on_conflict(
+                    OnConflict::columns([
+                        LicenseKeyIden::OfferId,
+                        LicenseKeyIden::LicenseKey,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .build_postgres(PostgresQueryBuilder)
+        };
+
+        Ok(client.execute(sql.as_str(), &values.as_params()).await?)
+    }
+
+    pub async fn count_available(
+        pool: &Pool,
+        offer_id: &Uuid,
+    ) -> Result<i64, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(LicenseKeyIden::LicenseKeyId)))
+            .from(LicenseKeyIden::Table)
+            .and_where(Expr::col(LicenseKeyIden::OfferId).eq(*offer_id))
+            .and_where(Expr::col(LicenseKeyIden::AssignedAt).is_null())
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(row.get(0))
+    }
+
+    /// Assigns the oldest available uploaded key of the offer to the
+    /// payment. Concurrent assignments skip keys locked by each other.
+    pub async fn assign(
+        pool: &Pool,
+        offer_id: &Uuid,
+        payment_id: &Uuid,
+        user_id: Option<String>,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(LicenseKeyIden::Table)
+            .values([
+                (LicenseKeyIden::PaymentId, (*payment_id).into()),
+                (LicenseKeyIden::UserId, user_id.into()),
+            ])
+            .value(LicenseKeyIden::AssignedAt, Expr::current_timestamp())
+            .and_where(
+                Expr::col(LicenseKeyIden::LicenseKeyId).in_subquery(
+                    Query::select()
+                        .column(LicenseKeyIden::LicenseKeyId)
+                        .from(LicenseKeyIden::Table)
+                        .and_where(
+                            Expr::col(LicenseKeyIden::OfferId).eq(*offer_id),
+                        )
+                        .and_where(
+                            Expr::col(LicenseKeyIden::AssignedAt).is_null(),
+                        )
+                        .order_by(LicenseKeyIden::CreatedAt, Order::Asc)
+                        .limit(1)
+                        .lock_with_behavior(
+                            LockType::Update,
+                            LockBehavior::SkipLocked,
+                        )
+                        .to_owned(),
+                ),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns whether a license key was assigned to the payment before.
+    pub async fn is_assigned(
+        pool: &Pool,
+        payment_id: &Uuid,
+    ) -> Result<bool, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(LicenseKeyIden::LicenseKeyId)
+            .from(LicenseKeyIden::Table)
+            .and_where(Expr::col(LicenseKeyIden::PaymentId).eq(*payment_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .is_some())
+    }
+
+    /// Creates a generated license key assigned to the payment. Returns
+    /// `None` if the key exists already or the payment has a key.
+    pub async fn create_assigned(
+        pool: &Pool,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+        license_key: &String,
+        payment_id: &Uuid,
+        user_id: Option<String>,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(LicenseKeyIden::Table)
+            .columns([
+                LicenseKeyIden::LicenseKeyId,
+                LicenseKeyIden::OfferId,
+                LicenseKeyIden::ShopId,
+                LicenseKeyIden::LicenseKey,
+                LicenseKeyIden::PaymentId,
+                LicenseKeyIden::UserId,
+                LicenseKeyIden::AssignedAt,
+            ])
+            .values([
+                Uuid::new_v4().into(),
+                (*offer_id).into(),
+                (*shop_id).into(),
+                license_key.into(),
+                (*payment_id).into(),
+                user_id.into(),
+                Expr::current_timestamp().into(),
+            ])?
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns the license keys assigned to the user which are not revoked.
+    pub async fn list_for_user(
+        pool: &Pool,
+        user_id: &String,
+        offer_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = {
+            let mut query = Query::select();
+            query
+                .column(Asterisk)
+                .from(LicenseKeyIden::Table)
+                .and_where(Expr::col(LicenseKeyIden::UserId).eq(user_id))
+                .and_where(Expr::col(LicenseKeyIden::RevokedAt).is_null())
+                .order_by(LicenseKeyIden::AssignedAt, Order::Desc);
+            if let Some(offer_id) = offer_id {
+                query
+                    .and_where(Expr::col(LicenseKeyIden::OfferId).eq(offer_id));
+            }
+            query.build_postgres(PostgresQueryBuilder)
+        };
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    /// Assigns the license keys of claimed guest payments to the user.
+    pub async fn claim(
+        transaction: &Transaction<'_>,
+        payment_ids: &[Uuid],
+        user_id: &String,
+    ) -> Result<(), DbError> {
+        if payment_ids.is_empty() {
+            return Ok(());
+        }
+
+        let (sql, values) = Query::update()
+            .table(LicenseKeyIden::Table)
+            .values([(LicenseKeyIden::UserId, user_id.into())])
+            .and_where(
+                Expr::col(LicenseKeyIden::PaymentId)
+                    .is_in(payment_ids.iter().copied()),
+            )
+            .and_where(Expr::col(LicenseKeyIden::UserId).is_null())
+            .build_postgres(PostgresQueryBuilder);
+
+        transaction
+            .execute(sql.as_str(), &values.as_params())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Revokes the license keys assigned to a payment.
+    pub async fn revoke_for_payment(
+        pool: &Pool,
+        payment_id: &Uuid,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(LicenseKeyIden::Table)
+            .value(LicenseKeyIden::RevokedAt, Expr::current_timestamp())
+            .and_where(Expr::col(LicenseKeyIden::PaymentId).eq(*payment_id))
+            .and_where(Expr::col(LicenseKeyIden::RevokedAt).is_null())
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Revokes the license keys assigned to the payment of a payment intent.
+    pub async fn revoke_for_payment_intent(
+        pool: &Pool,
+        stripe_payment_intent_id: &String,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(LicenseKeyIden::Table)
+            .value(LicenseKeyIden::RevokedAt, Expr::current_timestamp())
+            .and_where(
+                Expr::col(LicenseKeyIden::PaymentId).in_subquery(
+                    Query::select()
+                        .column(PaymentIden::PaymentId)
+                        .from(PaymentIden::Table)
+                        .and_where(
+                            Expr::col(PaymentIden::StripePaymentIntentId)
+                                .eq(stripe_payment_intent_id),
+                        )
+                        .to_owned(),
+                ),
+            )
+            .and_where(Expr::col(LicenseKeyIden::RevokedAt).is_null())
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+}
+
+impl From<&Row> for LicenseKey {
+    fn from(row: &Row) -> Self {
+        Self {
+            license_key_id: row
+                .get(LicenseKeyIden::LicenseKeyId.to_string().as_str()),
+            offer_id: row.get(LicenseKeyIden::OfferId.to_string().as_str()),
+            shop_id: row.get(LicenseKeyIden::ShopId.to_string().as_str()),
+            license_key: row
+                .get(LicenseKeyIden::LicenseKey.to_string().as_str()),
+            payment_id: row.get(LicenseKeyIden::PaymentId.to_string().as_str()),
+            assigned_at: row
+                .get(LicenseKeyIden::AssignedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for LicenseKey {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
@@ -1,3 +1,31 @@
+mod abandoned_checkout;
+mod billing_portal_settings;
+mod customer;
+mod entitlement;
+mod license_key;
+mod offer_settings;
+mod offer_stock;
+mod order;
+mod payment;
+mod purchase_reservation;
+mod shipping_option;
+mod shipping_zone;
+mod shop_tax_settings;
 mod stripe_account;
+mod subscription_cancellation;
 
+pub use abandoned_checkout::AbandonedCheckout;
+pub use billing_portal_settings::BillingPortalSettings;
+pub use customer::Customer;
+pub use entitlement::Entitlement;
+pub use license_key::{LicenseKey, LicenseKeyPool};
+pub use offer_settings::OfferSettings;
+pub use offer_stock::OfferStock;
+pub use order::{Order, OrderEvent, ShippingAddress};
+pub use payment::{Payment, PaymentAmounts};
+pub use purchase_reservation::PurchaseReservation;
+pub use shipping_option::ShippingOption;
+pub use shipping_zone::{ShippingZone, ShippingZoneRate};
+pub use shop_tax_settings::ShopTaxSettings;
 pub use stripe_account::StripeAccount;
+pub use subscription_cancellation::SubscriptionCancellation;
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{
+    Asterisk, Expr, Iden, OnConflict, PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "offer_settings")]
+pub enum OfferSettingsIden {
+    Table,
+    OfferId,
+    ShopId,
+    ManualCapture,
+    TrialWithoutPaymentMethod,
+    AllowMultipleSubscriptions,
+    MaxQuantityPerSession,
+    MaxQuantityPerBuyer,
+    CreatedAt,
+    UpdatedAt,
+}
+
+pub struct OfferSettings {
+    pub offer_id: Uuid,
+    pub shop_id: Uuid,
+    pub manual_capture: bool,
+    pub trial_without_payment_method: bool,
+    pub allow_multiple_subscriptions: bool,
+    pub max_quantity_per_session: Option<i64>,
+    pub max_quantity_per_buyer: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OfferSettings {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn put(
+        pool: &Pool,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+        manual_capture: bool,
+        trial_without_payment_method: bool,
+        allow_multiple_subscriptions: bool,
+        max_quantity_per_session: Option<i64>,
+        max_quantity_per_buyer: Option<i64>,
+    ) -> Result<Self, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(OfferSettingsIden::Table)
+            .columns([
+                OfferSettingsIden::OfferId,
+                OfferSettingsIden::ShopId,
+                OfferSettingsIden::ManualCapture,
+                OfferSettingsIden::TrialWithoutPaymentMethod,
+                OfferSettingsIden::AllowMultipleSubscriptions,
+                OfferSettingsIden::MaxQuantityPerSession,
+                OfferSettingsIden::MaxQuantityPerBuyer,
+            ])
+            .values([
+                (*offer_id).into(),
+                (*shop_id).into(),
+                manual_capture.into(),
+                trial_without_payment_method.into(),
+                allow_multiple_subscriptions.into(),
+                max_quantity_per_session.into(),
+                max_quantity_per_buyer.into(),
+            ])?
+            .on_conflict(
+                OnConflict::column(OfferSettingsIden::OfferId)
+                    .update_columns([
+                        OfferSettingsIden::ManualCapture,
+                        OfferSettingsIden::TrialWithoutPaymentMethod,
+                        OfferSettingsIden::AllowMultipleSubscriptions,
+                        OfferSettingsIden::MaxQuantityPerSession,
+                        OfferSettingsIden::MaxQuantityPerBuyer,
+                    ])
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(Self::from(row))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        offer_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(OfferSettingsIden::Table)
+            .and_where(Expr::col(OfferSettingsIden::OfferId).eq(*offer_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+}
+
+impl From<&Row> for OfferSettings {
+    fn from(row: &Row) -> Self {
+        Self {
+            offer_id: row.get(OfferSettingsIden::OfferId.to_string().as_str()),
+            shop_id: row.get(OfferSettingsIden::ShopId.to_string().as_str()),
+            manual_capture: row
+                .get(OfferSettingsIden::ManualCapture.to_string().as_str()),
+            trial_without_payment_method: row.get(
+                OfferSettingsIden::TrialWithoutPaymentMethod
+                    .to_string()
+                    .as_str(),
+            ),
+            allow_multiple_subscriptions: row.get(
+                OfferSettingsIden::AllowMultipleSubscriptions
+                    .to_string()
+                    .as_str(),
+            ),
+            max_quantity_per_session: row.get(
+                OfferSettingsIden::MaxQuantityPerSession
+                    .to_string()
+                    .as_str(),
+            ),
+            max_quantity_per_buyer: row.get(
+                OfferSettingsIden::MaxQuantityPerBuyer.to_string().as_str(),
+            ),
+            created_at: row
+                .get(OfferSettingsIden::CreatedAt.to_string().as_str()),
+            updated_at: row
+                .get(OfferSettingsIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for OfferSettings {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
@@ -0,0 +1,350 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{
+    Asterisk, Expr, Iden, OnConflict, PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "offer_stocks")]
+pub enum OfferStockIden {
+    Table,
+    OfferId,
+    ShopId,
+    Quantity,
+    Reserved,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "stock_reservations")]
+pub enum StockReservationIden {
+    Table,
+    StripeCheckoutSessionId,
+    OfferId,
+    Quantity,
+    ExpiresAt,
+}
+
+/// Stock of an offer. Quantities reserved by open checkout sessions are not
+/// available to other buyers.
+pub struct OfferStock {
+    pub offer_id: Uuid,
+    pub shop_id: Uuid,
+    pub quantity: i64,
+    pub reserved: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OfferStock {
+    pub fn available(&self) -> i64 {
+        (self.quantity - self.reserved).max(0)
+    }
+
+    /// Returns whether more was sold and reserved than there is in stock.
+    pub fn is_oversold(&self) -> bool {
+        self.quantity < self.reserved
+    }
+
+    /// Sets the quantity in stock. Reservations are kept.
+    pub async fn put(
+        pool: &Pool,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+        quantity: i64,
+    ) -> Result<Self, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(OfferStockIden::Table)
+            .columns([
+                OfferStockIden::OfferId,
+                OfferStockIden::ShopId,
+                OfferStockIden::Quantity,
+            ])
+            .values([(*offer_id).into(), (*shop_id).into(), quantity.into()])?
+            .on_conflict(
+                OnConflict::column(OfferStockIden::OfferId)
+                    .update_columns([OfferStockIden::Quantity])
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(Self::from(row))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        offer_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(OfferStockIden::Table)
+            .and_where(Expr::col(OfferStockIden::OfferId).eq(*offer_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Stops tracking the stock of an offer.
+    pub async fn delete(
+        pool: &Pool,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+    ) -> Result<bool, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::delete()
+            .from_table(OfferStockIden::Table)
+            .and_where(Expr::col(OfferStockIden::OfferId).eq(*offer_id))
+            .and_where(Expr::col(OfferStockIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        let deleted = client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Reserves `quantity` if it is available. Returns `None` if the stock
+    /// is not sufficient.
+    pub async fn reserve(
+        pool: &Pool,
+        offer_id: &Uuid,
+        quantity: i64,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(OfferStockIden::Table)
+            .value(
+                OfferStockIden::Reserved,
+                Expr::col(OfferStockIden::Reserved).add(quantity),
+            )
+            .and_where(Expr::col(OfferStockIden::OfferId).eq(*offer_id))
+            .and_where(
+                Expr::expr(
+                    Expr::col(OfferStockIden::Quantity)
+                        .sub(Expr::col(OfferStockIden::Reserved)),
+                )
+                .gte(quantity),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Removes `quantity` from the stock if it is available. Used for
+    /// purchases without checkout session. Returns `None` if the stock is
+    /// not sufficient.
+    pub async fn take(
+        pool: &Pool,
+        offer_id: &Uuid,
+        quantity: i64,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(OfferStockIden::Table)
+            .value(
+                OfferStockIden::Quantity,
+                Expr::col(OfferStockIden::Quantity).sub(quantity),
+            )
+            .and_where(Expr::col(OfferStockIden::OfferId).eq(*offer_id))
+            .and_where(
+                Expr::expr(
+                    Expr::col(OfferStockIden::Quantity)
+                        .sub(Expr::col(OfferStockIden::Reserved)),
+                )
+                .gte(quantity),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns `quantity` taken from the stock before.
+    pub async fn restock(
+        pool: &Pool,
+        offer_id: &Uuid,
+        quantity: i64,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(OfferStockIden::Table)
+            .value(
+                OfferStockIden::Quantity,
+                Expr::col(OfferStockIden::Quantity).add(quantity),
+            )
+            .and_where(Expr::col(OfferStockIden::OfferId).eq(*offer_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Releases a reservation which has no checkout session.
+    pub async fn release(
+        pool: &Pool,
+        offer_id: &Uuid,
+        quantity: i64,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(OfferStockIden::Table)
+            .value(
+                OfferStockIden::Reserved,
+                Expr::cust_with_exprs(
+                    "GREATEST($1 - $2, 0)",
+                    [
+                        Expr::col(OfferStockIden::Reserved).into(),
+                        Expr::val(quantity).into(),
+                    ],
+                ),
+            )
+            .and_where(Expr::col(OfferStockIden::OfferId).eq(*offer_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Records the reservation of a checkout session.
+    pub async fn create_reservation(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+        offer_id: &Uuid,
+        quantity: i64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(StockReservationIden::Table)
+            .columns([
+                StockReservationIden::StripeCheckoutSessionId,
+                StockReservationIden::OfferId,
+                StockReservationIden::Quantity,
+                StockReservationIden::ExpiresAt,
+            ])
+            .values([
+                stripe_checkout_session_id.into(),
+                (*offer_id).into(),
+                quantity.into(),
+                expires_at.into(),
+            ])?
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Settles the reservation of a checkout session by removing
+    /// `purchased` from the stock and releasing the reserved quantity.
+    /// Expired checkout sessions settle with nothing purchased. Returns
+    /// `None` if the checkout session has no reservation or the stock is no
+    /// longer tracked.
+    pub async fn settle_reservation(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+        purchased: i64,
+    ) -> Result<Option<Self>, DbError> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = Query::delete()
+            .from_table(StockReservationIden::Table)
+            .and_where(
+                Expr::col(StockReservationIden::StripeCheckoutSessionId)
+                    .eq(stripe_checkout_session_id),
+            )
+            .returning(Query::returning().columns([
+                StockReservationIden::OfferId,
+                StockReservationIden::Quantity,
+            ]))
+            .build_postgres(PostgresQueryBuilder);
+
+        let Some(row) = transaction
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+        else {
+            return Ok(None);
+        };
+        let offer_id: Uuid = row.get(0);
+        let reserved: i64 = row.get(1);
+
+        let (sql, values) = Query::update()
+            .table(OfferStockIden::Table)
+            .value(
+                OfferStockIden::Quantity,
+                Expr::col(OfferStockIden::Quantity).sub(purchased),
+            )
+            .value(
+                OfferStockIden::Reserved,
+                Expr::cust_with_exprs(
+                    "GREATEST($1 - $2, 0)",
+                    [
+                        Expr::col(OfferStockIden::Reserved).into(),
+                        Expr::val(reserved).into(),
+                    ],
+                ),
+            )
+            .and_where(Expr::col(OfferStockIden::OfferId).eq(offer_id))
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let stock = transaction
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from);
+
+        transaction.commit().await?;
+
+        Ok(stock)
+    }
+}
+
+impl From<&Row> for OfferStock {
+    fn from(row: &Row) -> Self {
+        Self {
+            offer_id: row.get(OfferStockIden::OfferId.to_string().as_str()),
+            shop_id: row.get(OfferStockIden::ShopId.to_string().as_str()),
+            quantity: row.get(OfferStockIden::Quantity.to_string().as_str()),
+            reserved: row.get(OfferStockIden::Reserved.to_string().as_str()),
+            created_at: row.get(OfferStockIden::CreatedAt.to_string().as_str()),
+            updated_at: row.get(OfferStockIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for OfferStock {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
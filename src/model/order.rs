@@ -0,0 +1,454 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::{Pool, Transaction};
+use sea_query::{
+    Asterisk, Expr, Iden, OnConflict, Order as SortOrder, PostgresQueryBuilder,
+    Query, SelectStatement,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+use super::payment::PaymentIden;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "orders")]
+pub enum OrderIden {
+    Table,
+    OrderId,
+    PaymentId,
+    ShopId,
+    OfferId,
+    Status,
+    ShippingName,
+    ShippingLine1,
+    ShippingLine2,
+    ShippingPostalCode,
+    ShippingCity,
+    ShippingState,
+    ShippingCountry,
+    Carrier,
+    TrackingNumber,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "order_events")]
+pub enum OrderEventIden {
+    Table,
+    OrderEventId,
+    OrderId,
+    ShopId,
+    FromStatus,
+    ToStatus,
+    CreatedAt,
+    PublishedAt,
+}
+
+/// Shipping address collected in the checkout session of a physical offer.
+#[derive(Default)]
+pub struct ShippingAddress {
+    pub name: Option<String>,
+    pub line1: Option<String>,
+    pub line2: Option<String>,
+    pub postal_code: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+}
+
+/// Fulfillment of a paid physical offer.
+pub struct Order {
+    pub order_id: Uuid,
+    pub payment_id: Uuid,
+    pub shop_id: Uuid,
+    pub offer_id: Uuid,
+    pub status: String,
+    pub shipping_address: ShippingAddress,
+    pub carrier: Option<String>,
+    pub tracking_number: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Status transition of an order. Orders are created without a previous
+/// status.
+pub struct OrderEvent {
+    pub order_event_id: Uuid,
+    pub order_id: Uuid,
+    pub shop_id: Uuid,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Order {
+    /// Creates the order of a payment. Returns `None` if the payment already
+    /// has an order.
+    pub async fn create(
+        pool: &Pool,
+        payment_id: &Uuid,
+        shop_id: &Uuid,
+        offer_id: &Uuid,
+        status: &str,
+        shipping_address: ShippingAddress,
+    ) -> Result<Option<Self>, DbError> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(OrderIden::Table)
+            .columns([
+                OrderIden::OrderId,
+                OrderIden::PaymentId,
+                OrderIden::ShopId,
+                OrderIden::OfferId,
+                OrderIden::Status,
+                OrderIden::ShippingName,
+                OrderIden::ShippingLine1,
+                OrderIden::ShippingLine2,
+                OrderIden::ShippingPostalCode,
+                OrderIden::ShippingCity,
+                OrderIden::ShippingState,
+                OrderIden::ShippingCountry,
+            ])
+            .values([
+                Uuid::new_v4().into(),
+                (*payment_id).into(),
+                (*shop_id).into(),
+                (*offer_id).into(),
+                status.into(),
+                shipping_address.name.into(),
+                shipping_address.line1.into(),
+                shipping_address.line2.into(),
+                shipping_address.postal_code.into(),
+                shipping_address.city.into(),
+                shipping_address.state.into(),
+                shipping_address.country.into(),
+            ])?
+            .on_conflict(
+                OnConflict::column(OrderIden::PaymentId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let Some(row) = transaction
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+        else {
+            return Ok(None);
+        };
+        let order = Self::from(row);
+
+        OrderEvent::create(&transaction, &order, None).await?;
+
+        transaction.commit().await?;
+
+        Ok(Some(order))
+    }
+
+    /// Updates the status of an order if it is still in `from_status` and
+    /// records the transition. Carrier and tracking number are only updated
+    /// if given. Returns `None` if no order was updated.
+    pub async fn update_status(
+        pool: &Pool,
+        order_id: &Uuid,
+        shop_id: &Uuid,
+        from_status: &str,
+        status: &str,
+        carrier: Option<String>,
+        tracking_number: Option<String>,
+    ) -> Result<Option<Self>, DbError> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = {
+            let mut query = Query::update();
+            query
+                .table(OrderIden::Table)
+                .values([(OrderIden::Status, status.into())])
+                .and_where(Expr::col(OrderIden::OrderId).eq(*order_id))
+                .and_where(Expr::col(OrderIden::ShopId).eq(*shop_id))
+                .and_where(Expr::col(OrderIden::Status).eq(from_status));
+            if let Some(carrier) = carrier {
+                query.values([(OrderIden::Carrier, carrier.into())]);
+            }
+            if let Some(tracking_number) = tracking_number {
+                query.values([(
+                    OrderIden::TrackingNumber,
+                    tracking_number.into(),
+                )]);
+            }
+            query.returning_all().build_postgres(PostgresQueryBuilder)
+        };
+
+        let Some(row) = transaction
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+        else {
+            return Ok(None);
+        };
+        let order = Self::from(row);
+
+        OrderEvent::create(&transaction, &order, Some(from_status)).await?;
+
+        transaction.commit().await?;
+
+        Ok(Some(order))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        order_id: &Uuid,
+        shop_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(OrderIden::Table)
+            .and_where(Expr::col(OrderIden::OrderId).eq(*order_id))
+            .and_where(Expr::col(OrderIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    pub async fn list(
+        pool: &Pool,
+        shop_id: &Uuid,
+        status: Option<&str>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = {
+            let mut query = Query::select();
+            query
+                .column(Asterisk)
+                .from(OrderIden::Table)
+                .and_where(Expr::col(OrderIden::ShopId).eq(*shop_id))
+                .order_by(OrderIden::CreatedAt, SortOrder::Desc)
+                .limit(limit)
+                .offset(offset);
+            if let Some(status) = status {
+                query.and_where(Expr::col(OrderIden::Status).eq(status));
+            }
+            query.build_postgres(PostgresQueryBuilder)
+        };
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    /// Returns an order of a payment of the user.
+    pub async fn get_for_user(
+        pool: &Pool,
+        order_id: &Uuid,
+        user_id: &String,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Self::select_for_user(user_id)
+            .and_where(
+                Expr::col((OrderIden::Table, OrderIden::OrderId)).eq(*order_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns the orders of the payments of the user.
+    pub async fn list_for_user(
+        pool: &Pool,
+        user_id: &String,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Self::select_for_user(user_id)
+            .order_by((OrderIden::Table, OrderIden::CreatedAt), SortOrder::Desc)
+            .limit(limit)
+            .offset(offset)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    fn select_for_user(user_id: &String) -> SelectStatement {
+        Query::select()
+            .column((OrderIden::Table, Asterisk))
+            .from(OrderIden::Table)
+            .inner_join(
+                PaymentIden::Table,
+                Expr::col((PaymentIden::Table, PaymentIden::PaymentId))
+                    .equals((OrderIden::Table, OrderIden::PaymentId)),
+            )
+            .and_where(
+                Expr::col((PaymentIden::Table, PaymentIden::UserId))
+                    .eq(user_id),
+            )
+            .to_owned()
+    }
+}
+
+impl From<&Row> for Order {
+    fn from(row: &Row) -> Self {
+        Self {
+            order_id: row.get(OrderIden::OrderId.to_string().as_str()),
+            payment_id: row.get(OrderIden::PaymentId.to_string().as_str()),
+            shop_id: row.get(OrderIden::ShopId.to_string().as_str()),
+            offer_id: row.get(OrderIden::OfferId.to_string().as_str()),
+            status: row.get(OrderIden::Status.to_string().as_str()),
+            shipping_address: ShippingAddress {
+                name: row.get(OrderIden::ShippingName.to_string().as_str()),
+                line1: row.get(OrderIden::ShippingLine1.to_string().as_str()),
+                line2: row.get(OrderIden::ShippingLine2.to_string().as_str()),
+                postal_code: row
+                    .get(OrderIden::ShippingPostalCode.to_string().as_str()),
+                city: row.get(OrderIden::ShippingCity.to_string().as_str()),
+                state: row.get(OrderIden::ShippingState.to_string().as_str()),
+                country: row
+                    .get(OrderIden::ShippingCountry.to_string().as_str()),
+            },
+            carrier: row.get(OrderIden::Carrier.to_string().as_str()),
+            tracking_number: row
+                .get(OrderIden::TrackingNumber.to_string().as_str()),
+            created_at: row.get(OrderIden::CreatedAt.to_string().as_str()),
+            updated_at: row.get(OrderIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for Order {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
+
+impl OrderEvent {
+    async fn create(
+        transaction: &Transaction<'_>,
+        order: &Order,
+        from_status: Option<&str>,
+    ) -> Result<(), DbError> {
+        let (sql, values) = Query::insert()
+            .into_table(OrderEventIden::Table)
+            .columns([
+                OrderEventIden::OrderEventId,
+                OrderEventIden::OrderId,
+                OrderEventIden::ShopId,
+                OrderEventIden::FromStatus,
+                OrderEventIden::ToStatus,
+            ])
+            .values([
+                Uuid::new_v4().into(),
+                order.order_id.into(),
+                order.shop_id.into(),
+                from_status.into(),
+                order.status.as_str().into(),
+            ])?
+            .build_postgres(PostgresQueryBuilder);
+
+        transaction
+            .execute(sql.as_str(), &values.as_params())
+            .await?;
+
+        tracing::log::info!(
+            "Order {} of shop {} changed from {} to {}",
+            order.order_id,
+            order.shop_id,
+            from_status.unwrap_or("none"),
+            order.status
+        );
+
+        Ok(())
+    }
+
+    pub async fn list(
+        pool: &Pool,
+        order_id: &Uuid,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(OrderEventIden::Table)
+            .and_where(Expr::col(OrderEventIden::OrderId).eq(*order_id))
+            .order_by(OrderEventIden::CreatedAt, SortOrder::Asc)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    /// Returns the oldest events which were not published yet.
+    pub async fn list_unpublished(
+        pool: &Pool,
+        limit: u64,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(OrderEventIden::Table)
+            .and_where(Expr::col(OrderEventIden::PublishedAt).is_null())
+            .order_by(OrderEventIden::CreatedAt, SortOrder::Asc)
+            .limit(limit)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    pub async fn mark_published(
+        pool: &Pool,
+        order_event_id: &Uuid,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(OrderEventIden::Table)
+            .value(OrderEventIden::PublishedAt, Expr::current_timestamp())
+            .and_where(
+                Expr::col(OrderEventIden::OrderEventId).eq(*order_event_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+}
+
+impl From<&Row> for OrderEvent {
+    fn from(row: &Row) -> Self {
+        Self {
+            order_event_id: row
+                .get(OrderEventIden::OrderEventId.to_string().as_str()),
+            order_id: row.get(OrderEventIden::OrderId.to_string().as_str()),
+            shop_id: row.get(OrderEventIden::ShopId.to_string().as_str()),
+            from_status: row
+                .get(OrderEventIden::FromStatus.to_string().as_str()),
+            to_status: row.get(OrderEventIden::ToStatus.to_string().as_str()),
+            created_at: row.get(OrderEventIden::CreatedAt.to_string().as_str()),
+        }
+    }
+}
@@ -0,0 +1,577 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{
+    Alias, Asterisk, Cond, Expr, Func, Iden, OnConflict, Order,
+    PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+use super::{Entitlement, LicenseKey};
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "payments")]
+pub enum PaymentIden {
+    Table,
+    PaymentId,
+    ShopId,
+    OfferId,
+    UserId,
+    StripeCheckoutSessionId,
+    StripePaymentIntentId,
+    StripeSubscriptionId,
+    StripeCustomerId,
+    PaymentStatus,
+    Currency,
+    AmountSubtotal,
+    AmountDiscount,
+    AmountShipping,
+    AmountTax,
+    AmountTotal,
+    AuthorizationExpiresAt,
+    TrialEndsAt,
+    BuyerEmail,
+    Quantity,
+    CreatedAt,
+    UpdatedAt,
+}
+
+pub struct Payment {
+    pub payment_id: Uuid,
+    pub shop_id: Uuid,
+    pub offer_id: Uuid,
+    pub user_id: Option<String>,
+    pub stripe_checkout_session_id: Option<String>,
+    pub stripe_payment_intent_id: Option<String>,
+    pub stripe_subscription_id: Option<String>,
+    pub stripe_customer_id: Option<String>,
+    pub payment_status: String,
+    pub currency: String,
+    pub amount_subtotal: i64,
+    pub amount_discount: i64,
+    pub amount_shipping: i64,
+    pub amount_tax: i64,
+    pub amount_total: i64,
+    pub authorization_expires_at: Option<DateTime<Utc>>,
+    pub trial_ends_at: Option<DateTime<Utc>>,
+    pub buyer_email: Option<String>,
+    /// Quantities of payments made before purchase limits were introduced
+    /// are not known.
+    pub quantity: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Amounts of a payment in the smallest currency unit.
+pub struct PaymentAmounts {
+    pub subtotal: i64,
+    pub discount: i64,
+    pub shipping: i64,
+    pub tax: i64,
+    pub total: i64,
+}
+
+impl Payment {
+    /// Records a completed checkout session. Stripe may deliver webhook
+    /// events more than once, so `None` is returned if the checkout session
+    /// was recorded before. Free purchases have no checkout session, `None`
+    /// is returned if the buyer already got the offer for free.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &Pool,
+        shop_id: &Uuid,
+        offer_id: &Uuid,
+        user_id: Option<String>,
+        stripe_checkout_session_id: Option<String>,
+        stripe_payment_intent_id: Option<String>,
+        stripe_subscription_id: Option<String>,
+        stripe_customer_id: Option<String>,
+        payment_status: &str,
+        currency: &str,
+        amounts: &PaymentAmounts,
+        authorization_expires_at: Option<DateTime<Utc>>,
+        trial_ends_at: Option<DateTime<Utc>>,
+        buyer_email: Option<String>,
+        quantity: i64,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(PaymentIden::Table)
+            .columns([
+                PaymentIden::PaymentId,
+                PaymentIden::ShopId,
+                PaymentIden::OfferId,
+                PaymentIden::UserId,
+                PaymentIden::StripeCheckoutSessionId,
+                PaymentIden::StripePaymentIntentId,
+                PaymentIden::StripeSubscriptionId,
+                PaymentIden::StripeCustomerId,
+                PaymentIden::PaymentStatus,
+                PaymentIden::Currency,
+                PaymentIden::AmountSubtotal,
+                PaymentIden::AmountDiscount,
+                PaymentIden::AmountShipping,
+                PaymentIden::AmountTax,
+                PaymentIden::AmountTotal,
+                PaymentIden::AuthorizationExpiresAt,
+                PaymentIden::TrialEndsAt,
+                PaymentIden::BuyerEmail,
+                PaymentIden::Quantity,
+            ])
+            .values([
+                Uuid::new_v4().into(),
+                (*shop_id).into(),
+                (*offer_id).into(),
+                user_id.into(),
+                stripe_checkout_session_id.into(),
+                stripe_payment_intent_id.into(),
+                stripe_subscription_id.into(),
+                stripe_customer_id.into(),
+                payment_status.into(),
+                currency.into(),
+                amounts.subtotal.into(),
+                amounts.discount.into(),
+                amounts.shipping.into(),
+                amounts.tax.into(),
+                amounts.total.into(),
+                authorization_expires_at.into(),
+                trial_ends_at.into(),
+                buyer_email.map(|e| e.to_lowercase()).into(),
+                quantity.into(),
+            ])?
+            .on_conflict(OnConflict::new().do_nothing().to_owned())
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    pub async fn list(
+        pool: &Pool,
+        shop_id: &Uuid,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(PaymentIden::Table)
+            .and_where(Expr::col(PaymentIden::ShopId).eq(*shop_id))
+            .order_by(PaymentIden::CreatedAt, Order::Desc)
+            .limit(limit)
+            .offset(offset)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    /// Returns the Stripe customer of the most recent payment of a user in a
+    /// shop.
+    pub async fn get_latest_stripe_customer_id(
+        pool: &Pool,
+        shop_id: &Uuid,
+        user_id: &String,
+    ) -> Result<Option<String>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(PaymentIden::StripeCustomerId)
+            .from(PaymentIden::Table)
+            .and_where(Expr::col(PaymentIden::ShopId).eq(*shop_id))
+            .and_where(Expr::col(PaymentIden::UserId).eq(user_id))
+            .and_where(Expr::col(PaymentIden::StripeCustomerId).is_not_null())
+            .order_by(PaymentIden::CreatedAt, Order::Desc)
+            .limit(1)
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(|row| row.get(0)))
+    }
+
+    /// Returns the offers a user subscribed to in a shop.
+    pub async fn list_subscribed_offer_ids(
+        pool: &Pool,
+        shop_id: &Uuid,
+        user_id: &String,
+    ) -> Result<Vec<Uuid>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .distinct()
+            .column(PaymentIden::OfferId)
+            .from(PaymentIden::Table)
+            .and_where(Expr::col(PaymentIden::ShopId).eq(*shop_id))
+            .and_where(Expr::col(PaymentIden::UserId).eq(user_id))
+            .and_where(
+                Expr::col(PaymentIden::StripeSubscriptionId).is_not_null(),
+            )
+            .order_by(PaymentIden::OfferId, Order::Asc)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    pub async fn get_for_checkout_session(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(PaymentIden::Table)
+            .and_where(
+                Expr::col(PaymentIden::StripeCheckoutSessionId)
+                    .eq(stripe_checkout_session_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        payment_id: &Uuid,
+        shop_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(PaymentIden::Table)
+            .and_where(Expr::col(PaymentIden::PaymentId).eq(*payment_id))
+            .and_where(Expr::col(PaymentIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Updates the status of the payment of a payment intent if it is still
+    /// in `from_status`. Returns `None` if no payment was updated.
+    pub async fn update_status(
+        pool: &Pool,
+        stripe_payment_intent_id: &String,
+        from_status: &str,
+        payment_status: &str,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(PaymentIden::Table)
+            .values([(PaymentIden::PaymentStatus, payment_status.into())])
+            .and_where(
+                Expr::col(PaymentIden::StripePaymentIntentId)
+                    .eq(stripe_payment_intent_id),
+            )
+            .and_where(Expr::col(PaymentIden::PaymentStatus).eq(from_status))
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Updates the status of the payment of a checkout session if it is still
+    /// in `from_status`. Returns `None` if no payment was updated.
+    pub async fn update_status_for_checkout_session(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+        from_status: &str,
+        payment_status: &str,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(PaymentIden::Table)
+            .values([(PaymentIden::PaymentStatus, payment_status.into())])
+            .and_where(
+                Expr::col(PaymentIden::StripeCheckoutSessionId)
+                    .eq(stripe_checkout_session_id),
+            )
+            .and_where(Expr::col(PaymentIden::PaymentStatus).eq(from_status))
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns the payments of guest purchases made with the email address.
+    pub async fn list_unclaimed(
+        pool: &Pool,
+        buyer_email: &str,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(PaymentIden::Table)
+            .and_where(
+                Expr::col(PaymentIden::BuyerEmail)
+                    .eq(buyer_email.to_lowercase()),
+            )
+            .and_where(Expr::col(PaymentIden::UserId).is_null())
+            .and_where(
+                Expr::col(PaymentIden::PaymentStatus)
+                    .is_not_in(["refunded", "canceled", "failed"]),
+            )
+            .order_by(PaymentIden::CreatedAt, Order::Asc)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    /// Assigns payments of guest purchases and their license keys to the
+    /// user. Completed purchases of digital offers grant access to the
+    /// user. Returns the payments which were not claimed before.
+    pub async fn claim(
+        pool: &Pool,
+        payment_ids: &[Uuid],
+        digital_offer_ids: &[Uuid],
+        user_id: &String,
+    ) -> Result<Vec<Self>, DbError> {
+        if payment_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = Query::update()
+            .table(PaymentIden::Table)
+            .values([(PaymentIden::UserId, user_id.into())])
+            .and_where(
+                Expr::col(PaymentIden::PaymentId)
+                    .is_in(payment_ids.iter().copied()),
+            )
+            .and_where(Expr::col(PaymentIden::UserId).is_null())
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = transaction.query(sql.as_str(), &values.as_params()).await?;
+        let payments = rows.iter().map(Self::from).collect::<Vec<_>>();
+
+        Entitlement::create_for_payments(
+            &transaction,
+            user_id,
+            &payments
+                .iter()
+                .filter(|p| {
+                    matches!(
+                        p.payment_status.as_str(),
+                        "paid" | "no_payment_required"
+                    ) && digital_offer_ids.contains(&p.offer_id)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .await?;
+
+        LicenseKey::claim(
+            &transaction,
+            &payments.iter().map(|p| p.payment_id).collect::<Vec<_>>(),
+            user_id,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(payments)
+    }
+
+    /// Matches payments of the user and payments made with the email address.
+    fn buyer_condition(
+        user_id: Option<&String>,
+        buyer_email: Option<&str>,
+    ) -> Cond {
+        Cond::any()
+            .add_option(
+                user_id.map(|u| Expr::col(PaymentIden::UserId).eq(u.as_str())),
+            )
+            .add_option(buyer_email.map(|e| {
+                Expr::col(PaymentIden::BuyerEmail).eq(e.to_lowercase())
+            }))
+    }
+
+    /// Returns the free purchase of an offer the buyer made before.
+    pub async fn get_free(
+        pool: &Pool,
+        offer_id: &Uuid,
+        user_id: Option<&String>,
+        buyer_email: Option<&str>,
+    ) -> Result<Option<Self>, DbError> {
+        if user_id.is_none() && buyer_email.is_none() {
+            return Ok(None);
+        }
+
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(PaymentIden::Table)
+            .cond_where(
+                Cond::all()
+                    .add(Expr::col(PaymentIden::OfferId).eq(*offer_id))
+                    .add(
+                        Expr::col(PaymentIden::StripeCheckoutSessionId)
+                            .is_null(),
+                    )
+                    .add(
+                        Expr::col(PaymentIden::PaymentStatus)
+                            .eq("no_payment_required"),
+                    )
+                    .add(Self::buyer_condition(user_id, buyer_email)),
+            )
+            .order_by(PaymentIden::CreatedAt, Order::Asc)
+            .limit(1)
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+
+    /// Returns the quantity of an offer the buyer purchased before, counting
+    /// payments of the user and payments made with the email address.
+    /// Refunded and canceled payments are not counted. Payments without a
+    /// known quantity count as one.
+    pub async fn sum_purchased_quantity(
+        pool: &Pool,
+        offer_id: &Uuid,
+        user_id: Option<&String>,
+        buyer_email: Option<&str>,
+    ) -> Result<i64, DbError> {
+        if user_id.is_none() && buyer_email.is_none() {
+            return Ok(0);
+        }
+
+        let client = pool.get().await?;
+
+        let (sql, values) = {
+            let buyer_condition = Self::buyer_condition(user_id, buyer_email);
+
+            Query::select()
+                .expr(Func::cast_as(
+                    Func::coalesce([
+                        Func::sum(Func::coalesce([
+                            Expr::col(PaymentIden::Quantity).into(),
+                            Expr::val(1).into(),
+                        ]))
+                        .into(),
+                        Expr::val(0).into(),
+                    ]),
+                    Alias::new("INT8"),
+                ))
+                .from(PaymentIden::Table)
+                .cond_where(
+                    Cond::all()
+                        .add(Expr::col(PaymentIden::OfferId).eq(*offer_id))
+                        .add(Expr::col(PaymentIden::PaymentStatus).is_in([
+                            "paid",
+                            "no_payment_required",
+                            "authorized",
+                            "pending",
+                        ]))
+                        .add(buyer_condition),
+                )
+                .build_postgres(PostgresQueryBuilder)
+        };
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(row.get(0))
+    }
+
+    /// Updates the trial end of the payments of a subscription.
+    pub async fn update_trial_end(
+        pool: &Pool,
+        stripe_subscription_id: &String,
+        trial_ends_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::update()
+            .table(PaymentIden::Table)
+            .values([(PaymentIden::TrialEndsAt, trial_ends_at.into())])
+            .and_where(
+                Expr::col(PaymentIden::StripeSubscriptionId)
+                    .eq(stripe_subscription_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+}
+
+impl From<&Row> for Payment {
+    fn from(row: &Row) -> Self {
+        Self {
+            payment_id: row.get(PaymentIden::PaymentId.to_string().as_str()),
+            shop_id: row.get(PaymentIden::ShopId.to_string().as_str()),
+            offer_id: row.get(PaymentIden::OfferId.to_string().as_str()),
+            user_id: row.get(PaymentIden::UserId.to_string().as_str()),
+            stripe_checkout_session_id: row
+                .get(PaymentIden::StripeCheckoutSessionId.to_string().as_str()),
+            stripe_payment_intent_id: row
+                .get(PaymentIden::StripePaymentIntentId.to_string().as_str()),
+            stripe_subscription_id: row
+                .get(PaymentIden::StripeSubscriptionId.to_string().as_str()),
+            stripe_customer_id: row
+                .get(PaymentIden::StripeCustomerId.to_string().as_str()),
+            payment_status: row
+                .get(PaymentIden::PaymentStatus.to_string().as_str()),
+            currency: row.get(PaymentIden::Currency.to_string().as_str()),
+            amount_subtotal: row
+                .get(PaymentIden::AmountSubtotal.to_string().as_str()),
+            amount_discount: row
+                .get(PaymentIden::AmountDiscount.to_string().as_str()),
+            amount_shipping: row
+                .get(PaymentIden::AmountShipping.to_string().as_str()),
+            amount_tax: row.get(PaymentIden::AmountTax.to_string().as_str()),
+            amount_total: row
+                .get(PaymentIden::AmountTotal.to_string().as_str()),
+            authorization_expires_at: row
+                .get(PaymentIden::AuthorizationExpiresAt.to_string().as_str()),
+            trial_ends_at: row
+                .get(PaymentIden::TrialEndsAt.to_string().as_str()),
+            buyer_email: row.get(PaymentIden::BuyerEmail.to_string().as_str()),
+            quantity: row.get(PaymentIden::Quantity.to_string().as_str()),
+            created_at: row.get(PaymentIden::CreatedAt.to_string().as_str()),
+            updated_at: row.get(PaymentIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for Payment {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::Pool;
+use sea_query::{Alias, Cond, Expr, Func, Iden, PostgresQueryBuilder, Query};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+use super::payment::PaymentIden;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "purchase_reservations")]
+pub enum PurchaseReservationIden {
+    Table,
+    StripeCheckoutSessionId,
+    OfferId,
+    UserId,
+    BuyerEmail,
+    Quantity,
+    ExpiresAt,
+}
+
+/// Quantity of an offer with a purchase limit per buyer, which the buyer
+/// can still purchase with an open checkout session.
+pub struct PurchaseReservation;
+
+impl PurchaseReservation {
+    /// Records the reservation of a checkout session.
+    pub async fn create(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+        offer_id: &Uuid,
+        user_id: Option<&String>,
+        buyer_email: Option<&str>,
+        quantity: i64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(PurchaseReservationIden::Table)
+            .columns([
+                PurchaseReservationIden::StripeCheckoutSessionId,
+                PurchaseReservationIden::OfferId,
+                PurchaseReservationIden::UserId,
+                PurchaseReservationIden::BuyerEmail,
+                PurchaseReservationIden::Quantity,
+                PurchaseReservationIden::ExpiresAt,
+            ])
+            .values([
+                stripe_checkout_session_id.into(),
+                (*offer_id).into(),
+                user_id.cloned().into(),
+                buyer_email.map(str::to_lowercase).into(),
+                quantity.into(),
+                expires_at.into(),
+            ])?
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Deletes the reservation of an expired checkout session.
+    pub async fn delete(
+        pool: &Pool,
+        stripe_checkout_session_id: &String,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::delete()
+            .from_table(PurchaseReservationIden::Table)
+            .and_where(
+                Expr::col(PurchaseReservationIden::StripeCheckoutSessionId)
+                    .eq(stripe_checkout_session_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+
+    /// Returns the quantity of an offer reserved for the buyer by checkout
+    /// sessions which neither expired nor completed.
+    pub async fn sum_reserved_quantity(
+        pool: &Pool,
+        offer_id: &Uuid,
+        user_id: Option<&String>,
+        buyer_email: Option<&str>,
+    ) -> Result<i64, DbError> {
+        if user_id.is_none() && buyer_email.is_none() {
+            return Ok(0);
+        }
+
+        let client = pool.get().await?;
+
+        let (sql, values) = {
+            let buyer_condition = Cond::any()
+                .add_option(user_id.map(|u| {
+                    Expr::col(PurchaseReservationIden::UserId).eq(u.as_str())
+                }))
+                .add_option(buyer_email.map(|e| {
+                    Expr::col(PurchaseReservationIden::BuyerEmail)
+                        .eq(e.to_lowercase())
+                }));
+
+            Query::select()
+                .expr(Func::cast_as(
+                    Func::coalesce([
+                        Func::sum(Expr::col(PurchaseReservationIden::Quantity))
+                            .into(),
+                        Expr::val(0).into(),
+                    ]),
+                    Alias::new("INT8"),
+                ))
+                .from(PurchaseReservationIden::Table)
+                .cond_where(
+                    Cond::all()
+                        .add(
+                            Expr::col(PurchaseReservationIden::OfferId)
+                                .eq(*offer_id),
+                        )
+                        .add(
+                            Expr::col(PurchaseReservationIden::ExpiresAt)
+                                .gt(Expr::current_timestamp()),
+                        )
+                        .add(
+                            Expr::col(
+                                PurchaseReservationIden::StripeCheckoutSessionId,
+                            )
+                            .not_in_subquery(
+                                Query::select()
+                                    .column(
+                                        PaymentIden::StripeCheckoutSessionId,
+                                    )
+                                    .from(PaymentIden::Table)
+                                    .and_where(
+                                        Expr::col(
+                                            PaymentIden::StripeCheckoutSessionId,
+                                        )
+                                        .is_not_null(),
+                                    )
+                                    .and_where(
+                                        Expr::col(PaymentIden::OfferId)
+                                            .eq(*offer_id),
+                                    )
+                                    .to_owned(),
+                            ),
+                        )
+                        .add(buyer_condition),
+                )
+                .build_postgres(PostgresQueryBuilder)
+        };
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(row.get(0))
+    }
+}
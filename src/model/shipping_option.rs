@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{
+    Asterisk, Expr, Func, Iden, Order, PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+use super::ShippingZoneRate;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "shipping_options")]
+pub enum ShippingOptionIden {
+    Table,
+    ShippingOptionId,
+    ShopId,
+    DisplayName,
+    Currency,
+    Amount,
+    DomesticCountry,
+    InternationalAmount,
+    MinDeliveryDays,
+    MaxDeliveryDays,
+    FreeShippingThreshold,
+    CreatedAt,
+    UpdatedAt,
+}
+
+pub struct ShippingOption {
+    pub shipping_option_id: Uuid,
+    pub shop_id: Uuid,
+    pub display_name: String,
+    pub currency: String,
+    pub amount: i64,
+    pub domestic_country: Option<String>,
+    pub international_amount: Option<i64>,
+    pub min_delivery_days: Option<i64>,
+    pub max_delivery_days: Option<i64>,
+    pub free_shipping_threshold: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShippingOption {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &Pool,
+        shop_id: &Uuid,
+        display_name: &String,
+        currency: &String,
+        amount: i64,
+        domestic_country: Option<String>,
+        international_amount: Option<i64>,
+        min_delivery_days: Option<i64>,
+        max_delivery_days: Option<i64>,
+        free_shipping_threshold: Option<i64>,
+        zone_rates: &[(Uuid, i64)],
+    ) -> Result<(Self, Vec<ShippingZoneRate>), DbError> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(ShippingOptionIden::Table)
+            .columns([
+                ShippingOptionIden::ShippingOptionId,
+                ShippingOptionIden::ShopId,
+                ShippingOptionIden::DisplayName,
+                ShippingOptionIden::Currency,
+                ShippingOptionIden::Amount,
+                ShippingOptionIden::DomesticCountry,
+                ShippingOptionIden::InternationalAmount,
+                ShippingOptionIden::MinDeliveryDays,
+                ShippingOptionIden::MaxDeliveryDays,
+                ShippingOptionIden::FreeShippingThreshold,
+            ])
+            .values([
+                Uuid::new_v4().into(),
+                (*shop_id).into(),
+                display_name.into(),
+                currency.into(),
+                amount.into(),
+                domestic_country.into(),
+                international_amount.into(),
+                min_delivery_days.into(),
+                max_delivery_days.into(),
+                free_shipping_threshold.into(),
+            ])?
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = transaction
+            .query_one(sql.as_str(), &values.as_params())
+            .await?;
+        let shipping_option = Self::from(row);
+
+        let created_zone_rates = ShippingZoneRate::put_for_shipping_option(
+            &transaction,
+            &shipping_option.shipping_option_id,
+            shop_id,
+            zone_rates,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok((shipping_option, created_zone_rates))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &Pool,
+        shipping_option_id: &Uuid,
+        shop_id: &Uuid,
+        display_name: &String,
+        currency: &String,
+        amount: i64,
+        domestic_country: Option<String>,
+        international_amount: Option<i64>,
+        min_delivery_days: Option<i64>,
+        max_delivery_days: Option<i64>,
+        free_shipping_threshold: Option<i64>,
+        zone_rates: &[(Uuid, i64)],
+    ) -> Result<Option<(Self, Vec<ShippingZoneRate>)>, DbError> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = Query::update()
+            .table(ShippingOptionIden::Table)
+            .values([
+                (ShippingOptionIden::DisplayName, display_name.into()),
+                (ShippingOptionIden::Currency, currency.into()),
+                (ShippingOptionIden::Amount, amount.into()),
+                (ShippingOptionIden::DomesticCountry, domestic_country.into()),
+                (
+                    ShippingOptionIden::InternationalAmount,
+                    international_amount.into(),
+                ),
+                (
+                    ShippingOptionIden::MinDeliveryDays,
+                    min_delivery_days.into(),
+                ),
+                (
+                    ShippingOptionIden::MaxDeliveryDays,
+                    max_delivery_days.into(),
+                ),
+                (
+                    ShippingOptionIden::FreeShippingThreshold,
+                    free_shipping_threshold.into(),
+                ),
+            ])
+            .and_where(
+                Expr::col(ShippingOptionIden::ShippingOptionId)
+                    .eq(*shipping_option_id),
+            )
+            .and_where(Expr::col(ShippingOptionIden::ShopId).eq(*shop_id))
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let Some(row) = transaction
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+        else {
+            return Ok(None);
+        };
+        let shipping_option = Self::from(row);
+
+        let updated_zone_rates = ShippingZoneRate::put_for_shipping_option(
+            &transaction,
+            shipping_option_id,
+            shop_id,
+            zone_rates,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(Some((shipping_option, updated_zone_rates)))
+    }
+
+    pub async fn list(
+        pool: &Pool,
+        shop_id: &Uuid,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(ShippingOptionIden::Table)
+            .and_where(Expr::col(ShippingOptionIden::ShopId).eq(*shop_id))
+            .order_by(ShippingOptionIden::Amount, Order::Asc)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+
+    pub async fn count(pool: &Pool, shop_id: &Uuid) -> Result<i64, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(ShippingOptionIden::ShippingOptionId)))
+            .from(ShippingOptionIden::Table)
+            .and_where(Expr::col(ShippingOptionIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(row.get(0))
+    }
+
+    pub async fn delete(
+        pool: &Pool,
+        shipping_option_id: &Uuid,
+        shop_id: &Uuid,
+    ) -> Result<bool, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::delete()
+            .from_table(ShippingOptionIden::Table)
+            .and_where(
+                Expr::col(ShippingOptionIden::ShippingOptionId)
+                    .eq(*shipping_option_id),
+            )
+            .and_where(Expr::col(ShippingOptionIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        let deleted = client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(deleted > 0)
+    }
+}
+
+impl From<&Row> for ShippingOption {
+    fn from(row: &Row) -> Self {
+        Self {
+            shipping_option_id: row
+                .get(ShippingOptionIden::ShippingOptionId.to_string().as_str()),
+            shop_id: row.get(ShippingOptionIden::ShopId.to_string().as_str()),
+            display_name: row
+                .get(ShippingOptionIden::DisplayName.to_string().as_str()),
+            currency: row
+                .get(ShippingOptionIden::Currency.to_string().as_str()),
+            amount: row.get(ShippingOptionIden::Amount.to_string().as_str()),
+            domestic_country: row
+                .get(ShippingOptionIden::DomesticCountry.to_string().as_str()),
+            international_amount: row.get(
+                ShippingOptionIden::InternationalAmount.to_string().as_str(),
+            ),
+            min_delivery_days: row
+                .get(ShippingOptionIden::MinDeliveryDays.to_string().as_str()),
+            max_delivery_days: row
+                .get(ShippingOptionIden::MaxDeliveryDays.to_string().as_str()),
+            free_shipping_threshold: row.get(
+                ShippingOptionIden::FreeShippingThreshold
+                    .to_string()
+                    .as_str(),
+            ),
+            created_at: row
+                .get(ShippingOptionIden::CreatedAt.to_string().as_str()),
+            updated_at: row
+                .get(ShippingOptionIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for ShippingOption {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
@@ -0,0 +1,380 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::{Pool, Transaction};
+use sea_query::{Asterisk, Expr, Iden, Order, PostgresQueryBuilder, Query};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "shipping_zones")]
+pub enum ShippingZoneIden {
+    Table,
+    ShippingZoneId,
+    ShopId,
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "shipping_zone_countries")]
+pub enum ShippingZoneCountryIden {
+    Table,
+    ShopId,
+    Country,
+    ShippingZoneId,
+}
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "shipping_zone_rates")]
+pub enum ShippingZoneRateIden {
+    Table,
+    ShippingOptionId,
+    ShippingZoneId,
+    ShopId,
+    Amount,
+}
+
+pub struct ShippingZone {
+    pub shipping_zone_id: Uuid,
+    pub shop_id: Uuid,
+    pub name: String,
+    pub countries: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub struct ShippingZoneRate {
+    pub shipping_option_id: Uuid,
+    pub shipping_zone_id: Uuid,
+    pub amount: i64,
+}
+
+impl ShippingZone {
+    pub async fn create(
+        pool: &Pool,
+        shop_id: &Uuid,
+        name: &String,
+        countries: &[String],
+    ) -> Result<Self, DbError> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(ShippingZoneIden::Table)
+            .columns([
+                ShippingZoneIden::ShippingZoneId,
+                ShippingZoneIden::ShopId,
+                ShippingZoneIden::Name,
+            ])
+            .values([Uuid::new_v4().into(), (*shop_id).into(), name.into()])?
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = transaction
+            .query_one(sql.as_str(), &values.as_params())
+            .await?;
+        let mut shipping_zone = Self::from(row);
+
+        Self::insert_countries(
+            &transaction,
+            shop_id,
+            &shipping_zone.shipping_zone_id,
+            countries,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        shipping_zone.countries = countries.to_vec();
+
+        Ok(shipping_zone)
+    }
+
+    pub async fn update(
+        pool: &Pool,
+        shipping_zone_id: &Uuid,
+        shop_id: &Uuid,
+        name: &String,
+        countries: &[String],
+    ) -> Result<Option<Self>, DbError> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let (sql, values) = Query::update()
+            .table(ShippingZoneIden::Table)
+            .values([(ShippingZoneIden::Name, name.into())])
+            .and_where(
+                Expr::col(ShippingZoneIden::ShippingZoneId)
+                    .eq(*shipping_zone_id),
+            )
+            .and_where(Expr::col(ShippingZoneIden::ShopId).eq(*shop_id))
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let Some(row) = transaction
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+        else {
+            return Ok(None);
+        };
+        let mut shipping_zone = Self::from(row);
+
+        let (sql, values) = Query::delete()
+            .from_table(ShippingZoneCountryIden::Table)
+            .and_where(
+                Expr::col(ShippingZoneCountryIden::ShippingZoneId)
+                    .eq(*shipping_zone_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        transaction
+            .execute(sql.as_str(), &values.as_params())
+            .await?;
+
+        Self::insert_countries(
+            &transaction,
+            shop_id,
+            shipping_zone_id,
+            countries,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        shipping_zone.countries = countries.to_vec();
+
+        Ok(Some(shipping_zone))
+    }
+
+    pub async fn list(
+        pool: &Pool,
+        shop_id: &Uuid,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(ShippingZoneIden::Table)
+            .and_where(Expr::col(ShippingZoneIden::ShopId).eq(*shop_id))
+            .order_by(ShippingZoneIden::CreatedAt, Order::Asc)
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+        let mut shipping_zones: Vec<Self> =
+            rows.iter().map(Self::from).collect();
+
+        let (sql, values) = Query::select()
+            .columns([
+                ShippingZoneCountryIden::ShippingZoneId,
+                ShippingZoneCountryIden::Country,
+            ])
+            .from(ShippingZoneCountryIden::Table)
+            .and_where(Expr::col(ShippingZoneCountryIden::ShopId).eq(*shop_id))
+            .order_by(ShippingZoneCountryIden::Country, Order::Asc)
+            .build_postgres(PostgresQueryBuilder);
+
+        for row in client.query(sql.as_str(), &values.as_params()).await? {
+            let shipping_zone_id: Uuid = row.get(
+                ShippingZoneCountryIden::ShippingZoneId.to_string().as_str(),
+            );
+            if let Some(shipping_zone) = shipping_zones
+                .iter_mut()
+                .find(|z| z.shipping_zone_id == shipping_zone_id)
+            {
+                shipping_zone.countries.push(row.get(
+                    ShippingZoneCountryIden::Country.to_string().as_str(),
+                ));
+            }
+        }
+
+        Ok(shipping_zones)
+    }
+
+    pub async fn delete(
+        pool: &Pool,
+        shipping_zone_id: &Uuid,
+        shop_id: &Uuid,
+    ) -> Result<bool, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::delete()
+            .from_table(ShippingZoneIden::Table)
+            .and_where(
+                Expr::col(ShippingZoneIden::ShippingZoneId)
+                    .eq(*shipping_zone_id),
+            )
+            .and_where(Expr::col(ShippingZoneIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        let deleted = client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(deleted > 0)
+    }
+
+    /// Returns the id of the shipping zone of the shop containing `country`.
+    pub async fn get_id_for_country(
+        pool: &Pool,
+        shop_id: &Uuid,
+        country: &str,
+    ) -> Result<Option<Uuid>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(ShippingZoneCountryIden::ShippingZoneId)
+            .from(ShippingZoneCountryIden::Table)
+            .and_where(Expr::col(ShippingZoneCountryIden::ShopId).eq(*shop_id))
+            .and_where(
+                Expr::col(ShippingZoneCountryIden::Country)
+                    .eq(country.to_uppercase()),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(|row| row.get(0)))
+    }
+
+    async fn insert_countries(
+        transaction: &Transaction<'_>,
+        shop_id: &Uuid,
+        shipping_zone_id: &Uuid,
+        countries: &[String],
+    ) -> Result<(), DbError> {
+        if countries.is_empty() {
+            return Ok(());
+        }
+
+        let (sql, values) = {
+            let mut query = Query::insert();
+            query.into_table(ShippingZoneCountryIden::Table).columns([
+                ShippingZoneCountryIden::ShopId,
+                ShippingZoneCountryIden::Country,
+                ShippingZoneCountryIden::ShippingZoneId,
+            ]);
+            for country in countries {
+                query.values([
+                    (*shop_id).into(),
+                    country.into(),
+                    (*shipping_zone_id).into(),
+                ])?;
+            }
+            query.build_postgres(PostgresQueryBuilder)
+        };
+
+        transaction
+            .execute(sql.as_str(), &values.as_params())
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl From<&Row> for ShippingZone {
+    fn from(row: &Row) -> Self {
+        Self {
+            shipping_zone_id: row
+                .get(ShippingZoneIden::ShippingZoneId.to_string().as_str()),
+            shop_id: row.get(ShippingZoneIden::ShopId.to_string().as_str()),
+            name: row.get(ShippingZoneIden::Name.to_string().as_str()),
+            countries: Vec::new(),
+            created_at: row
+                .get(ShippingZoneIden::CreatedAt.to_string().as_str()),
+            updated_at: row
+                .get(ShippingZoneIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for ShippingZone {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
+
+impl ShippingZoneRate {
+    /// Replaces the zone rates of a shipping option.
+    pub async fn put_for_shipping_option(
+        transaction: &Transaction<'_>,
+        shipping_option_id: &Uuid,
+        shop_id: &Uuid,
+        zone_rates: &[(Uuid, i64)],
+    ) -> Result<Vec<Self>, DbError> {
+        let (sql, values) = Query::delete()
+            .from_table(ShippingZoneRateIden::Table)
+            .and_where(
+                Expr::col(ShippingZoneRateIden::ShippingOptionId)
+                    .eq(*shipping_option_id),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        transaction
+            .execute(sql.as_str(), &values.as_params())
+            .await?;
+
+        let mut created_zone_rates = Vec::new();
+
+        if !zone_rates.is_empty() {
+            let (sql, values) = {
+                let mut query = Query::insert();
+                query.into_table(ShippingZoneRateIden::Table).columns([
+                    ShippingZoneRateIden::ShippingOptionId,
+                    ShippingZoneRateIden::ShippingZoneId,
+                    ShippingZoneRateIden::ShopId,
+                    ShippingZoneRateIden::Amount,
+                ]);
+                for (shipping_zone_id, amount) in zone_rates {
+                    query.values([
+                        (*shipping_option_id).into(),
+                        (*shipping_zone_id).into(),
+                        (*shop_id).into(),
+                        (*amount).into(),
+                    ])?;
+                }
+                query.returning_all().build_postgres(PostgresQueryBuilder)
+            };
+
+            created_zone_rates = transaction
+                .query(sql.as_str(), &values.as_params())
+                .await?
+                .iter()
+                .map(Self::from)
+                .collect();
+        }
+
+        Ok(created_zone_rates)
+    }
+
+    pub async fn list(
+        pool: &Pool,
+        shop_id: &Uuid,
+    ) -> Result<Vec<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(ShippingZoneRateIden::Table)
+            .and_where(Expr::col(ShippingZoneRateIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        let rows = client.query(sql.as_str(), &values.as_params()).await?;
+
+        Ok(rows.iter().map(Self::from).collect())
+    }
+}
+
+impl From<&Row> for ShippingZoneRate {
+    fn from(row: &Row) -> Self {
+        Self {
+            shipping_option_id: row.get(
+                ShippingZoneRateIden::ShippingOptionId.to_string().as_str(),
+            ),
+            shipping_zone_id: row
+                .get(ShippingZoneRateIden::ShippingZoneId.to_string().as_str()),
+            amount: row.get(ShippingZoneRateIden::Amount.to_string().as_str()),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::Pool;
+use sea_query::{
+    Asterisk, Expr, Iden, OnConflict, PostgresQueryBuilder, Query,
+};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "shop_tax_settings")]
+pub enum ShopTaxSettingsIden {
+    Table,
+    ShopId,
+    AutomaticTaxEnabled,
+    TaxBehavior,
+    PhysicalTaxCode,
+    DigitalTaxCode,
+    ServiceTaxCode,
+    TaxIdCollectionEnabled,
+    CreatedAt,
+    UpdatedAt,
+}
+
+pub struct ShopTaxSettings {
+    pub shop_id: Uuid,
+    pub automatic_tax_enabled: bool,
+    pub tax_behavior: String,
+    pub physical_tax_code: Option<String>,
+    pub digital_tax_code: Option<String>,
+    pub service_tax_code: Option<String>,
+    pub tax_id_collection_enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShopTaxSettings {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn put(
+        pool: &Pool,
+        shop_id: &Uuid,
+        automatic_tax_enabled: bool,
+        tax_behavior: &str,
+        physical_tax_code: Option<String>,
+        digital_tax_code: Option<String>,
+        service_tax_code: Option<String>,
+        tax_id_collection_enabled: bool,
+    ) -> Result<Self, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(ShopTaxSettingsIden::Table)
+            .columns([
+                ShopTaxSettingsIden::ShopId,
+                ShopTaxSettingsIden::AutomaticTaxEnabled,
+                ShopTaxSettingsIden::TaxBehavior,
+                ShopTaxSettingsIden::PhysicalTaxCode,
+                ShopTaxSettingsIden::DigitalTaxCode,
+                ShopTaxSettingsIden::ServiceTaxCode,
+                ShopTaxSettingsIden::TaxIdCollectionEnabled,
+            ])
+            .values([
+                (*shop_id).into(),
+                automatic_tax_enabled.into(),
+                tax_behavior.into(),
+                physical_tax_code.into(),
+                digital_tax_code.into(),
+                service_tax_code.into(),
+                tax_id_collection_enabled.into(),
+            ])?
+            .on_conflict(
+                OnConflict::column(ShopTaxSettingsIden::ShopId)
+                    .update_columns([
+                        ShopTaxSettingsIden::AutomaticTaxEnabled,
+                        ShopTaxSettingsIden::TaxBehavior,
+                        ShopTaxSettingsIden::PhysicalTaxCode,
+                        ShopTaxSettingsIden::DigitalTaxCode,
+                        ShopTaxSettingsIden::ServiceTaxCode,
+                        ShopTaxSettingsIden::TaxIdCollectionEnabled,
+                    ])
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_postgres(PostgresQueryBuilder);
+
+        let row = client.query_one(sql.as_str(), &values.as_params()).await?;
+
+        Ok(Self::from(row))
+    }
+
+    pub async fn get(
+        pool: &Pool,
+        shop_id: &Uuid,
+    ) -> Result<Option<Self>, DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(ShopTaxSettingsIden::Table)
+            .and_where(Expr::col(ShopTaxSettingsIden::ShopId).eq(*shop_id))
+            .build_postgres(PostgresQueryBuilder);
+
+        Ok(client
+            .query_opt(sql.as_str(), &values.as_params())
+            .await?
+            .map(Self::from))
+    }
+}
+
+impl From<&Row> for ShopTaxSettings {
+    fn from(row: &Row) -> Self {
+        Self {
+            shop_id: row.get(ShopTaxSettingsIden::ShopId.to_string().as_str()),
+            automatic_tax_enabled: row.get(
+                ShopTaxSettingsIden::AutomaticTaxEnabled
+                    .to_string()
+                    .as_str(),
+            ),
+            tax_behavior: row
+                .get(ShopTaxSettingsIden::TaxBehavior.to_string().as_str()),
+            physical_tax_code: row
+                .get(ShopTaxSettingsIden::PhysicalTaxCode.to_string().as_str()),
+            digital_tax_code: row
+                .get(ShopTaxSettingsIden::DigitalTaxCode.to_string().as_str()),
+            service_tax_code: row
+                .get(ShopTaxSettingsIden::ServiceTaxCode.to_string().as_str()),
+            tax_id_collection_enabled: row.get(
+                ShopTaxSettingsIden::TaxIdCollectionEnabled
+                    .to_string()
+                    .as_str(),
+            ),
+            created_at: row
+                .get(ShopTaxSettingsIden::CreatedAt.to_string().as_str()),
+            updated_at: row
+                .get(ShopTaxSettingsIden::UpdatedAt.to_string().as_str()),
+        }
+    }
+}
+
+impl From<Row> for ShopTaxSettings {
+    fn from(row: Row) -> Self {
+        Self::from(&row)
+    }
+}
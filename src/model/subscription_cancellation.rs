@@ -0,0 +1,72 @@
+use deadpool_postgres::Pool;
+use sea_query::{Iden, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_postgres::PostgresBinder;
+use uuid::Uuid;
+
+use crate::db::DbError;
+
+#[derive(Debug, Clone, Iden)]
+#[iden(rename = "subscription_cancellations")]
+pub enum SubscriptionCancellationIden {
+    Table,
+    SubscriptionCancellationId,
+    ShopId,
+    StripeSubscriptionId,
+    CancellationMode,
+    RefundPolicy,
+    AmountRefunded,
+    Reason,
+}
+
+/// Record of a subscription canceled by a seller.
+pub struct SubscriptionCancellation;
+
+impl SubscriptionCancellation {
+    /// Records the cancellation of a subscription once per cancellation
+    /// mode, retried cancellations keep the first record.
+    pub async fn create(
+        pool: &Pool,
+        shop_id: &Uuid,
+        stripe_subscription_id: &String,
+        cancellation_mode: &str,
+        refund_policy: &str,
+        amount_refunded: i64,
+        reason: Option<String>,
+    ) -> Result<(), DbError> {
+        let client = pool.get().await?;
+
+        let (sql, values) = Query::insert()
+            .into_table(SubscriptionCancellationIden::Table)
+            .columns([
+                SubscriptionCancellationIden::SubscriptionCancellationId,
+                SubscriptionCancellationIden::ShopId,
+                SubscriptionCancellationIden::StripeSubscriptionId,
+                SubscriptionCancellationIden::CancellationMode,
+                SubscriptionCancellationIden::RefundPolicy,
+                SubscriptionCancellationIden::AmountRefunded,
+                SubscriptionCancellationIden::Reason,
+            ])
+            .values([
+                Uuid::new_v4().into(),
+                (*shop_id).into(),
+                stripe_subscription_id.into(),
+                cancellation_mode.into(),
+                refund_policy.into(),
+                amount_refunded.into(),
+                reason.into(),
+            ])?
+            .on_conflict(
+                OnConflict::columns([
+                    SubscriptionCancellationIden::StripeSubscriptionId,
+                    SubscriptionCancellationIden::CancellationMode,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .build_postgres(PostgresQueryBuilder);
+
+        client.execute(sql.as_str(), &values.as_params()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use deadpool_postgres::Pool;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::api::sited_io::payment::v1::abandoned_checkout_service_server::{
+    self, AbandonedCheckoutServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    AbandonedCheckoutResponse, ListAbandonedCheckoutsRequest,
+    ListAbandonedCheckoutsResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::AbandonedCheckout;
+use crate::{parse_uuid, CommerceService};
+
+pub struct AbandonedCheckoutService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    commerce_service: CommerceService,
+}
+
+impl AbandonedCheckoutService {
+    const DEFAULT_LIMIT: u64 = 100;
+    const NOTIFICATION_INTERVAL: Duration = Duration::from_secs(600);
+    const NOTIFICATION_BATCH_SIZE: u64 = 500;
+
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> AbandonedCheckoutServiceServer<Self> {
+        AbandonedCheckoutServiceServer::new(Self::new(
+            pool,
+            verifier,
+            commerce_service,
+        ))
+    }
+
+    fn to_response(
+        abandoned_checkout: AbandonedCheckout,
+    ) -> AbandonedCheckoutResponse {
+        AbandonedCheckoutResponse {
+            stripe_checkout_session_id: abandoned_checkout
+                .stripe_checkout_session_id,
+            shop_id: abandoned_checkout.shop_id.to_string(),
+            offer_id: abandoned_checkout.offer_id.to_string(),
+            buyer_email: abandoned_checkout.buyer_email,
+            recovery_url: abandoned_checkout.recovery_url,
+            recovery_expires_at: abandoned_checkout
+                .recovery_expires_at
+                .map(|r| r.timestamp()),
+            seller_notified_at: abandoned_checkout
+                .seller_notified_at
+                .map(|s| s.timestamp()),
+            recovered_at: abandoned_checkout
+                .recovered_at
+                .map(|r| r.timestamp()),
+            created_at: abandoned_checkout.created_at.timestamp(),
+        }
+    }
+
+    /// Groups the ids of abandoned checkout sessions by shop.
+    fn group_by_shop(
+        abandoned_checkouts: Vec<AbandonedCheckout>,
+    ) -> BTreeMap<Uuid, Vec<String>> {
+        let mut shops: BTreeMap<Uuid, Vec<String>> = BTreeMap::new();
+        for abandoned_checkout in abandoned_checkouts {
+            shops
+                .entry(abandoned_checkout.shop_id)
+                .or_default()
+                .push(abandoned_checkout.stripe_checkout_session_id);
+        }
+        shops
+    }
+
+    /// Notifies sellers about abandoned checkouts periodically. Notifications
+    /// are posted to the notification URL if configured, otherwise they are
+    /// only logged.
+    pub async fn run_seller_notifications(
+        pool: Pool,
+        notification_url: Option<String>,
+    ) {
+        let http_client = reqwest::Client::new();
+        let mut interval = tokio::time::interval(Self::NOTIFICATION_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = Self::notify_sellers(
+                &pool,
+                &http_client,
+                notification_url.as_ref(),
+            )
+            .await
+            {
+                tracing::log::error!("{err:?}");
+            }
+        }
+    }
+
+    async fn notify_sellers(
+        pool: &Pool,
+        http_client: &reqwest::Client,
+        notification_url: Option<&String>,
+    ) -> Result<(), Status> {
+        let abandoned_checkouts = AbandonedCheckout::list_unnotified(
+            pool,
+            Self::NOTIFICATION_BATCH_SIZE,
+        )
+        .await?;
+
+        for (shop_id, stripe_checkout_session_ids) in
+            Self::group_by_shop(abandoned_checkouts)
+        {
+            if let Some(notification_url) = notification_url {
+                if let Err(err) = http_client
+                    .post(notification_url)
+                    .form(&[
+                        ("event", String::from("abandoned_checkouts")),
+                        ("shop_id", shop_id.to_string()),
+                        (
+                            "count",
+                            stripe_checkout_session_ids.len().to_string(),
+                        ),
+                    ])
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                {
+                    tracing::log::error!(
+                        "Failed to notify seller of shop {shop_id}: {err}"
+                    );
+                    continue;
+                }
+            }
+
+            tracing::log::info!(
+                "Notified seller of shop {shop_id} about {} abandoned checkouts",
+                stripe_checkout_session_ids.len()
+            );
+
+            AbandonedCheckout::mark_notified(
+                pool,
+                &stripe_checkout_session_ids,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl abandoned_checkout_service_server::AbandonedCheckoutService
+    for AbandonedCheckoutService
+{
+    async fn list_abandoned_checkouts(
+        &self,
+        request: Request<ListAbandonedCheckoutsRequest>,
+    ) -> Result<Response<ListAbandonedCheckoutsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let ListAbandonedCheckoutsRequest {
+            shop_id,
+            limit,
+            offset,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let abandoned_checkouts = AbandonedCheckout::list(
+            &self.pool,
+            &shop_uuid,
+            limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .min(Self::DEFAULT_LIMIT),
+            offset.unwrap_or(0),
+        )
+        .await?;
+
+        Ok(Response::new(ListAbandonedCheckoutsResponse {
+            abandoned_checkouts: abandoned_checkouts
+                .into_iter()
+                .map(Self::to_response)
+                .collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn abandoned_checkout(
+        shop_id: Uuid,
+        stripe_checkout_session_id: &str,
+    ) -> AbandonedCheckout {
+        AbandonedCheckout {
+            stripe_checkout_session_id: stripe_checkout_session_id.to_owned(),
+            shop_id,
+            offer_id: Uuid::new_v4(),
+            buyer_email: String::from("buyer@example.com"),
+            recovery_url: String::from("https://checkout.stripe.com/r"),
+            recovery_expires_at: None,
+            seller_notified_at: None,
+            recovered_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_group_by_shop() {
+        let shop_a = Uuid::new_v4();
+        let shop_b = Uuid::new_v4();
+
+        let shops = AbandonedCheckoutService::group_by_shop(vec![
+            abandoned_checkout(shop_a, "cs_1"),
+            abandoned_checkout(shop_b, "cs_2"),
+            abandoned_checkout(shop_a, "cs_3"),
+        ]);
+
+        assert_eq!(shops.len(), 2);
+        assert_eq!(shops[&shop_a], vec!["cs_1", "cs_3"]);
+        assert_eq!(shops[&shop_b], vec!["cs_2"]);
+    }
+}
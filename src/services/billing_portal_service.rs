@@ -0,0 +1,521 @@
+use deadpool_postgres::Pool;
+use std::str::FromStr;
+
+use http::Uri;
+use jwtk::jwk::RemoteJwksVerifier;
+use serde::Serialize;
+use stripe::{
+    BillingPortalConfiguration, BillingPortalSession, Client,
+    CreateBillingPortalSession, CustomerId, IdOrCreate, ListPrices,
+    PortalSubscriptionCancelMode,
+    PortalSubscriptionUpdateDefaultAllowedUpdates,
+    PortalSubscriptionUpdateProrationBehavior, Price, PriceType,
+};
+use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::api::sited_io::payment::v1::billing_portal_service_server::{
+    self, BillingPortalServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    BillingPortalSettingsResponse, CreateBillingPortalSessionRequest,
+    CreateBillingPortalSessionResponse, GetBillingPortalSettingsRequest,
+    GetBillingPortalSettingsResponse, PutBillingPortalSettingsRequest,
+    PutBillingPortalSettingsResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::{BillingPortalSettings, Customer, Payment, StripeAccount};
+use crate::products::offer_product_id;
+use crate::{
+    connected_stripe_client, parse_id_error_to_status, parse_uuid,
+    stripe_error_to_status, CommerceService,
+};
+
+/// Parameters of Stripe customer portal configurations, which are not
+/// covered by async-stripe.
+#[derive(Serialize)]
+struct PortalConfigurationParams {
+    features: PortalFeaturesParams,
+}
+
+#[derive(Serialize)]
+struct PortalFeaturesParams {
+    invoice_history: PortalFeatureParams,
+    payment_method_update: PortalFeatureParams,
+    subscription_cancel: PortalSubscriptionCancelParams,
+    subscription_update: PortalSubscriptionUpdateParams,
+}
+
+#[derive(Serialize)]
+struct PortalFeatureParams {
+    enabled: bool,
+}
+
+#[derive(Serialize)]
+struct PortalSubscriptionCancelParams {
+    enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<PortalSubscriptionCancelMode>,
+}
+
+#[derive(Serialize)]
+struct PortalSubscriptionUpdateParams {
+    enabled: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    default_allowed_updates: Vec<PortalSubscriptionUpdateDefaultAllowedUpdates>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    products: Vec<PortalSubscriptionUpdateProductParams>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proration_behavior: Option<PortalSubscriptionUpdateProrationBehavior>,
+}
+
+#[derive(Serialize)]
+struct PortalSubscriptionUpdateProductParams {
+    product: String,
+    prices: Vec<String>,
+}
+
+pub struct BillingPortalService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    stripe_client: Client,
+    commerce_service: CommerceService,
+    platform_domain: String,
+}
+
+impl BillingPortalService {
+    const MAX_PORTAL_PRODUCTS: usize = 10;
+
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        stripe_client: Client,
+        commerce_service: CommerceService,
+        platform_domain: String,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            stripe_client,
+            commerce_service,
+            platform_domain,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        stripe_client: Client,
+        commerce_service: CommerceService,
+        platform_domain: String,
+    ) -> BillingPortalServiceServer<Self> {
+        BillingPortalServiceServer::new(Self::new(
+            pool,
+            verifier,
+            stripe_client,
+            commerce_service,
+            platform_domain,
+        ))
+    }
+
+    fn to_response(
+        billing_portal_settings: BillingPortalSettings,
+    ) -> BillingPortalSettingsResponse {
+        BillingPortalSettingsResponse {
+            shop_id: billing_portal_settings.shop_id.to_string(),
+            allow_cancel: billing_portal_settings.allow_cancel,
+            allow_plan_switch: billing_portal_settings.allow_plan_switch,
+            created_at: billing_portal_settings.created_at.timestamp(),
+            updated_at: billing_portal_settings.updated_at.timestamp(),
+        }
+    }
+
+    fn default_response(shop_id: String) -> BillingPortalSettingsResponse {
+        BillingPortalSettingsResponse {
+            shop_id,
+            ..Default::default()
+        }
+    }
+
+    /// Buyers must be sent back to the shop, which is either served on its
+    /// own domain or on the subdomain of its slug on the platform.
+    fn validate_return_url(
+        return_url: &str,
+        shop_domain: Option<&str>,
+        shop_slug: &str,
+        platform_domain: &str,
+    ) -> Result<(), Status> {
+        let uri = Uri::from_str(return_url).map_err(|_| {
+            Status::invalid_argument("field return_url is not a valid URL")
+        })?;
+
+        if uri.scheme_str() != Some("https") {
+            return Err(Status::invalid_argument(
+                "field return_url must use https",
+            ));
+        }
+
+        let host = uri.host().unwrap_or_default().to_lowercase();
+        let is_shop_domain =
+            shop_domain.is_some_and(|d| d.eq_ignore_ascii_case(&host));
+        let is_platform_domain =
+            host == format!("{shop_slug}.{platform_domain}").to_lowercase();
+
+        if is_shop_domain || is_platform_domain {
+            Ok(())
+        } else {
+            Err(Status::invalid_argument(
+                "field return_url must point to the shop",
+            ))
+        }
+    }
+
+    /// Lists the active recurring prices of the products of the offers a
+    /// buyer subscribed to, so subscribers can only switch between the plans
+    /// of their offers in the portal. Prices are ordered by creation.
+    ///
+    /// Stripe accepts at most 10 products per portal configuration.
+    async fn list_portal_products(
+        stripe_client: &Client,
+        offer_ids: &[Uuid],
+    ) -> Result<Vec<PortalSubscriptionUpdateProductParams>, Status> {
+        let mut products = Vec::new();
+        for offer_id in offer_ids {
+            let product_id = offer_product_id(&offer_id.to_string())?;
+
+            let mut list_prices = ListPrices::new();
+            list_prices.active = Some(true);
+            list_prices.product = Some(IdOrCreate::Id(&product_id));
+            list_prices.type_ = Some(PriceType::Recurring);
+            list_prices.limit = Some(100);
+
+            let mut prices = Price::list(stripe_client, &list_prices)
+                .await
+                .map_err(stripe_error_to_status)?
+                .data;
+            prices.sort_by(|a, b| {
+                (a.created, a.id.as_str()).cmp(&(b.created, b.id.as_str()))
+            });
+
+            if !prices.is_empty() {
+                products.push(PortalSubscriptionUpdateProductParams {
+                    product: product_id.to_string(),
+                    prices: prices.iter().map(|p| p.id.to_string()).collect(),
+                });
+            }
+        }
+
+        if products.len() > Self::MAX_PORTAL_PRODUCTS {
+            tracing::log::warn!(
+                "Only {} of {} products can be switched between in the portal",
+                Self::MAX_PORTAL_PRODUCTS,
+                products.len()
+            );
+            products.truncate(Self::MAX_PORTAL_PRODUCTS);
+        }
+
+        Ok(products)
+    }
+
+    /// Creates or updates a customer portal configuration on the connected
+    /// account of a shop and returns its id. Plan switches are enabled if
+    /// products to switch between are given.
+    async fn put_portal_configuration(
+        stripe_client: &Client,
+        stripe_configuration_id: Option<&str>,
+        allow_cancel: bool,
+        products: Vec<PortalSubscriptionUpdateProductParams>,
+    ) -> Result<String, Status> {
+        let subscription_update = if !products.is_empty() {
+            PortalSubscriptionUpdateParams {
+                enabled: true,
+                default_allowed_updates: vec![
+                    PortalSubscriptionUpdateDefaultAllowedUpdates::Price,
+                    PortalSubscriptionUpdateDefaultAllowedUpdates::Quantity,
+                ],
+                products,
+                proration_behavior: Some(
+                    PortalSubscriptionUpdateProrationBehavior::CreateProrations,
+                ),
+            }
+        } else {
+            PortalSubscriptionUpdateParams {
+                enabled: false,
+                default_allowed_updates: Vec::new(),
+                products: Vec::new(),
+                proration_behavior: None,
+            }
+        };
+
+        let params = PortalConfigurationParams {
+            features: PortalFeaturesParams {
+                invoice_history: PortalFeatureParams { enabled: true },
+                payment_method_update: PortalFeatureParams { enabled: true },
+                subscription_cancel: PortalSubscriptionCancelParams {
+                    enabled: allow_cancel,
+                    mode: allow_cancel
+                        .then_some(PortalSubscriptionCancelMode::AtPeriodEnd),
+                },
+                subscription_update,
+            },
+        };
+
+        let path = match stripe_configuration_id {
+            Some(id) => format!("/billing_portal/configurations/{id}"),
+            None => String::from("/billing_portal/configurations"),
+        };
+
+        Ok(stripe_client
+            .post_form::<BillingPortalConfiguration, _>(&path, params)
+            .await
+            .map_err(stripe_error_to_status)?
+            .id
+            .to_string())
+    }
+}
+
+#[async_trait]
+impl billing_portal_service_server::BillingPortalService
+    for BillingPortalService
+{
+    async fn put_billing_portal_settings(
+        &self,
+        request: Request<PutBillingPortalSettingsRequest>,
+    ) -> Result<Response<PutBillingPortalSettingsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let PutBillingPortalSettingsRequest {
+            shop_id,
+            allow_cancel,
+            allow_plan_switch,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let stripe_account =
+            StripeAccount::get_for_user(&self.pool, &shop_uuid, &user_id)
+                .await?
+                .ok_or_else(|| Status::not_found("stripe account"))?;
+
+        let stripe_client =
+            connected_stripe_client(&self.stripe_client, &stripe_account)?;
+
+        let found_settings =
+            BillingPortalSettings::get(&self.pool, &shop_uuid).await?;
+
+        // Plan switches depend on the offers of the subscriber, so they are
+        // configured per portal session
+        let stripe_configuration_id = Self::put_portal_configuration(
+            &stripe_client,
+            found_settings
+                .as_ref()
+                .map(|s| s.stripe_configuration_id.as_str()),
+            allow_cancel,
+            Vec::new(),
+        )
+        .await?;
+
+        let billing_portal_settings = BillingPortalSettings::put(
+            &self.pool,
+            &shop_uuid,
+            allow_cancel,
+            allow_plan_switch,
+            &stripe_configuration_id,
+        )
+        .await?;
+
+        Ok(Response::new(PutBillingPortalSettingsResponse {
+            billing_portal_settings: Some(Self::to_response(
+                billing_portal_settings,
+            )),
+        }))
+    }
+
+    async fn get_billing_portal_settings(
+        &self,
+        request: Request<GetBillingPortalSettingsRequest>,
+    ) -> Result<Response<GetBillingPortalSettingsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let GetBillingPortalSettingsRequest { shop_id } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let billing_portal_settings =
+            match BillingPortalSettings::get(&self.pool, &shop_uuid).await? {
+                Some(settings) => Self::to_response(settings),
+                None => Self::default_response(shop_id),
+            };
+
+        Ok(Response::new(GetBillingPortalSettingsResponse {
+            billing_portal_settings: Some(billing_portal_settings),
+        }))
+    }
+
+    async fn create_billing_portal_session(
+        &self,
+        request: Request<CreateBillingPortalSessionRequest>,
+    ) -> Result<Response<CreateBillingPortalSessionResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let CreateBillingPortalSessionRequest {
+            shop_id,
+            return_url,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let found_shop =
+            self.commerce_service.get_shop(&shop_id, &metadata).await?;
+
+        Self::validate_return_url(
+            &return_url,
+            found_shop.domain.as_deref(),
+            &found_shop.slug,
+            &self.platform_domain,
+        )?;
+
+        let stripe_account = StripeAccount::get(&self.pool, &shop_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("stripe account"))?;
+
+        // Customers created before buyers were stored per connected account
+        // are only known from their payments
+        let customer_id = match Customer::get(
+            &self.pool,
+            &user_id,
+            &stripe_account.stripe_account_id,
+        )
+        .await?
+        {
+            Some(customer) => customer.stripe_customer_id,
+            None => Payment::get_latest_stripe_customer_id(
+                &self.pool, &shop_uuid, &user_id,
+            )
+            .await?
+            .ok_or_else(|| Status::not_found("customer"))?,
+        };
+
+        let customer_id = CustomerId::from_str(&customer_id)
+            .map_err(parse_id_error_to_status)?;
+
+        let billing_portal_settings =
+            BillingPortalSettings::get(&self.pool, &shop_uuid).await?;
+
+        let stripe_client =
+            connected_stripe_client(&self.stripe_client, &stripe_account)?;
+
+        let session_configuration_id = match billing_portal_settings
+            .as_ref()
+            .filter(|s| s.allow_plan_switch)
+        {
+            Some(settings) => {
+                let offer_ids = Payment::list_subscribed_offer_ids(
+                    &self.pool, &shop_uuid, &user_id,
+                )
+                .await?;
+                let products =
+                    Self::list_portal_products(&stripe_client, &offer_ids)
+                        .await?;
+                if products.is_empty() {
+                    None
+                } else {
+                    Some(
+                        Self::put_portal_configuration(
+                            &stripe_client,
+                            None,
+                            settings.allow_cancel,
+                            products,
+                        )
+                        .await?,
+                    )
+                }
+            }
+            None => None,
+        };
+
+        let mut create_session = CreateBillingPortalSession::new(customer_id);
+        create_session.return_url = Some(&return_url);
+        create_session.configuration =
+            session_configuration_id
+                .as_deref()
+                .or(billing_portal_settings
+                    .as_ref()
+                    .map(|s| s.stripe_configuration_id.as_str()));
+
+        let link = BillingPortalSession::create(&stripe_client, create_session)
+            .await
+            .map_err(stripe_error_to_status)?
+            .url;
+
+        Ok(Response::new(CreateBillingPortalSessionResponse { link }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_return_url() {
+        assert!(BillingPortalService::validate_return_url(
+            "https://shop.sited.io/account",
+            None,
+            "shop",
+            "sited.io"
+        )
+        .is_ok());
+        assert!(BillingPortalService::validate_return_url(
+            "https://www.example.com/account",
+            Some("www.example.com"),
+            "shop",
+            "sited.io"
+        )
+        .is_ok());
+        assert!(BillingPortalService::validate_return_url(
+            "http://shop.sited.io/account",
+            None,
+            "shop",
+            "sited.io"
+        )
+        .is_err());
+        assert!(BillingPortalService::validate_return_url(
+            "https://other.sited.io/account",
+            None,
+            "shop",
+            "sited.io"
+        )
+        .is_err());
+        assert!(BillingPortalService::validate_return_url(
+            "https://evil-sited.io/account",
+            None,
+            "shop",
+            "sited.io"
+        )
+        .is_err());
+        assert!(BillingPortalService::validate_return_url(
+            "https://www.example.com/account",
+            None,
+            "shop",
+            "sited.io"
+        )
+        .is_err());
+        assert!(BillingPortalService::validate_return_url(
+            "not a url",
+            None,
+            "shop",
+            "sited.io"
+        )
+        .is_err());
+    }
+}
@@ -490,3 +490,14 @@ pub fn to_stripe_country(
         ShippingCountry::Zz => Zz,
     }
 }
+
+/// Parses an ISO 3166-1 alpha-2 country code like `DE` into a shipping
+/// country.
+pub fn parse_country_code(code: &str) -> Option<i32> {
+    ShippingCountry::from_str_name(&format!(
+        "SHIPPING_COUNTRY_{}",
+        code.to_uppercase()
+    ))
+    .filter(|c| *c != ShippingCountry::Unspecified)
+    .map(i32::from)
+}
@@ -0,0 +1,422 @@
+use deadpool_postgres::Pool;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use stripe::{
+    Client, Coupon, CouponDuration as StripeCouponDuration, CouponId,
+    CreateCoupon, CreateCouponAppliesTo, CreatePromotionCode,
+    Currency as StripeCurrency, ListCoupons, ListPromotionCodes, PromotionCode,
+    PromotionCodeId, UpdatePromotionCode,
+};
+use tonic::{async_trait, Request, Response, Status};
+
+use crate::api::sited_io::payment::v1::coupon_service_server::{
+    self, CouponServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    CouponDuration, CouponResponse, CreateCouponRequest, CreateCouponResponse,
+    CreatePromotionCodeRequest, CreatePromotionCodeResponse,
+    DeactivateCouponRequest, DeactivateCouponResponse,
+    DeactivatePromotionCodeRequest, DeactivatePromotionCodeResponse,
+    ListCouponsRequest, ListCouponsResponse, ListPromotionCodesRequest,
+    ListPromotionCodesResponse, PromotionCodeResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::StripeAccount;
+use crate::products::put_offer_product;
+use crate::{
+    connected_stripe_client, parse_id_error_to_status, parse_uuid,
+    stripe_error_to_status, CommerceService,
+};
+
+pub struct CouponService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    stripe_client: Client,
+    commerce_service: CommerceService,
+}
+
+impl CouponService {
+    const LIST_LIMIT: u64 = 100;
+
+    fn metadata_key_shop_id() -> String {
+        String::from("shop_id")
+    }
+
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        stripe_client: Client,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            stripe_client,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        stripe_client: Client,
+        commerce_service: CommerceService,
+    ) -> CouponServiceServer<Self> {
+        CouponServiceServer::new(Self::new(
+            pool,
+            verifier,
+            stripe_client,
+            commerce_service,
+        ))
+    }
+
+    fn to_coupon_response(shop_id: &str, coupon: Coupon) -> CouponResponse {
+        let duration = match coupon.duration {
+            Some(StripeCouponDuration::Once) => CouponDuration::Once,
+            Some(StripeCouponDuration::Repeating) => CouponDuration::Repeating,
+            Some(StripeCouponDuration::Forever) => CouponDuration::Forever,
+            None => CouponDuration::Unspecified,
+        };
+
+        CouponResponse {
+            coupon_id: coupon.id.to_string(),
+            shop_id: shop_id.to_owned(),
+            name: coupon.name.unwrap_or_default(),
+            percent_off: coupon.percent_off,
+            amount_off: coupon.amount_off.and_then(|a| a.try_into().ok()),
+            currency: coupon.currency.map(|c| c.to_string()),
+            duration: duration.into(),
+            duration_in_months: coupon
+                .duration_in_months
+                .and_then(|d| d.try_into().ok()),
+            max_redemptions: coupon
+                .max_redemptions
+                .and_then(|m| m.try_into().ok()),
+            redeem_by: coupon.redeem_by,
+            offer_ids: coupon
+                .applies_to
+                .map(|a| a.products)
+                .unwrap_or_default(),
+            times_redeemed: coupon
+                .times_redeemed
+                .and_then(|t| t.try_into().ok())
+                .unwrap_or_default(),
+            valid: coupon.valid.unwrap_or(false),
+            created_at: coupon.created.unwrap_or_default(),
+        }
+    }
+
+    fn to_promotion_code_response(
+        promotion_code: PromotionCode,
+    ) -> PromotionCodeResponse {
+        PromotionCodeResponse {
+            promotion_code_id: promotion_code.id.to_string(),
+            coupon_id: promotion_code.coupon.id.to_string(),
+            code: promotion_code.code,
+            active: promotion_code.active,
+            max_redemptions: promotion_code
+                .max_redemptions
+                .and_then(|m| m.try_into().ok()),
+            expires_at: promotion_code.expires_at,
+            times_redeemed: promotion_code
+                .times_redeemed
+                .try_into()
+                .unwrap_or_default(),
+            created_at: promotion_code.created,
+        }
+    }
+
+    async fn get_stripe_client_for_owner(
+        &self,
+        shop_id: &str,
+        user_id: &String,
+    ) -> Result<Client, Status> {
+        let shop_uuid = parse_uuid(shop_id, "shop_id")?;
+
+        let stripe_account =
+            StripeAccount::get_for_user(&self.pool, &shop_uuid, user_id)
+                .await?
+                .ok_or_else(|| Status::not_found("stripe account"))?;
+
+        connected_stripe_client(&self.stripe_client, &stripe_account)
+    }
+}
+
+#[async_trait]
+impl coupon_service_server::CouponService for CouponService {
+    async fn create_coupon(
+        &self,
+        request: Request<CreateCouponRequest>,
+    ) -> Result<Response<CreateCouponResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let duration = request.get_ref().duration();
+
+        let CreateCouponRequest {
+            shop_id,
+            name,
+            percent_off,
+            amount_off,
+            currency,
+            duration_in_months,
+            max_redemptions,
+            redeem_by,
+            offer_ids,
+            ..
+        } = request.into_inner();
+
+        let stripe_client =
+            self.get_stripe_client_for_owner(&shop_id, &user_id).await?;
+
+        let mut create_coupon = CreateCoupon::new();
+
+        match (percent_off, amount_off) {
+            (Some(percent_off), None) => {
+                if percent_off <= 0.0 || percent_off > 100.0 {
+                    return Err(Status::invalid_argument(
+                        "percent_off must be greater than 0 and at most 100",
+                    ));
+                }
+                create_coupon.percent_off = Some(percent_off);
+            }
+            (None, Some(amount_off)) => {
+                if amount_off == 0 {
+                    return Err(Status::invalid_argument(
+                        "amount_off must be greater than 0",
+                    ));
+                }
+                let currency = currency
+                    .as_deref()
+                    .map(|c| c.to_lowercase())
+                    .ok_or_else(|| {
+                        Status::invalid_argument(
+                            "currency is required for amount_off",
+                        )
+                    })?;
+                create_coupon.amount_off = Some(i64::from(amount_off));
+                create_coupon.currency = Some(
+                    StripeCurrency::from_str(&currency)
+                        .map_err(|_| Status::invalid_argument("currency"))?,
+                );
+            }
+            _ => {
+                return Err(Status::invalid_argument(
+                    "exactly one of percent_off or amount_off is required",
+                ))
+            }
+        }
+
+        create_coupon.duration = match duration {
+            CouponDuration::Unspecified => {
+                return Err(Status::invalid_argument("duration"))
+            }
+            CouponDuration::Once => Some(StripeCouponDuration::Once),
+            CouponDuration::Repeating => {
+                create_coupon.duration_in_months =
+                    Some(i64::from(duration_in_months.ok_or_else(|| {
+                        Status::invalid_argument(
+                            "duration_in_months is required for repeating coupons",
+                        )
+                    })?));
+                Some(StripeCouponDuration::Repeating)
+            }
+            CouponDuration::Forever => Some(StripeCouponDuration::Forever),
+        };
+
+        create_coupon.name = (!name.is_empty()).then_some(name.as_str());
+        create_coupon.max_redemptions = max_redemptions.map(i64::from);
+        create_coupon.redeem_by = redeem_by;
+        create_coupon.metadata = Some(HashMap::from([(
+            Self::metadata_key_shop_id(),
+            shop_id.to_owned(),
+        )]));
+
+        // Coupons can only be restricted to products that exist on the
+        // connected account, so ensure every offer is mirrored as product.
+        if !offer_ids.is_empty() {
+            let mut products = Vec::with_capacity(offer_ids.len());
+            for offer_id in offer_ids.iter() {
+                let offer = self.commerce_service.get_offer(offer_id).await?;
+                if offer.shop_id != shop_id {
+                    return Err(Status::invalid_argument(format!(
+                        "offer '{offer_id}' does not belong to shop"
+                    )));
+                }
+                products.push(
+                    put_offer_product(&stripe_client, &offer, None)
+                        .await?
+                        .to_string(),
+                );
+            }
+            create_coupon.applies_to = Some(CreateCouponAppliesTo {
+                products: Some(products),
+            });
+        }
+
+        let coupon = Coupon::create(&stripe_client, create_coupon)
+            .await
+            .map_err(stripe_error_to_status)?;
+
+        Ok(Response::new(CreateCouponResponse {
+            coupon: Some(Self::to_coupon_response(&shop_id, coupon)),
+        }))
+    }
+
+    async fn list_coupons(
+        &self,
+        request: Request<ListCouponsRequest>,
+    ) -> Result<Response<ListCouponsResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let ListCouponsRequest { shop_id } = request.into_inner();
+
+        let stripe_client =
+            self.get_stripe_client_for_owner(&shop_id, &user_id).await?;
+
+        let mut list_coupons = ListCoupons::new();
+        list_coupons.limit = Some(Self::LIST_LIMIT);
+        list_coupons.expand = &["data.applies_to"];
+
+        let coupons = Coupon::list(&stripe_client, &list_coupons)
+            .await
+            .map_err(stripe_error_to_status)?
+            .data
+            .into_iter()
+            .map(|c| Self::to_coupon_response(&shop_id, c))
+            .collect();
+
+        Ok(Response::new(ListCouponsResponse { coupons }))
+    }
+
+    async fn deactivate_coupon(
+        &self,
+        request: Request<DeactivateCouponRequest>,
+    ) -> Result<Response<DeactivateCouponResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let DeactivateCouponRequest { shop_id, coupon_id } =
+            request.into_inner();
+
+        let stripe_client =
+            self.get_stripe_client_for_owner(&shop_id, &user_id).await?;
+
+        let coupon_id =
+            CouponId::from_str(&coupon_id).map_err(parse_id_error_to_status)?;
+
+        // Stripe coupons can not be deactivated. Deleting a coupon prevents
+        // new redemptions while discounts that were already applied remain.
+        Coupon::delete(&stripe_client, &coupon_id)
+            .await
+            .map_err(stripe_error_to_status)?;
+
+        Ok(Response::new(DeactivateCouponResponse {}))
+    }
+
+    async fn create_promotion_code(
+        &self,
+        request: Request<CreatePromotionCodeRequest>,
+    ) -> Result<Response<CreatePromotionCodeResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let CreatePromotionCodeRequest {
+            shop_id,
+            coupon_id,
+            code,
+            max_redemptions,
+            expires_at,
+        } = request.into_inner();
+
+        if code.is_empty() {
+            return Err(Status::invalid_argument("code"));
+        }
+
+        let stripe_client =
+            self.get_stripe_client_for_owner(&shop_id, &user_id).await?;
+
+        let metadata =
+            HashMap::from([(Self::metadata_key_shop_id(), shop_id.to_owned())]);
+
+        let mut create_promotion_code = CreatePromotionCode::new(&coupon_id);
+        create_promotion_code.code = Some(&code);
+        create_promotion_code.max_redemptions = max_redemptions.map(i64::from);
+        create_promotion_code.expires_at = expires_at;
+        create_promotion_code.metadata = Some(&metadata);
+
+        let promotion_code = create_promotion_code
+            .send(&stripe_client)
+            .await
+            .map_err(stripe_error_to_status)?;
+
+        Ok(Response::new(CreatePromotionCodeResponse {
+            promotion_code: Some(Self::to_promotion_code_response(
+                promotion_code,
+            )),
+        }))
+    }
+
+    async fn list_promotion_codes(
+        &self,
+        request: Request<ListPromotionCodesRequest>,
+    ) -> Result<Response<ListPromotionCodesResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let ListPromotionCodesRequest { shop_id, coupon_id } =
+            request.into_inner();
+
+        let stripe_client =
+            self.get_stripe_client_for_owner(&shop_id, &user_id).await?;
+
+        let mut list_promotion_codes = ListPromotionCodes::new();
+        list_promotion_codes.limit = Some(Self::LIST_LIMIT);
+        list_promotion_codes.coupon = coupon_id
+            .map(|c| CouponId::from_str(&c))
+            .transpose()
+            .map_err(parse_id_error_to_status)?;
+
+        let promotion_codes =
+            PromotionCode::list(&stripe_client, &list_promotion_codes)
+                .await
+                .map_err(stripe_error_to_status)?
+                .data
+                .into_iter()
+                .map(Self::to_promotion_code_response)
+                .collect();
+
+        Ok(Response::new(ListPromotionCodesResponse {
+            promotion_codes,
+        }))
+    }
+
+    async fn deactivate_promotion_code(
+        &self,
+        request: Request<DeactivatePromotionCodeRequest>,
+    ) -> Result<Response<DeactivatePromotionCodeResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let DeactivatePromotionCodeRequest {
+            shop_id,
+            promotion_code_id,
+        } = request.into_inner();
+
+        let stripe_client =
+            self.get_stripe_client_for_owner(&shop_id, &user_id).await?;
+
+        let promotion_code_id = PromotionCodeId::from_str(&promotion_code_id)
+            .map_err(parse_id_error_to_status)?;
+
+        let mut update_promotion_code = UpdatePromotionCode::new();
+        update_promotion_code.active = Some(false);
+
+        PromotionCode::update(
+            &stripe_client,
+            &promotion_code_id,
+            update_promotion_code,
+        )
+        .await
+        .map_err(stripe_error_to_status)?;
+
+        Ok(Response::new(DeactivatePromotionCodeResponse {}))
+    }
+}
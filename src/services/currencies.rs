@@ -0,0 +1,318 @@
+use stripe::Currency as StripeCurrency;
+use tonic::Status;
+
+use crate::api::sited_io::commerce::v1::Currency;
+
+/// Currencies without minor unit. Amounts are given in the major unit.
+const ZERO_DECIMAL_CURRENCIES: [&str; 16] = [
+    "bif", "clp", "djf", "gnf", "jpy", "kmf", "krw", "mga", "pyg", "rwf",
+    "ugx", "vnd", "vuv", "xaf", "xof", "xpf",
+];
+
+/// Minimum amounts Stripe accepts for a charge in the smallest currency unit.
+/// For currencies not listed here Stripe requires the equivalent of 0.50 USD,
+/// which can only be checked by Stripe itself.
+const MINIMUM_CHARGE_AMOUNTS: [(&str, u32); 24] = [
+    ("aed", 200),
+    ("aud", 50),
+    ("bgn", 100),
+    ("brl", 50),
+    ("cad", 50),
+    ("chf", 50),
+    ("czk", 1500),
+    ("dkk", 250),
+    ("eur", 50),
+    ("gbp", 30),
+    ("hkd", 400),
+    ("huf", 17500),
+    ("inr", 50),
+    ("jpy", 50),
+    ("mxn", 1000),
+    ("myr", 200),
+    ("nok", 300),
+    ("nzd", 50),
+    ("pln", 200),
+    ("ron", 200),
+    ("sek", 300),
+    ("sgd", 50),
+    ("thb", 1000),
+    ("usd", 50),
+];
+
+/// Minimum platform fees in the smallest currency unit, which apply instead
+/// of the minimum platform fee of shops in currencies other than EUR. Fees
+/// can only be charged in the listed currencies if a shop has a minimum
+/// platform fee.
+const MINIMUM_FEE_AMOUNTS: [(&str, u32); 23] = [
+    ("aed", 200),
+    ("aud", 80),
+    ("bgn", 100),
+    ("brl", 300),
+    ("cad", 75),
+    ("chf", 50),
+    ("czk", 1300),
+    ("dkk", 375),
+    ("gbp", 45),
+    ("hkd", 450),
+    ("huf", 20000),
+    ("inr", 4500),
+    ("jpy", 80),
+    ("mxn", 1000),
+    ("myr", 250),
+    ("nok", 600),
+    ("nzd", 90),
+    ("pln", 220),
+    ("ron", 250),
+    ("sek", 600),
+    ("sgd", 75),
+    ("thb", 2000),
+    ("usd", 55),
+];
+
+pub fn to_stripe_currency(currency: &i32) -> Result<StripeCurrency, Status> {
+    use stripe::Currency::*;
+
+    let c = Currency::try_from(*currency).map_err(|_| Status::internal(""))?;
+    Ok(match c {
+        Currency::Unspecified => return Err(Status::internal("")),
+        Currency::Eur => EUR,
+        Currency::Aed => AED,
+        Currency::Afn => AFN,
+        Currency::All => ALL,
+        Currency::Amd => AMD,
+        Currency::Ang => ANG,
+        Currency::Aoa => AOA,
+        Currency::Ars => ARS,
+        Currency::Aud => AUD,
+        Currency::Awg => AWG,
+        Currency::Azn => AZN,
+        Currency::Bam => BAM,
+        Currency::Bbd => BBD,
+        Currency::Bdt => BDT,
+        Currency::Bgn => BGN,
+        Currency::Bif => BIF,
+        Currency::Bmd => BMD,
+        Currency::Bnd => BND,
+        Currency::Bob => BOB,
+        Currency::Brl => BRL,
+        Currency::Bsd => BSD,
+        Currency::Bwp => BWP,
+        Currency::Byn => BYN,
+        Currency::Bzd => BZD,
+        Currency::Cad => CAD,
+        Currency::Cdf => CDF,
+        Currency::Chf => CHF,
+        Currency::Clp => CLP,
+        Currency::Cny => CNY,
+        Currency::Cop => COP,
+        Currency::Crc => CRC,
+        Currency::Cve => CVE,
+        Currency::Czk => CZK,
+        Currency::Djf => DJF,
+        Currency::Dkk => DKK,
+        Currency::Dop => DOP,
+        Currency::Dzd => DZD,
+        Currency::Egp => EGP,
+        Currency::Etb => ETB,
+        Currency::Fjd => FJD,
+        Currency::Fkp => FKP,
+        Currency::Gbp => GBP,
+        Currency::Gel => GEL,
+        Currency::Gip => GIP,
+        Currency::Gmd => GMD,
+        Currency::Gnf => GNF,
+        Currency::Gtq => GTQ,
+        Currency::Gyd => GYD,
+        Currency::Hkd => HKD,
+        Currency::Hnl => HNL,
+        Currency::Htg => HTG,
+        Currency::Huf => HUF,
+        Currency::Idr => IDR,
+        Currency::Ils => ILS,
+        Currency::Inr => INR,
+        Currency::Isk => ISK,
+        Currency::Jmd => JMD,
+        Currency::Jpy => JPY,
+        Currency::Kes => KES,
+        Currency::Kgs => KGS,
+        Currency::Khr => KHR,
+        Currency::Kmf => KMF,
+        Currency::Krw => KRW,
+        Currency::Kyd => KYD,
+        Currency::Kzt => KZT,
+        Currency::Lak => LAK,
+        Currency::Lbp => LBP,
+        Currency::Lkr => LKR,
+        Currency::Lrd => LRD,
+        Currency::Lsl => LSL,
+        Currency::Mad => MAD,
+        Currency::Mdl => MDL,
+        Currency::Mga => MGA,
+        Currency::Mkd => MKD,
+        Currency::Mmk => MMK,
+        Currency::Mnt => MNT,
+        Currency::Mop => MOP,
+        Currency::Mur => MUR,
+        Currency::Mvr => MVR,
+        Currency::Mwk => MWK,
+        Currency::Mxn => MXN,
+        Currency::Myr => MYR,
+        Currency::Mzn => MZN,
+        Currency::Nad => NAD,
+        Currency::Ngn => NGN,
+        Currency::Nio => NIO,
+        Currency::Nok => NOK,
+        Currency::Npr => NPR,
+        Currency::Nzd => NZD,
+        Currency::Pab => PAB,
+        Currency::Pen => PEN,
+        Currency::Pgk => PGK,
+        Currency::Php => PHP,
+        Currency::Pkr => PKR,
+        Currency::Pln => PLN,
+        Currency::Pyg => PYG,
+        Currency::Qar => QAR,
+        Currency::Ron => RON,
+        Currency::Rsd => RSD,
+        Currency::Rub => RUB,
+        Currency::Rwf => RWF,
+        Currency::Sar => SAR,
+        Currency::Sbd => SBD,
+        Currency::Scr => SCR,
+        Currency::Sek => SEK,
+        Currency::Sgd => SGD,
+        Currency::Shp => SHP,
+        Currency::Sos => SOS,
+        Currency::Srd => SRD,
+        Currency::Svc => SVC,
+        Currency::Szl => SZL,
+        Currency::Thb => THB,
+        Currency::Tjs => TJS,
+        Currency::Top => TOP,
+        Currency::Try => TRY,
+        Currency::Ttd => TTD,
+        Currency::Twd => TWD,
+        Currency::Tzs => TZS,
+        Currency::Uah => UAH,
+        Currency::Ugx => UGX,
+        Currency::Usd => USD,
+        Currency::Uyu => UYU,
+        Currency::Uzs => UZS,
+        Currency::Vnd => VND,
+        Currency::Vuv => VUV,
+        Currency::Wst => WST,
+        Currency::Xaf => XAF,
+        Currency::Xcd => XCD,
+        Currency::Xof => XOF,
+        Currency::Xpf => XPF,
+        Currency::Yer => YER,
+        Currency::Zar => ZAR,
+        Currency::Zmw => ZMW,
+    })
+}
+
+/// Number of decimals of the given ISO currency code.
+pub fn decimals(currency: &str) -> u32 {
+    let currency = currency.to_lowercase();
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency.as_str()) {
+        0
+    } else {
+        2
+    }
+}
+
+/// Formats an amount given in the smallest unit of the currency in the
+/// major unit, e.g. `0.50 EUR`.
+fn format_amount(amount: u32, currency: &str) -> String {
+    let decimals = decimals(currency);
+    let currency = currency.to_uppercase();
+    if decimals == 0 {
+        return format!("{amount} {currency}");
+    }
+    let divisor = 10u32.pow(decimals);
+    format!(
+        "{}.{:0width$} {currency}",
+        amount / divisor,
+        amount % divisor,
+        width = decimals as usize
+    )
+}
+
+/// Returns the minimum platform fee in the smallest unit of the given
+/// currency. The minimum platform fee of shops is configured in euro cents
+/// and applies to EUR. Other currencies use the minimum platform fee
+/// configured for the currency, because amounts can not be converted without
+/// exchange rates.
+pub fn minimum_fee_amount(
+    min_fee_cent: u32,
+    currency: &str,
+) -> Result<u32, Status> {
+    let currency = currency.to_lowercase();
+    if min_fee_cent == 0 || currency == "eur" {
+        return Ok(min_fee_cent);
+    }
+
+    MINIMUM_FEE_AMOUNTS
+        .iter()
+        .find(|(c, _)| *c == currency)
+        .map(|(_, amount)| *amount)
+        .ok_or_else(|| {
+            Status::failed_precondition(format!(
+                "minimum platform fee is not configured for currency {currency}"
+            ))
+        })
+}
+
+pub fn minimum_charge_amount(currency: &str) -> Option<u32> {
+    let currency = currency.to_lowercase();
+    MINIMUM_CHARGE_AMOUNTS
+        .iter()
+        .find(|(c, _)| *c == currency)
+        .map(|(_, amount)| *amount)
+}
+
+pub fn check_minimum_charge_amount(
+    amount: u32,
+    currency: &str,
+) -> Result<(), Status> {
+    match minimum_charge_amount(currency) {
+        Some(minimum) if amount < minimum => {
+            Err(Status::failed_precondition(format!(
+                "amount is below the minimum charge amount of {}",
+                format_amount(minimum, currency)
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(50, "eur"), "0.50 EUR");
+        assert_eq!(format_amount(1999, "usd"), "19.99 USD");
+        assert_eq!(format_amount(50, "jpy"), "50 JPY");
+    }
+
+    #[test]
+    fn test_minimum_fee_amount() {
+        assert_eq!(minimum_fee_amount(50, "eur").unwrap(), 50);
+        assert_eq!(minimum_fee_amount(120, "EUR").unwrap(), 120);
+        assert_eq!(minimum_fee_amount(50, "JPY").unwrap(), 80);
+        assert_eq!(minimum_fee_amount(50, "huf").unwrap(), 20000);
+        assert_eq!(minimum_fee_amount(0, "gbp").unwrap(), 0);
+        assert_eq!(minimum_fee_amount(0, "isk").unwrap(), 0);
+        assert!(minimum_fee_amount(50, "isk").is_err());
+    }
+
+    #[test]
+    fn test_check_minimum_charge_amount() {
+        assert!(check_minimum_charge_amount(50, "eur").is_ok());
+        assert!(check_minimum_charge_amount(49, "eur").is_err());
+        assert!(check_minimum_charge_amount(29, "gbp").is_err());
+        assert!(check_minimum_charge_amount(1, "isk").is_ok());
+    }
+}
@@ -0,0 +1,107 @@
+use deadpool_postgres::Pool;
+use jwtk::jwk::RemoteJwksVerifier;
+use tonic::{async_trait, Request, Response, Status};
+
+use crate::api::sited_io::payment::v1::entitlement_service_server::{
+    self, EntitlementServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    CheckEntitlementRequest, CheckEntitlementResponse, EntitlementResponse,
+    ListEntitlementsRequest, ListEntitlementsResponse,
+};
+use crate::auth::verify_service_user;
+use crate::model::Entitlement;
+use crate::parse_uuid;
+
+pub struct EntitlementService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+}
+
+impl EntitlementService {
+    const DEFAULT_LIMIT: u64 = 100;
+
+    fn new(pool: Pool, verifier: RemoteJwksVerifier) -> Self {
+        Self { pool, verifier }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+    ) -> EntitlementServiceServer<Self> {
+        EntitlementServiceServer::new(Self::new(pool, verifier))
+    }
+
+    fn to_response(entitlement: Entitlement) -> EntitlementResponse {
+        EntitlementResponse {
+            entitlement_id: entitlement.entitlement_id.to_string(),
+            user_id: entitlement.user_id,
+            offer_id: entitlement.offer_id.to_string(),
+            shop_id: entitlement.shop_id.to_string(),
+            payment_id: entitlement.payment_id.map(|p| p.to_string()),
+            stripe_subscription_id: entitlement.stripe_subscription_id,
+            valid_from: entitlement.valid_from.timestamp(),
+            valid_until: entitlement.valid_until.map(|v| v.timestamp()),
+            created_at: entitlement.created_at.timestamp(),
+            updated_at: entitlement.updated_at.timestamp(),
+        }
+    }
+}
+
+#[async_trait]
+impl entitlement_service_server::EntitlementService for EntitlementService {
+    async fn check_entitlement(
+        &self,
+        request: Request<CheckEntitlementRequest>,
+    ) -> Result<Response<CheckEntitlementResponse>, Status> {
+        verify_service_user(request.metadata(), &self.verifier).await?;
+
+        let CheckEntitlementRequest { user_id, offer_id } =
+            request.into_inner();
+
+        let offer_uuid = parse_uuid(&offer_id, "offer_id")?;
+
+        let entitlement =
+            Entitlement::get_valid(&self.pool, &user_id, &offer_uuid).await?;
+
+        Ok(Response::new(CheckEntitlementResponse {
+            entitled: entitlement.is_some(),
+            entitlement: entitlement.map(Self::to_response),
+        }))
+    }
+
+    async fn list_entitlements(
+        &self,
+        request: Request<ListEntitlementsRequest>,
+    ) -> Result<Response<ListEntitlementsResponse>, Status> {
+        verify_service_user(request.metadata(), &self.verifier).await?;
+
+        let ListEntitlementsRequest {
+            user_id,
+            shop_id,
+            limit,
+            offset,
+        } = request.into_inner();
+
+        let shop_uuid =
+            shop_id.map(|s| parse_uuid(&s, "shop_id")).transpose()?;
+
+        let entitlements = Entitlement::list_valid(
+            &self.pool,
+            &user_id,
+            shop_uuid,
+            limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .min(Self::DEFAULT_LIMIT),
+            offset.unwrap_or(0),
+        )
+        .await?;
+
+        Ok(Response::new(ListEntitlementsResponse {
+            entitlements: entitlements
+                .into_iter()
+                .map(Self::to_response)
+                .collect(),
+        }))
+    }
+}
@@ -0,0 +1,419 @@
+use deadpool_postgres::Pool;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use tonic::metadata::MetadataMap;
+use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::api::sited_io::commerce::v1::OfferType;
+use crate::api::sited_io::payment::v1::license_key_service_server::{
+    self, LicenseKeyServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    AddLicenseKeysRequest, AddLicenseKeysResponse, GetLicenseKeyPoolRequest,
+    GetLicenseKeyPoolResponse, LicenseKeyPoolResponse, LicenseKeyResponse,
+    ListPurchasedLicenseKeysRequest, ListPurchasedLicenseKeysResponse,
+    PutLicenseKeyPoolRequest, PutLicenseKeyPoolResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::{LicenseKey, LicenseKeyPool};
+use crate::{parse_uuid, CommerceService};
+
+pub struct LicenseKeyService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    commerce_service: CommerceService,
+}
+
+impl LicenseKeyService {
+    const KEY_ALPHABET: &'static [u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+    const KEY_PLACEHOLDER: char = 'X';
+    const MIN_KEY_PLACEHOLDERS: usize = 12;
+    const MAX_KEY_LENGTH: usize = 200;
+    const MAX_ADD_LICENSE_KEYS: usize = 1000;
+    const MAX_GENERATE_ATTEMPTS: usize = 5;
+
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> LicenseKeyServiceServer<Self> {
+        LicenseKeyServiceServer::new(Self::new(
+            pool,
+            verifier,
+            commerce_service,
+        ))
+    }
+
+    fn to_pool_response(
+        key_pool: LicenseKeyPool,
+        available_keys: i64,
+    ) -> LicenseKeyPoolResponse {
+        LicenseKeyPoolResponse {
+            offer_id: key_pool.offer_id.to_string(),
+            shop_id: key_pool.shop_id.to_string(),
+            key_format: key_pool.key_format,
+            available_keys,
+            created_at: key_pool.created_at.timestamp(),
+            updated_at: key_pool.updated_at.timestamp(),
+        }
+    }
+
+    fn to_response(license_key: LicenseKey) -> LicenseKeyResponse {
+        LicenseKeyResponse {
+            license_key_id: license_key.license_key_id.to_string(),
+            offer_id: license_key.offer_id.to_string(),
+            shop_id: license_key.shop_id.to_string(),
+            license_key: license_key.license_key,
+            payment_id: license_key.payment_id.map(|p| p.to_string()),
+            assigned_at: license_key.assigned_at.map(|a| a.timestamp()),
+        }
+    }
+
+    /// Checks that the user owns the shop and the offer is a digital offer
+    /// of the shop.
+    async fn get_offer_for_owner(
+        &self,
+        shop_id: &String,
+        offer_id: &String,
+        user_id: &String,
+        metadata: &MetadataMap,
+    ) -> Result<(Uuid, Uuid), Status> {
+        let shop_uuid = parse_uuid(shop_id, "shop_id")?;
+        let offer_uuid = parse_uuid(offer_id, "offer_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(shop_id, user_id, metadata)
+            .await?;
+
+        let offer = self.commerce_service.get_offer(offer_id).await?;
+        if offer.shop_id != *shop_id {
+            return Err(Status::not_found("offer"));
+        }
+        if offer.r#type() != OfferType::Digital {
+            return Err(Status::failed_precondition(
+                "license keys are only supported for digital offers",
+            ));
+        }
+
+        Ok((shop_uuid, offer_uuid))
+    }
+
+    fn validate_key_format(key_format: &str) -> Result<(), Status> {
+        if key_format.len() > Self::MAX_KEY_LENGTH
+            || !key_format
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(Status::invalid_argument(
+                "key_format must only contain letters, digits and '-'",
+            ));
+        }
+
+        if key_format
+            .chars()
+            .filter(|c| *c == Self::KEY_PLACEHOLDER)
+            .count()
+            < Self::MIN_KEY_PLACEHOLDERS
+        {
+            return Err(Status::invalid_argument(format!(
+                "key_format must contain at least {} '{}' placeholders",
+                Self::MIN_KEY_PLACEHOLDERS,
+                Self::KEY_PLACEHOLDER
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Replaces each placeholder of the key format with a character of the
+    /// key alphabet, which leaves out characters that are easily confused.
+    fn generate_license_key(
+        key_format: &str,
+        mut random_bytes: impl Iterator<Item = u8>,
+    ) -> String {
+        key_format
+            .chars()
+            .map(|c| match c {
+                Self::KEY_PLACEHOLDER => {
+                    let byte = random_bytes.next().unwrap_or_default();
+                    char::from(
+                        Self::KEY_ALPHABET
+                            [usize::from(byte) % Self::KEY_ALPHABET.len()],
+                    )
+                }
+                c => c,
+            })
+            .collect()
+    }
+
+    /// Random bytes of v4 UUIDs, skipping the version and variant bytes.
+    fn random_bytes() -> impl Iterator<Item = u8> {
+        std::iter::repeat_with(Uuid::new_v4).flat_map(|uuid| {
+            uuid.into_bytes()
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| *i != 6 && *i != 8)
+                .map(|(_, byte)| byte)
+        })
+    }
+
+    /// Checkouts of offers with a pool are rejected once no uploaded keys
+    /// are left and no keys can be generated.
+    pub async fn check_license_key_available(
+        pool: &Pool,
+        offer_id: &Uuid,
+    ) -> Result<(), Status> {
+        let Some(key_pool) = LicenseKeyPool::get(pool, offer_id).await? else {
+            return Ok(());
+        };
+
+        if key_pool.key_format.is_none()
+            && LicenseKey::count_available(pool, offer_id).await? < 1
+        {
+            return Err(Status::failed_precondition(
+                "no license keys left for offer",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Assigns a license key of the offer's pool to a completed payment.
+    /// Offers without a pool do not issue license keys.
+    pub async fn assign_license_key(
+        pool: &Pool,
+        offer_id: &Uuid,
+        shop_id: &Uuid,
+        payment_id: &Uuid,
+        user_id: Option<String>,
+    ) -> Result<(), Status> {
+        let Some(key_pool) = LicenseKeyPool::get(pool, offer_id).await? else {
+            return Ok(());
+        };
+
+        // Payments are granted again when Stripe redelivers their event
+        if LicenseKey::is_assigned(pool, payment_id).await? {
+            return Ok(());
+        }
+
+        if LicenseKey::assign(pool, offer_id, payment_id, user_id.clone())
+            .await?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let Some(key_format) = key_pool.key_format else {
+            tracing::log::warn!(
+                "No license key left for offer {offer_id} and payment {payment_id}"
+            );
+            return Ok(());
+        };
+
+        // Generated keys may collide with existing keys of the offer, in
+        // which case another key is generated
+        for _ in 0..Self::MAX_GENERATE_ATTEMPTS {
+            if LicenseKey::create_assigned(
+                pool,
+                offer_id,
+                shop_id,
+                &Self::generate_license_key(&key_format, Self::random_bytes()),
+                payment_id,
+                user_id.clone(),
+            )
+            .await?
+            .is_some()
+                || LicenseKey::is_assigned(pool, payment_id).await?
+            {
+                return Ok(());
+            }
+        }
+
+        Err(Status::internal("failed to generate license key"))
+    }
+}
+
+#[async_trait]
+impl license_key_service_server::LicenseKeyService for LicenseKeyService {
+    async fn put_license_key_pool(
+        &self,
+        request: Request<PutLicenseKeyPoolRequest>,
+    ) -> Result<Response<PutLicenseKeyPoolResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let PutLicenseKeyPoolRequest {
+            shop_id,
+            offer_id,
+            key_format,
+        } = request.into_inner();
+
+        let (shop_uuid, offer_uuid) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        if let Some(key_format) = key_format.as_ref() {
+            Self::validate_key_format(key_format)?;
+        }
+
+        let key_pool = LicenseKeyPool::put(
+            &self.pool,
+            &offer_uuid,
+            &shop_uuid,
+            key_format,
+        )
+        .await?;
+        let available_keys =
+            LicenseKey::count_available(&self.pool, &offer_uuid).await?;
+
+        Ok(Response::new(PutLicenseKeyPoolResponse {
+            pool: Some(Self::to_pool_response(key_pool, available_keys)),
+        }))
+    }
+
+    async fn get_license_key_pool(
+        &self,
+        request: Request<GetLicenseKeyPoolRequest>,
+    ) -> Result<Response<GetLicenseKeyPoolResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let GetLicenseKeyPoolRequest { shop_id, offer_id } =
+            request.into_inner();
+
+        let (_, offer_uuid) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        let key_pool = LicenseKeyPool::get(&self.pool, &offer_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("license key pool"))?;
+        let available_keys =
+            LicenseKey::count_available(&self.pool, &offer_uuid).await?;
+
+        Ok(Response::new(GetLicenseKeyPoolResponse {
+            pool: Some(Self::to_pool_response(key_pool, available_keys)),
+        }))
+    }
+
+    async fn add_license_keys(
+        &self,
+        request: Request<AddLicenseKeysRequest>,
+    ) -> Result<Response<AddLicenseKeysResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let AddLicenseKeysRequest {
+            shop_id,
+            offer_id,
+            license_keys,
+        } = request.into_inner();
+
+        let (shop_uuid, offer_uuid) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        if license_keys.len() > Self::MAX_ADD_LICENSE_KEYS {
+            return Err(Status::invalid_argument(format!(
+                "at most {} license_keys can be added at once",
+                Self::MAX_ADD_LICENSE_KEYS
+            )));
+        }
+
+        let license_keys: Vec<String> = license_keys
+            .iter()
+            .map(|k| k.trim().to_owned())
+            .filter(|k| !k.is_empty())
+            .collect();
+        if license_keys.iter().any(|k| k.len() > Self::MAX_KEY_LENGTH) {
+            return Err(Status::invalid_argument(format!(
+                "license_keys must be at most {} characters long",
+                Self::MAX_KEY_LENGTH
+            )));
+        }
+
+        if LicenseKeyPool::get(&self.pool, &offer_uuid)
+            .await?
+            .is_none()
+        {
+            return Err(Status::failed_precondition(
+                "license key pool of offer does not exist",
+            ));
+        }
+
+        let added_keys =
+            LicenseKey::add(&self.pool, &offer_uuid, &shop_uuid, &license_keys)
+                .await?;
+        let available_keys =
+            LicenseKey::count_available(&self.pool, &offer_uuid).await?;
+
+        Ok(Response::new(AddLicenseKeysResponse {
+            added_keys,
+            available_keys,
+        }))
+    }
+
+    async fn list_purchased_license_keys(
+        &self,
+        request: Request<ListPurchasedLicenseKeysRequest>,
+    ) -> Result<Response<ListPurchasedLicenseKeysResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let ListPurchasedLicenseKeysRequest { offer_id } = request.into_inner();
+
+        let offer_uuid =
+            offer_id.map(|o| parse_uuid(&o, "offer_id")).transpose()?;
+
+        let license_keys =
+            LicenseKey::list_for_user(&self.pool, &user_id, offer_uuid).await?;
+
+        Ok(Response::new(ListPurchasedLicenseKeysResponse {
+            license_keys: license_keys
+                .into_iter()
+                .map(Self::to_response)
+                .collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_license_key() {
+        let license_key = LicenseKeyService::generate_license_key(
+            "KEY-XXXX-XXXX",
+            [0, 1, 31, 32, 255, 8, 9, 10].into_iter(),
+        );
+
+        assert_eq!(license_key, "KEY-AB9A-9JKL");
+    }
+
+    #[test]
+    fn test_validate_key_format() {
+        assert!(
+            LicenseKeyService::validate_key_format("XXXX-XXXX-XXXX").is_ok()
+        );
+        assert!(LicenseKeyService::validate_key_format("XXXX-XXXX").is_err());
+        assert!(
+            LicenseKeyService::validate_key_format("XXXX XXXX XXXX").is_err()
+        );
+    }
+}
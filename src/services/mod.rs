@@ -1,10 +1,40 @@
-use stripe::{ParseIdError, StripeError};
+use std::str::FromStr;
+
+use stripe::{AccountId, Client, ParseIdError, StripeError};
 use tonic::Status;
 use uuid::Uuid;
 
+use crate::model::StripeAccount;
+
+mod abandoned_checkout_service;
+mod billing_portal_service;
 pub mod countries;
+mod coupon_service;
+pub mod currencies;
+mod entitlement_service;
+mod license_key_service;
+mod offer_settings_service;
+mod order_service;
+mod payment_service;
+pub mod products;
+mod shipping_option_service;
+mod stock_service;
 mod stripe_service;
+mod stripe_webhook;
+mod tax_service;
+pub use abandoned_checkout_service::AbandonedCheckoutService;
+pub use billing_portal_service::BillingPortalService;
+pub use coupon_service::CouponService;
+pub use entitlement_service::EntitlementService;
+pub use license_key_service::LicenseKeyService;
+pub use offer_settings_service::OfferSettingsService;
+pub use order_service::OrderService;
+pub use payment_service::PaymentService;
+pub use shipping_option_service::ShippingOptionService;
+pub use stock_service::StockService;
 pub use stripe_service::StripeService;
+pub use stripe_webhook::StripeWebhook;
+pub use tax_service::TaxService;
 
 pub fn parse_uuid(uuid_string: &str, field: &str) -> Result<Uuid, Status> {
     uuid_string.parse().map_err(|_| {
@@ -23,3 +53,14 @@ pub fn parse_id_error_to_status(err: ParseIdError) -> Status {
     tracing::log::error!("{err}");
     Status::internal("")
 }
+
+pub fn connected_stripe_client(
+    stripe_client: &Client,
+    stripe_account: &StripeAccount,
+) -> Result<Client, Status> {
+    let stripe_account_id =
+        AccountId::from_str(&stripe_account.stripe_account_id)
+            .map_err(parse_id_error_to_status)?;
+
+    Ok(stripe_client.clone().with_stripe_account(stripe_account_id))
+}
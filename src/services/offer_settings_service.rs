@@ -0,0 +1,199 @@
+use deadpool_postgres::Pool;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use tonic::metadata::MetadataMap;
+use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::api::sited_io::commerce::v1::{OfferResponse, OfferType};
+use crate::api::sited_io::payment::v1::offer_settings_service_server::{
+    self, OfferSettingsServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    GetOfferSettingsRequest, GetOfferSettingsResponse, OfferSettingsResponse,
+    PutOfferSettingsRequest, PutOfferSettingsResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::OfferSettings;
+use crate::{parse_uuid, CommerceService};
+
+pub struct OfferSettingsService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    commerce_service: CommerceService,
+}
+
+impl OfferSettingsService {
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> OfferSettingsServiceServer<Self> {
+        OfferSettingsServiceServer::new(Self::new(
+            pool,
+            verifier,
+            commerce_service,
+        ))
+    }
+
+    fn to_response(offer_settings: OfferSettings) -> OfferSettingsResponse {
+        OfferSettingsResponse {
+            offer_id: offer_settings.offer_id.to_string(),
+            shop_id: offer_settings.shop_id.to_string(),
+            manual_capture: offer_settings.manual_capture,
+            trial_without_payment_method: offer_settings
+                .trial_without_payment_method,
+            allow_multiple_subscriptions: offer_settings
+                .allow_multiple_subscriptions,
+            max_quantity_per_session: offer_settings.max_quantity_per_session,
+            max_quantity_per_buyer: offer_settings.max_quantity_per_buyer,
+            created_at: offer_settings.created_at.timestamp(),
+            updated_at: offer_settings.updated_at.timestamp(),
+        }
+    }
+
+    fn default_response(
+        offer_id: String,
+        shop_id: String,
+    ) -> OfferSettingsResponse {
+        OfferSettingsResponse {
+            offer_id,
+            shop_id,
+            ..Default::default()
+        }
+    }
+
+    /// Checks that the user owns the shop and the offer belongs to the shop.
+    async fn get_offer_for_owner(
+        &self,
+        shop_id: &String,
+        offer_id: &String,
+        user_id: &String,
+        metadata: &MetadataMap,
+    ) -> Result<(Uuid, Uuid, OfferResponse), Status> {
+        let shop_uuid = parse_uuid(shop_id, "shop_id")?;
+        let offer_uuid = parse_uuid(offer_id, "offer_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(shop_id, user_id, metadata)
+            .await?;
+
+        let offer = self.commerce_service.get_offer(offer_id).await?;
+        if offer.shop_id != *shop_id {
+            return Err(Status::not_found("offer"));
+        }
+
+        Ok((shop_uuid, offer_uuid, offer))
+    }
+}
+
+#[async_trait]
+impl offer_settings_service_server::OfferSettingsService
+    for OfferSettingsService
+{
+    async fn put_offer_settings(
+        &self,
+        request: Request<PutOfferSettingsRequest>,
+    ) -> Result<Response<PutOfferSettingsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let PutOfferSettingsRequest {
+            shop_id,
+            offer_id,
+            manual_capture,
+            trial_without_payment_method,
+            allow_multiple_subscriptions,
+            max_quantity_per_session,
+            max_quantity_per_buyer,
+        } = request.into_inner();
+
+        let (shop_uuid, offer_uuid, offer) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        if manual_capture && offer.r#type() != OfferType::Service {
+            return Err(Status::failed_precondition(
+                "manual_capture is only supported for service offers",
+            ));
+        }
+
+        if trial_without_payment_method
+            && offer
+                .price
+                .as_ref()
+                .and_then(|p| p.recurring.as_ref())
+                .and_then(|r| r.trial_period_days)
+                .is_none_or(|d| d == 0)
+        {
+            return Err(Status::failed_precondition(
+                "trial_without_payment_method requires a recurring price with a trial",
+            ));
+        }
+
+        if [max_quantity_per_session, max_quantity_per_buyer]
+            .into_iter()
+            .flatten()
+            .any(|m| m < 1)
+        {
+            return Err(Status::invalid_argument(
+                "max_quantity_per_session and max_quantity_per_buyer must be greater than 0",
+            ));
+        }
+
+        let offer_settings = OfferSettings::put(
+            &self.pool,
+            &offer_uuid,
+            &shop_uuid,
+            manual_capture,
+            trial_without_payment_method,
+            allow_multiple_subscriptions,
+            max_quantity_per_session,
+            max_quantity_per_buyer,
+        )
+        .await?;
+
+        Ok(Response::new(PutOfferSettingsResponse {
+            offer_settings: Some(Self::to_response(offer_settings)),
+        }))
+    }
+
+    async fn get_offer_settings(
+        &self,
+        request: Request<GetOfferSettingsRequest>,
+    ) -> Result<Response<GetOfferSettingsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let GetOfferSettingsRequest { shop_id, offer_id } =
+            request.into_inner();
+
+        let (_, offer_uuid, _) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        let offer_settings =
+            match OfferSettings::get(&self.pool, &offer_uuid).await? {
+                Some(offer_settings) => Self::to_response(offer_settings),
+                None => Self::default_response(offer_id, shop_id),
+            };
+
+        Ok(Response::new(GetOfferSettingsResponse {
+            offer_settings: Some(offer_settings),
+        }))
+    }
+}
@@ -0,0 +1,470 @@
+use deadpool_postgres::Pool;
+use std::time::Duration;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::api::sited_io::payment::v1::order_service_server::{
+    self, OrderServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    GetOrderRequest, GetOrderResponse, GetPurchasedOrderRequest,
+    GetPurchasedOrderResponse, ListOrdersRequest, ListOrdersResponse,
+    ListPurchasedOrdersRequest, ListPurchasedOrdersResponse,
+    OrderEventResponse, OrderResponse, OrderStatus,
+    ShippingAddress as ShippingAddressResponse, UpdateOrderStatusRequest,
+    UpdateOrderStatusResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::{Order, OrderEvent, ShippingAddress};
+use crate::{parse_uuid, CommerceService};
+
+pub struct OrderService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    commerce_service: CommerceService,
+}
+
+impl OrderService {
+    const DEFAULT_LIMIT: u64 = 100;
+    const MAX_TRACKING_LENGTH: usize = 200;
+    const EVENT_PUBLISHING_INTERVAL: Duration = Duration::from_secs(60);
+    const EVENT_PUBLISHING_BATCH_SIZE: u64 = 500;
+
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> OrderServiceServer<Self> {
+        OrderServiceServer::new(Self::new(pool, verifier, commerce_service))
+    }
+
+    fn status_to_str(status: OrderStatus) -> Option<&'static str> {
+        match status {
+            OrderStatus::Unspecified => None,
+            OrderStatus::Paid => Some("paid"),
+            OrderStatus::Processing => Some("processing"),
+            OrderStatus::Shipped => Some("shipped"),
+            OrderStatus::Delivered => Some("delivered"),
+            OrderStatus::Returned => Some("returned"),
+        }
+    }
+
+    fn status_from_str(status: &str) -> OrderStatus {
+        match status {
+            "paid" => OrderStatus::Paid,
+            "processing" => OrderStatus::Processing,
+            "shipped" => OrderStatus::Shipped,
+            "delivered" => OrderStatus::Delivered,
+            "returned" => OrderStatus::Returned,
+            _ => OrderStatus::Unspecified,
+        }
+    }
+
+    /// Orders move forward from paid to shipped, optionally processing in
+    /// between. Shipped and delivered orders may be returned.
+    fn is_valid_transition(from: OrderStatus, to: OrderStatus) -> bool {
+        matches!(
+            (from, to),
+            (
+                OrderStatus::Paid,
+                OrderStatus::Processing | OrderStatus::Shipped
+            ) | (OrderStatus::Processing, OrderStatus::Shipped)
+                | (
+                    OrderStatus::Shipped,
+                    OrderStatus::Delivered | OrderStatus::Returned
+                )
+                | (OrderStatus::Delivered, OrderStatus::Returned)
+        )
+    }
+
+    fn to_response(order: Order) -> OrderResponse {
+        OrderResponse {
+            order_id: order.order_id.to_string(),
+            payment_id: order.payment_id.to_string(),
+            shop_id: order.shop_id.to_string(),
+            offer_id: order.offer_id.to_string(),
+            status: Self::status_from_str(&order.status).into(),
+            shipping_address: Some(ShippingAddressResponse {
+                name: order.shipping_address.name,
+                line1: order.shipping_address.line1,
+                line2: order.shipping_address.line2,
+                postal_code: order.shipping_address.postal_code,
+                city: order.shipping_address.city,
+                state: order.shipping_address.state,
+                country: order.shipping_address.country,
+            }),
+            carrier: order.carrier,
+            tracking_number: order.tracking_number,
+            created_at: order.created_at.timestamp(),
+            updated_at: order.updated_at.timestamp(),
+        }
+    }
+
+    fn to_event_response(order_event: OrderEvent) -> OrderEventResponse {
+        OrderEventResponse {
+            order_event_id: order_event.order_event_id.to_string(),
+            from_status: order_event
+                .from_status
+                .map(|s| Self::status_from_str(&s))
+                .unwrap_or_default()
+                .into(),
+            to_status: Self::status_from_str(&order_event.to_status).into(),
+            created_at: order_event.created_at.timestamp(),
+        }
+    }
+
+    /// Creates the order of a paid physical offer.
+    pub async fn create_order(
+        pool: &Pool,
+        payment_id: &Uuid,
+        shop_id: &Uuid,
+        offer_id: &Uuid,
+        shipping_address: ShippingAddress,
+    ) -> Result<(), Status> {
+        Order::create(
+            pool,
+            payment_id,
+            shop_id,
+            offer_id,
+            Self::status_to_str(OrderStatus::Paid).unwrap_or_default(),
+            shipping_address,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Publishes status changes of orders periodically. Events are posted to
+    /// the seller notification URL if configured, otherwise they are only
+    /// logged.
+    pub async fn run_event_publishing(
+        pool: Pool,
+        notification_url: Option<String>,
+    ) {
+        let http_client = reqwest::Client::new();
+        let mut interval =
+            tokio::time::interval(Self::EVENT_PUBLISHING_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = Self::publish_events(
+                &pool,
+                &http_client,
+                notification_url.as_ref(),
+            )
+            .await
+            {
+                tracing::log::error!("{err:?}");
+            }
+        }
+    }
+
+    /// Events are published in the order they happened, so publishing stops
+    /// at the first event which could not be delivered.
+    async fn publish_events(
+        pool: &Pool,
+        http_client: &reqwest::Client,
+        notification_url: Option<&String>,
+    ) -> Result<(), Status> {
+        let order_events = OrderEvent::list_unpublished(
+            pool,
+            Self::EVENT_PUBLISHING_BATCH_SIZE,
+        )
+        .await?;
+
+        for order_event in order_events {
+            if let Some(notification_url) = notification_url {
+                if let Err(err) = http_client
+                    .post(notification_url)
+                    .form(&[
+                        ("event", String::from("order_status_changed")),
+                        ("shop_id", order_event.shop_id.to_string()),
+                        ("order_id", order_event.order_id.to_string()),
+                        (
+                            "from_status",
+                            order_event.from_status.clone().unwrap_or_default(),
+                        ),
+                        ("to_status", order_event.to_status.clone()),
+                        (
+                            "created_at",
+                            order_event.created_at.timestamp().to_string(),
+                        ),
+                    ])
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                {
+                    tracing::log::error!(
+                        "Failed to publish event {} of order {}: {err}",
+                        order_event.order_event_id,
+                        order_event.order_id
+                    );
+                    return Ok(());
+                }
+            }
+
+            OrderEvent::mark_published(pool, &order_event.order_event_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl order_service_server::OrderService for OrderService {
+    async fn list_orders(
+        &self,
+        request: Request<ListOrdersRequest>,
+    ) -> Result<Response<ListOrdersResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let ListOrdersRequest {
+            shop_id,
+            status,
+            limit,
+            offset,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let status = status
+            .map(|s| {
+                OrderStatus::try_from(s)
+                    .ok()
+                    .and_then(Self::status_to_str)
+                    .ok_or_else(|| {
+                        Status::invalid_argument("field status is not valid")
+                    })
+            })
+            .transpose()?;
+
+        let orders = Order::list(
+            &self.pool,
+            &shop_uuid,
+            status,
+            limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .min(Self::DEFAULT_LIMIT),
+            offset.unwrap_or(0),
+        )
+        .await?;
+
+        Ok(Response::new(ListOrdersResponse {
+            orders: orders.into_iter().map(Self::to_response).collect(),
+        }))
+    }
+
+    async fn get_order(
+        &self,
+        request: Request<GetOrderRequest>,
+    ) -> Result<Response<GetOrderResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let GetOrderRequest { shop_id, order_id } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+        let order_uuid = parse_uuid(&order_id, "order_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let order = Order::get(&self.pool, &order_uuid, &shop_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("order"))?;
+        let events = OrderEvent::list(&self.pool, &order_uuid).await?;
+
+        Ok(Response::new(GetOrderResponse {
+            order: Some(Self::to_response(order)),
+            events: events.into_iter().map(Self::to_event_response).collect(),
+        }))
+    }
+
+    async fn update_order_status(
+        &self,
+        request: Request<UpdateOrderStatusRequest>,
+    ) -> Result<Response<UpdateOrderStatusResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let UpdateOrderStatusRequest {
+            shop_id,
+            order_id,
+            status,
+            carrier,
+            tracking_number,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+        let order_uuid = parse_uuid(&order_id, "order_id")?;
+
+        let status = OrderStatus::try_from(status).map_err(|_| {
+            Status::invalid_argument("field status is not valid")
+        })?;
+
+        if [carrier.as_ref(), tracking_number.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|t| t.len() > Self::MAX_TRACKING_LENGTH)
+        {
+            return Err(Status::invalid_argument(format!(
+                "carrier and tracking_number must be at most {} characters long",
+                Self::MAX_TRACKING_LENGTH
+            )));
+        }
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let order = Order::get(&self.pool, &order_uuid, &shop_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("order"))?;
+
+        if !Self::is_valid_transition(
+            Self::status_from_str(&order.status),
+            status,
+        ) {
+            return Err(Status::failed_precondition(format!(
+                "order can not change from {} to {}",
+                order.status,
+                Self::status_to_str(status).unwrap_or("unspecified")
+            )));
+        }
+
+        let order = Order::update_status(
+            &self.pool,
+            &order_uuid,
+            &shop_uuid,
+            &order.status,
+            Self::status_to_str(status).unwrap_or_default(),
+            carrier,
+            tracking_number,
+        )
+        .await?
+        .ok_or_else(|| Status::aborted("order was changed concurrently"))?;
+
+        Ok(Response::new(UpdateOrderStatusResponse {
+            order: Some(Self::to_response(order)),
+        }))
+    }
+
+    async fn list_purchased_orders(
+        &self,
+        request: Request<ListPurchasedOrdersRequest>,
+    ) -> Result<Response<ListPurchasedOrdersResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let ListPurchasedOrdersRequest { limit, offset } = request.into_inner();
+
+        let orders = Order::list_for_user(
+            &self.pool,
+            &user_id,
+            limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .min(Self::DEFAULT_LIMIT),
+            offset.unwrap_or(0),
+        )
+        .await?;
+
+        Ok(Response::new(ListPurchasedOrdersResponse {
+            orders: orders.into_iter().map(Self::to_response).collect(),
+        }))
+    }
+
+    async fn get_purchased_order(
+        &self,
+        request: Request<GetPurchasedOrderRequest>,
+    ) -> Result<Response<GetPurchasedOrderResponse>, Status> {
+        let user_id = get_user_id(request.metadata(), &self.verifier).await?;
+
+        let GetPurchasedOrderRequest { order_id } = request.into_inner();
+
+        let order_uuid = parse_uuid(&order_id, "order_id")?;
+
+        let order = Order::get_for_user(&self.pool, &order_uuid, &user_id)
+            .await?
+            .ok_or_else(|| Status::not_found("order"))?;
+        let events = OrderEvent::list(&self.pool, &order_uuid).await?;
+
+        Ok(Response::new(GetPurchasedOrderResponse {
+            order: Some(Self::to_response(order)),
+            events: events.into_iter().map(Self::to_event_response).collect(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_transition() {
+        assert!(OrderService::is_valid_transition(
+            OrderStatus::Paid,
+            OrderStatus::Processing
+        ));
+        assert!(OrderService::is_valid_transition(
+            OrderStatus::Paid,
+            OrderStatus::Shipped
+        ));
+        assert!(OrderService::is_valid_transition(
+            OrderStatus::Shipped,
+            OrderStatus::Returned
+        ));
+        assert!(!OrderService::is_valid_transition(
+            OrderStatus::Paid,
+            OrderStatus::Delivered
+        ));
+        assert!(!OrderService::is_valid_transition(
+            OrderStatus::Delivered,
+            OrderStatus::Shipped
+        ));
+        assert!(!OrderService::is_valid_transition(
+            OrderStatus::Returned,
+            OrderStatus::Returned
+        ));
+    }
+
+    #[test]
+    fn test_status_str() {
+        for status in [
+            OrderStatus::Paid,
+            OrderStatus::Processing,
+            OrderStatus::Shipped,
+            OrderStatus::Delivered,
+            OrderStatus::Returned,
+        ] {
+            assert_eq!(
+                OrderService::status_to_str(status)
+                    .map(OrderService::status_from_str),
+                Some(status)
+            );
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use deadpool_postgres::Pool;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use stripe::{
+    CancelPaymentIntent, CapturePaymentIntent, Client, PaymentIntent,
+};
+use tonic::{async_trait, Request, Response, Status};
+
+use crate::api::sited_io::commerce::v1::OfferType;
+use crate::api::sited_io::payment::v1::payment_service_server::{
+    self, PaymentServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    CancelAuthorizationRequest, CancelAuthorizationResponse,
+    CapturePaymentRequest, CapturePaymentResponse, ClaimPurchasesRequest,
+    ClaimPurchasesResponse, ListPaymentsRequest, ListPaymentsResponse,
+    PaymentResponse,
+};
+use crate::auth::{get_user_id, get_user_id_and_verified_email};
+use crate::model::{Payment, StripeAccount};
+use crate::{
+    connected_stripe_client, parse_uuid, stripe_error_to_status,
+    CommerceService,
+};
+
+pub struct PaymentService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    stripe_client: Client,
+    commerce_service: CommerceService,
+}
+
+impl PaymentService {
+    const DEFAULT_LIMIT: u64 = 100;
+
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        stripe_client: Client,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            stripe_client,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        stripe_client: Client,
+        commerce_service: CommerceService,
+    ) -> PaymentServiceServer<Self> {
+        PaymentServiceServer::new(Self::new(
+            pool,
+            verifier,
+            stripe_client,
+            commerce_service,
+        ))
+    }
+
+    fn to_response(payment: Payment) -> PaymentResponse {
+        PaymentResponse {
+            payment_id: payment.payment_id.to_string(),
+            shop_id: payment.shop_id.to_string(),
+            offer_id: payment.offer_id.to_string(),
+            user_id: payment.user_id,
+            stripe_checkout_session_id: payment.stripe_checkout_session_id,
+            stripe_payment_intent_id: payment.stripe_payment_intent_id,
+            stripe_subscription_id: payment.stripe_subscription_id,
+            stripe_customer_id: payment.stripe_customer_id,
+            payment_status: payment.payment_status,
+            currency: payment.currency,
+            amount_subtotal: payment.amount_subtotal,
+            amount_discount: payment.amount_discount,
+            amount_shipping: payment.amount_shipping,
+            amount_tax: payment.amount_tax,
+            amount_total: payment.amount_total,
+            authorization_expires_at: payment
+                .authorization_expires_at
+                .map(|a| a.timestamp()),
+            trial_ends_at: payment.trial_ends_at.map(|t| t.timestamp()),
+            buyer_email: payment.buyer_email,
+            quantity: payment.quantity,
+            created_at: payment.created_at.timestamp(),
+            updated_at: payment.updated_at.timestamp(),
+        }
+    }
+
+    /// Loads an authorized payment of the shop of the given owner together
+    /// with a Stripe client for the connected account of the shop.
+    async fn get_authorized_payment(
+        &self,
+        shop_id: &str,
+        payment_id: &str,
+        user_id: &String,
+    ) -> Result<(Payment, String, Client), Status> {
+        let shop_uuid = parse_uuid(shop_id, "shop_id")?;
+        let payment_uuid = parse_uuid(payment_id, "payment_id")?;
+
+        let stripe_account =
+            StripeAccount::get_for_user(&self.pool, &shop_uuid, user_id)
+                .await?
+                .ok_or_else(|| Status::not_found("stripe account"))?;
+
+        let payment = Payment::get(&self.pool, &payment_uuid, &shop_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("payment"))?;
+
+        if payment.payment_status != "authorized" {
+            return Err(Status::failed_precondition(
+                "payment is not authorized",
+            ));
+        }
+        if payment
+            .authorization_expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+        {
+            return Err(Status::failed_precondition(
+                "authorization of payment expired",
+            ));
+        }
+
+        let payment_intent_id = payment
+            .stripe_payment_intent_id
+            .to_owned()
+            .ok_or_else(|| Status::internal("payment intent missing"))?;
+
+        let stripe_client =
+            connected_stripe_client(&self.stripe_client, &stripe_account)?;
+
+        Ok((payment, payment_intent_id, stripe_client))
+    }
+}
+
+#[async_trait]
+impl payment_service_server::PaymentService for PaymentService {
+    async fn list_payments(
+        &self,
+        request: Request<ListPaymentsRequest>,
+    ) -> Result<Response<ListPaymentsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let ListPaymentsRequest {
+            shop_id,
+            limit,
+            offset,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let payments = Payment::list(
+            &self.pool,
+            &shop_uuid,
+            limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .min(Self::DEFAULT_LIMIT),
+            offset.unwrap_or(0),
+        )
+        .await?;
+
+        Ok(Response::new(ListPaymentsResponse {
+            payments: payments.into_iter().map(Self::to_response).collect(),
+        }))
+    }
+
+    async fn capture_payment(
+        &self,
+        request: Request<CapturePaymentRequest>,
+    ) -> Result<Response<CapturePaymentResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let CapturePaymentRequest {
+            shop_id,
+            payment_id,
+        } = request.into_inner();
+
+        let (payment, payment_intent_id, stripe_client) = self
+            .get_authorized_payment(&shop_id, &payment_id, &user_id)
+            .await?;
+
+        PaymentIntent::capture(
+            &stripe_client,
+            &payment_intent_id,
+            CapturePaymentIntent::default(),
+        )
+        .await
+        .map_err(stripe_error_to_status)?;
+
+        // The webhook of the payment intent may have updated the payment
+        // already, in which case the updated payment is returned
+        let payment = match Payment::update_status(
+            &self.pool,
+            &payment_intent_id,
+            &payment.payment_status,
+            "paid",
+        )
+        .await?
+        {
+            Some(payment) => payment,
+            None => {
+                Payment::get(&self.pool, &payment.payment_id, &payment.shop_id)
+                    .await?
+                    .ok_or_else(|| Status::not_found("payment"))?
+            }
+        };
+
+        Ok(Response::new(CapturePaymentResponse {
+            payment: Some(Self::to_response(payment)),
+        }))
+    }
+
+    async fn cancel_authorization(
+        &self,
+        request: Request<CancelAuthorizationRequest>,
+    ) -> Result<Response<CancelAuthorizationResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let CancelAuthorizationRequest {
+            shop_id,
+            payment_id,
+        } = request.into_inner();
+
+        let (payment, payment_intent_id, stripe_client) = self
+            .get_authorized_payment(&shop_id, &payment_id, &user_id)
+            .await?;
+
+        PaymentIntent::cancel(
+            &stripe_client,
+            &payment_intent_id,
+            CancelPaymentIntent::default(),
+        )
+        .await
+        .map_err(stripe_error_to_status)?;
+
+        // The webhook of the payment intent may have updated the payment
+        // already, in which case the updated payment is returned
+        let payment = match Payment::update_status(
+            &self.pool,
+            &payment_intent_id,
+            &payment.payment_status,
+            "canceled",
+        )
+        .await?
+        {
+            Some(payment) => payment,
+            None => {
+                Payment::get(&self.pool, &payment.payment_id, &payment.shop_id)
+                    .await?
+                    .ok_or_else(|| Status::not_found("payment"))?
+            }
+        };
+
+        Ok(Response::new(CancelAuthorizationResponse {
+            payment: Some(Self::to_response(payment)),
+        }))
+    }
+
+    async fn claim_purchases(
+        &self,
+        request: Request<ClaimPurchasesRequest>,
+    ) -> Result<Response<ClaimPurchasesResponse>, Status> {
+        let (user_id, email) =
+            get_user_id_and_verified_email(request.metadata(), &self.verifier)
+                .await?;
+
+        let unclaimed_payments =
+            Payment::list_unclaimed(&self.pool, &email).await?;
+
+        // Offers are looked up before claiming, so a failed lookup leaves
+        // the purchases unclaimed. Purchases of deleted offers are claimed
+        // without granting access.
+        let mut digital_offer_ids = Vec::new();
+        for offer_id in unclaimed_payments
+            .iter()
+            .map(|p| p.offer_id)
+            .collect::<HashSet<_>>()
+        {
+            if self
+                .commerce_service
+                .find_offer(&offer_id.to_string())
+                .await?
+                .is_some_and(|o| o.r#type() == OfferType::Digital)
+            {
+                digital_offer_ids.push(offer_id);
+            }
+        }
+
+        let payments = Payment::claim(
+            &self.pool,
+            &unclaimed_payments
+                .iter()
+                .map(|p| p.payment_id)
+                .collect::<Vec<_>>(),
+            &digital_offer_ids,
+            &user_id,
+        )
+        .await?;
+
+        Ok(Response::new(ClaimPurchasesResponse {
+            payments: payments.into_iter().map(Self::to_response).collect(),
+        }))
+    }
+}
@@ -0,0 +1,341 @@
+use std::str::FromStr;
+
+use sha2::{Digest, Sha256};
+use stripe::{
+    Client, CreatePrice, CreatePriceCustomUnitAmount, CreatePriceRecurring,
+    CreatePriceRecurringInterval, CreatePriceTiers, CreateProduct,
+    Currency as StripeCurrency, IdOrCreate, ListPrices, Price as StripePrice,
+    PriceBillingScheme, PriceId, PriceTaxBehavior,
+    PriceTiersMode as StripePriceTiersMode, Product, ProductId, StripeError,
+    TaxCodeId, UpTo, UpToOther, UpdateProduct,
+};
+use tonic::Status;
+
+use crate::api::sited_io::commerce::v1::{
+    OfferResponse, Price, PriceTiersMode, RecurringInterval,
+};
+use crate::{parse_id_error_to_status, stripe_error_to_status};
+
+/// Offers are mirrored as Stripe products on the connected account of their
+/// shop. The offer id is used as product id, so coupons restricted to
+/// products can be mapped back to offers without additional lookups.
+pub fn offer_product_id(offer_id: &str) -> Result<ProductId, Status> {
+    offer_id.parse().map_err(parse_id_error_to_status)
+}
+
+/// Creates or updates the Stripe product of the given offer.
+///
+/// `stripe_client` must be configured for the connected account of the shop.
+/// The tax code of an existing product is left untouched if `tax_code` is
+/// `None`.
+pub async fn put_offer_product(
+    stripe_client: &Client,
+    offer: &OfferResponse,
+    tax_code: Option<&str>,
+) -> Result<ProductId, Status> {
+    let product_id = offer_product_id(&offer.offer_id)?;
+
+    let description =
+        (!offer.description.is_empty()).then(|| offer.description.to_owned());
+    let images = (!offer.images.is_empty()).then(|| {
+        offer
+            .images
+            .iter()
+            .map(|i| i.image_url.to_owned())
+            .collect()
+    });
+
+    let mut update_product = UpdateProduct::new();
+    update_product.name = Some(&offer.name);
+    update_product.description = description.to_owned();
+    update_product.images = images.to_owned();
+    update_product.tax_code = tax_code.map(str::to_owned);
+
+    match Product::update(stripe_client, &product_id, update_product).await {
+        Ok(product) => Ok(product.id),
+        Err(StripeError::Stripe(err)) if err.http_status == 404 => {
+            let mut create_product = CreateProduct::new(&offer.name);
+            create_product.id = Some(&offer.offer_id);
+            create_product.description = description.as_deref();
+            create_product.images = images;
+            create_product.tax_code = tax_code
+                .map(TaxCodeId::from_str)
+                .transpose()
+                .map_err(parse_id_error_to_status)?;
+
+            Ok(Product::create(stripe_client, create_product)
+                .await
+                .map_err(stripe_error_to_status)?
+                .id)
+        }
+        Err(err) => Err(stripe_error_to_status(err)),
+    }
+}
+
+/// Derives the lookup key of a Stripe price from the sha256 digest of its
+/// configuration, which is stable across releases and platforms.
+fn price_lookup_key(
+    product_id: &ProductId,
+    price: &Price,
+    tax_behavior: Option<PriceTaxBehavior>,
+) -> String {
+    let recurring = price
+        .recurring
+        .as_ref()
+        .map(|r| format!("{}/{}", r.interval().as_str_name(), r.interval_count))
+        .unwrap_or_default();
+    let tiers = price
+        .tiers
+        .iter()
+        .map(|t| {
+            format!(
+                "{}/{}/{}",
+                t.up_to.map(|u| u.to_string()).unwrap_or_default(),
+                t.unit_amount,
+                t.flat_amount.map(|f| f.to_string()).unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let custom_unit_amount = price
+        .custom_unit_amount
+        .as_ref()
+        .map(|c| {
+            format!(
+                ":{}/{}/{}",
+                c.minimum.map(|m| m.to_string()).unwrap_or_default(),
+                c.maximum.map(|m| m.to_string()).unwrap_or_default(),
+                c.preset.map(|p| p.to_string()).unwrap_or_default(),
+            )
+        })
+        .unwrap_or_default();
+
+    let digest = Sha256::digest(format!(
+        "{}:{}:{recurring}:{}:{tiers}{custom_unit_amount}",
+        price.currency().as_str_name(),
+        price.tiers_mode().as_str_name(),
+        tax_behavior.map(|t| t.as_str()).unwrap_or_default(),
+    ));
+    let digest = digest
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+
+    format!("{product_id}-{digest}")
+}
+
+/// Returns the active Stripe price with the given lookup key.
+async fn get_price_by_lookup_key(
+    stripe_client: &Client,
+    lookup_key: &str,
+) -> Result<Option<PriceId>, Status> {
+    let mut list_prices = ListPrices::new();
+    list_prices.active = Some(true);
+    list_prices.lookup_keys = Some(vec![lookup_key.to_owned()]);
+
+    Ok(StripePrice::list(stripe_client, &list_prices)
+        .await
+        .map_err(stripe_error_to_status)?
+        .data
+        .into_iter()
+        .next()
+        .map(|p| p.id))
+}
+
+/// Creates the Stripe price of an offer with a tiered price or returns the
+/// existing one.
+///
+/// Tiered prices can't be passed inline to checkout sessions. Stripe prices
+/// can't be changed once created, so their lookup key is derived from the
+/// price configuration and a new price is created whenever it changes.
+pub async fn put_offer_tiered_price(
+    stripe_client: &Client,
+    product_id: &ProductId,
+    price: &Price,
+    currency: StripeCurrency,
+    tax_behavior: Option<PriceTaxBehavior>,
+) -> Result<PriceId, Status> {
+    let tiers_mode = match price.tiers_mode() {
+        PriceTiersMode::Unspecified => {
+            return Err(Status::internal("price.tiers_mode unspecified"))
+        }
+        PriceTiersMode::Graduated => StripePriceTiersMode::Graduated,
+        PriceTiersMode::Volume => StripePriceTiersMode::Volume,
+    };
+
+    let recurring = match price.recurring.as_ref() {
+        Some(r) => Some(CreatePriceRecurring {
+            interval: match r.interval() {
+                RecurringInterval::Unspecified => {
+                    return Err(Status::internal(
+                        "price.recurring.interval unspecified",
+                    ))
+                }
+                RecurringInterval::Day => CreatePriceRecurringInterval::Day,
+                RecurringInterval::Week => CreatePriceRecurringInterval::Week,
+                RecurringInterval::Month => CreatePriceRecurringInterval::Month,
+                RecurringInterval::Year => CreatePriceRecurringInterval::Year,
+            },
+            interval_count: Some(u64::from(r.interval_count)),
+            ..Default::default()
+        }),
+        None => None,
+    };
+
+    let lookup_key = price_lookup_key(product_id, price, tax_behavior);
+
+    if let Some(price_id) =
+        get_price_by_lookup_key(stripe_client, &lookup_key).await?
+    {
+        return Ok(price_id);
+    }
+
+    let mut create_price = CreatePrice::new(currency);
+    create_price.product = Some(IdOrCreate::Id(product_id));
+    create_price.lookup_key = Some(&lookup_key);
+    create_price.billing_scheme = Some(PriceBillingScheme::Tiered);
+    create_price.tiers_mode = Some(tiers_mode);
+    create_price.tiers = Some(
+        price
+            .tiers
+            .iter()
+            .map(|t| CreatePriceTiers {
+                up_to: Some(match t.up_to {
+                    Some(up_to) => UpTo::Max(u64::from(up_to)),
+                    None => UpTo::Other(UpToOther::Inf),
+                }),
+                unit_amount: Some(i64::from(t.unit_amount)),
+                flat_amount: t.flat_amount.map(i64::from),
+                ..Default::default()
+            })
+            .collect(),
+    );
+    create_price.recurring = recurring;
+    create_price.tax_behavior = tax_behavior;
+
+    Ok(StripePrice::create(stripe_client, create_price)
+        .await
+        .map_err(stripe_error_to_status)?
+        .id)
+}
+
+/// Creates the Stripe price of an offer with a one-time pay-what-you-want
+/// price or returns the existing one.
+///
+/// Buyers choose the amount in Stripe Checkout, which requires a Stripe price
+/// with a custom unit amount. Like tiered prices, these can't be passed
+/// inline to checkout sessions.
+pub async fn put_offer_custom_unit_amount_price(
+    stripe_client: &Client,
+    product_id: &ProductId,
+    price: &Price,
+    currency: StripeCurrency,
+    tax_behavior: Option<PriceTaxBehavior>,
+) -> Result<PriceId, Status> {
+    let custom_unit_amount = price
+        .custom_unit_amount
+        .as_ref()
+        .ok_or_else(|| Status::internal("price.custom_unit_amount missing"))?;
+
+    let lookup_key = price_lookup_key(product_id, price, tax_behavior);
+
+    if let Some(price_id) =
+        get_price_by_lookup_key(stripe_client, &lookup_key).await?
+    {
+        return Ok(price_id);
+    }
+
+    let mut create_price = CreatePrice::new(currency);
+    create_price.product = Some(IdOrCreate::Id(product_id));
+    create_price.lookup_key = Some(&lookup_key);
+    create_price.custom_unit_amount = Some(CreatePriceCustomUnitAmount {
+        enabled: true,
+        minimum: custom_unit_amount.minimum.map(i64::from),
+        maximum: custom_unit_amount.maximum.map(i64::from),
+        preset: custom_unit_amount.preset.map(i64::from),
+    });
+    create_price.tax_behavior = tax_behavior;
+
+    Ok(StripePrice::create(stripe_client, create_price)
+        .await
+        .map_err(stripe_error_to_status)?
+        .id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::api::sited_io::commerce::v1::{
+        Currency, CustomUnitAmount, PriceTier,
+    };
+
+    #[test]
+    fn test_price_lookup_key() {
+        let product_id: ProductId =
+            "0b9a7a4c-2f5e-4d0a-9e3c-1f2d3c4b5a69".parse().unwrap();
+        let mut price = Price {
+            currency: Currency::Eur.into(),
+            tiers_mode: PriceTiersMode::Graduated.into(),
+            tiers: vec![
+                PriceTier {
+                    up_to: Some(10),
+                    unit_amount: 500,
+                    flat_amount: None,
+                },
+                PriceTier {
+                    up_to: None,
+                    unit_amount: 400,
+                    flat_amount: Some(100),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let lookup_key = price_lookup_key(&product_id, &price, None);
+        assert_eq!(
+            lookup_key,
+            price_lookup_key(&product_id, &price.clone(), None)
+        );
+        assert!(lookup_key.starts_with("0b9a7a4c-2f5e-4d0a-9e3c-1f2d3c4b5a69-"));
+        assert_eq!(lookup_key.len(), 36 + 1 + 64);
+
+        assert_ne!(
+            lookup_key,
+            price_lookup_key(
+                &product_id,
+                &price,
+                Some(PriceTaxBehavior::Inclusive)
+            )
+        );
+
+        price.tiers[1].flat_amount = None;
+        assert_ne!(lookup_key, price_lookup_key(&product_id, &price, None));
+
+        let price = Price {
+            currency: Currency::Eur.into(),
+            custom_unit_amount: Some(CustomUnitAmount {
+                minimum: Some(500),
+                maximum: None,
+                preset: Some(1000),
+            }),
+            ..Default::default()
+        };
+        let lookup_key = price_lookup_key(&product_id, &price, None);
+        assert_ne!(
+            lookup_key,
+            price_lookup_key(
+                &product_id,
+                &Price {
+                    custom_unit_amount: Some(CustomUnitAmount {
+                        minimum: Some(500),
+                        maximum: None,
+                        preset: Some(1500),
+                    }),
+                    ..price.clone()
+                },
+                None
+            )
+        );
+    }
+}
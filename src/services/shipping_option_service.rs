@@ -0,0 +1,550 @@
+use deadpool_postgres::Pool;
+use std::str::FromStr;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use stripe::Currency as StripeCurrency;
+use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::api::sited_io::payment::v1::shipping_option_service_server::{
+    self, ShippingOptionServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    CreateShippingOptionRequest, CreateShippingOptionResponse,
+    CreateShippingZoneRequest, CreateShippingZoneResponse,
+    DeleteShippingOptionRequest, DeleteShippingOptionResponse,
+    DeleteShippingZoneRequest, DeleteShippingZoneResponse,
+    ListShippingOptionsRequest, ListShippingOptionsResponse,
+    ListShippingZonesRequest, ListShippingZonesResponse,
+    ShippingOptionResponse, ShippingZoneRate as ShippingZoneRateMsg,
+    ShippingZoneResponse, UpdateShippingOptionRequest,
+    UpdateShippingOptionResponse, UpdateShippingZoneRequest,
+    UpdateShippingZoneResponse,
+};
+use crate::auth::get_user_id;
+use crate::countries::parse_country_code;
+use crate::model::{ShippingOption, ShippingZone, ShippingZoneRate};
+use crate::{parse_uuid, CommerceService};
+
+pub struct ShippingOptionService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    commerce_service: CommerceService,
+}
+
+impl ShippingOptionService {
+    /// Stripe Checkout accepts at most 5 shipping options per session.
+    const MAX_SHIPPING_OPTIONS: i64 = 5;
+
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> ShippingOptionServiceServer<Self> {
+        ShippingOptionServiceServer::new(Self::new(
+            pool,
+            verifier,
+            commerce_service,
+        ))
+    }
+
+    fn to_response(
+        shipping_option: ShippingOption,
+        zone_rates: &[ShippingZoneRate],
+    ) -> ShippingOptionResponse {
+        let zone_rates = zone_rates
+            .iter()
+            .filter(|r| {
+                r.shipping_option_id == shipping_option.shipping_option_id
+            })
+            .map(|r| ShippingZoneRateMsg {
+                shipping_zone_id: r.shipping_zone_id.to_string(),
+                amount: Self::to_u32(r.amount),
+            })
+            .collect();
+
+        ShippingOptionResponse {
+            shipping_option_id: shipping_option.shipping_option_id.to_string(),
+            shop_id: shipping_option.shop_id.to_string(),
+            display_name: shipping_option.display_name,
+            currency: shipping_option.currency,
+            amount: Self::to_u32(shipping_option.amount),
+            domestic_country: shipping_option.domestic_country,
+            international_amount: shipping_option
+                .international_amount
+                .map(Self::to_u32),
+            min_delivery_days: shipping_option
+                .min_delivery_days
+                .map(Self::to_u32),
+            max_delivery_days: shipping_option
+                .max_delivery_days
+                .map(Self::to_u32),
+            free_shipping_threshold: shipping_option
+                .free_shipping_threshold
+                .map(Self::to_u32),
+            created_at: shipping_option.created_at.timestamp(),
+            updated_at: shipping_option.updated_at.timestamp(),
+            zone_rates,
+        }
+    }
+
+    fn to_zone_response(shipping_zone: ShippingZone) -> ShippingZoneResponse {
+        ShippingZoneResponse {
+            shipping_zone_id: shipping_zone.shipping_zone_id.to_string(),
+            shop_id: shipping_zone.shop_id.to_string(),
+            name: shipping_zone.name,
+            countries: shipping_zone.countries,
+            created_at: shipping_zone.created_at.timestamp(),
+            updated_at: shipping_zone.updated_at.timestamp(),
+        }
+    }
+
+    fn to_u32(value: i64) -> u32 {
+        u32::try_from(value).unwrap_or_default()
+    }
+
+    fn validate_display_name(display_name: &str) -> Result<(), Status> {
+        if display_name.trim().is_empty() {
+            return Err(Status::invalid_argument(
+                "field display_name must not be empty",
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_currency(currency: &str) -> Result<String, Status> {
+        StripeCurrency::from_str(&currency.to_lowercase())
+            .map(|c| c.to_string())
+            .map_err(|_| Status::invalid_argument("currency"))
+    }
+
+    fn validate_international_amount(
+        domestic_country: Option<String>,
+        international_amount: Option<u32>,
+    ) -> Result<Option<String>, Status> {
+        match (domestic_country, international_amount) {
+            (Some(domestic_country), Some(_)) => {
+                parse_country_code(&domestic_country).ok_or_else(|| {
+                    Status::invalid_argument(
+                        "field domestic_country is not a valid country code",
+                    )
+                })?;
+                Ok(Some(domestic_country.to_uppercase()))
+            }
+            (None, None) => Ok(None),
+            _ => Err(Status::invalid_argument(
+                "domestic_country and international_amount must be set together",
+            )),
+        }
+    }
+
+    fn validate_zone_name(name: &str) -> Result<(), Status> {
+        if name.trim().is_empty() {
+            return Err(Status::invalid_argument(
+                "field name must not be empty",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates country codes of a shipping zone. A country can only be part
+    /// of one shipping zone per shop, which is enforced by the database.
+    fn validate_countries(
+        countries: Vec<String>,
+    ) -> Result<Vec<String>, Status> {
+        let mut validated_countries: Vec<String> = Vec::new();
+        for country in countries {
+            parse_country_code(&country).ok_or_else(|| {
+                Status::invalid_argument(format!(
+                    "field countries contains invalid country code '{country}'"
+                ))
+            })?;
+            let country = country.to_uppercase();
+            if !validated_countries.contains(&country) {
+                validated_countries.push(country);
+            }
+        }
+        Ok(validated_countries)
+    }
+
+    async fn validate_zone_rates(
+        &self,
+        shop_id: &Uuid,
+        zone_rates: Vec<ShippingZoneRateMsg>,
+    ) -> Result<Vec<(Uuid, i64)>, Status> {
+        if zone_rates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let shipping_zones = ShippingZone::list(&self.pool, shop_id).await?;
+
+        let mut validated_zone_rates: Vec<(Uuid, i64)> = Vec::new();
+        for zone_rate in zone_rates {
+            let shipping_zone_id = parse_uuid(
+                &zone_rate.shipping_zone_id,
+                "zone_rates.shipping_zone_id",
+            )?;
+            if !shipping_zones
+                .iter()
+                .any(|z| z.shipping_zone_id == shipping_zone_id)
+            {
+                return Err(Status::not_found(zone_rate.shipping_zone_id));
+            }
+            if validated_zone_rates
+                .iter()
+                .any(|(id, _)| *id == shipping_zone_id)
+            {
+                return Err(Status::invalid_argument(
+                    "zone_rates contains duplicate shipping zones",
+                ));
+            }
+            validated_zone_rates
+                .push((shipping_zone_id, i64::from(zone_rate.amount)));
+        }
+        Ok(validated_zone_rates)
+    }
+
+    fn validate_delivery_days(
+        min_delivery_days: Option<u32>,
+        max_delivery_days: Option<u32>,
+    ) -> Result<(), Status> {
+        if let (Some(min), Some(max)) = (min_delivery_days, max_delivery_days) {
+            if min > max {
+                return Err(Status::invalid_argument(
+                    "min_delivery_days must not exceed max_delivery_days",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl shipping_option_service_server::ShippingOptionService
+    for ShippingOptionService
+{
+    async fn create_shipping_option(
+        &self,
+        request: Request<CreateShippingOptionRequest>,
+    ) -> Result<Response<CreateShippingOptionResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let CreateShippingOptionRequest {
+            shop_id,
+            display_name,
+            currency,
+            amount,
+            domestic_country,
+            international_amount,
+            min_delivery_days,
+            max_delivery_days,
+            free_shipping_threshold,
+            zone_rates,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        Self::validate_display_name(&display_name)?;
+        let currency = Self::validate_currency(&currency)?;
+        let domestic_country = Self::validate_international_amount(
+            domestic_country,
+            international_amount,
+        )?;
+        Self::validate_delivery_days(min_delivery_days, max_delivery_days)?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let zone_rates =
+            self.validate_zone_rates(&shop_uuid, zone_rates).await?;
+
+        if ShippingOption::count(&self.pool, &shop_uuid).await?
+            >= Self::MAX_SHIPPING_OPTIONS
+        {
+            return Err(Status::failed_precondition(format!(
+                "a shop can have at most {} shipping options",
+                Self::MAX_SHIPPING_OPTIONS
+            )));
+        }
+
+        let (created_shipping_option, created_zone_rates) =
+            ShippingOption::create(
+                &self.pool,
+                &shop_uuid,
+                &display_name,
+                &currency,
+                amount.into(),
+                domestic_country,
+                international_amount.map(i64::from),
+                min_delivery_days.map(i64::from),
+                max_delivery_days.map(i64::from),
+                free_shipping_threshold.map(i64::from),
+                &zone_rates,
+            )
+            .await?;
+
+        Ok(Response::new(CreateShippingOptionResponse {
+            shipping_option: Some(Self::to_response(
+                created_shipping_option,
+                &created_zone_rates,
+            )),
+        }))
+    }
+
+    async fn update_shipping_option(
+        &self,
+        request: Request<UpdateShippingOptionRequest>,
+    ) -> Result<Response<UpdateShippingOptionResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let UpdateShippingOptionRequest {
+            shop_id,
+            shipping_option_id,
+            display_name,
+            currency,
+            amount,
+            domestic_country,
+            international_amount,
+            min_delivery_days,
+            max_delivery_days,
+            free_shipping_threshold,
+            zone_rates,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+        let shipping_option_uuid =
+            parse_uuid(&shipping_option_id, "shipping_option_id")?;
+
+        Self::validate_display_name(&display_name)?;
+        let currency = Self::validate_currency(&currency)?;
+        let domestic_country = Self::validate_international_amount(
+            domestic_country,
+            international_amount,
+        )?;
+        Self::validate_delivery_days(min_delivery_days, max_delivery_days)?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let zone_rates =
+            self.validate_zone_rates(&shop_uuid, zone_rates).await?;
+
+        let (updated_shipping_option, updated_zone_rates) =
+            ShippingOption::update(
+                &self.pool,
+                &shipping_option_uuid,
+                &shop_uuid,
+                &display_name,
+                &currency,
+                amount.into(),
+                domestic_country,
+                international_amount.map(i64::from),
+                min_delivery_days.map(i64::from),
+                max_delivery_days.map(i64::from),
+                free_shipping_threshold.map(i64::from),
+                &zone_rates,
+            )
+            .await?
+            .ok_or_else(|| Status::not_found(shipping_option_id))?;
+
+        Ok(Response::new(UpdateShippingOptionResponse {
+            shipping_option: Some(Self::to_response(
+                updated_shipping_option,
+                &updated_zone_rates,
+            )),
+        }))
+    }
+
+    async fn list_shipping_options(
+        &self,
+        request: Request<ListShippingOptionsRequest>,
+    ) -> Result<Response<ListShippingOptionsResponse>, Status> {
+        let ListShippingOptionsRequest { shop_id } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let shipping_options =
+            ShippingOption::list(&self.pool, &shop_uuid).await?;
+        let zone_rates = ShippingZoneRate::list(&self.pool, &shop_uuid).await?;
+
+        Ok(Response::new(ListShippingOptionsResponse {
+            shipping_options: shipping_options
+                .into_iter()
+                .map(|o| Self::to_response(o, &zone_rates))
+                .collect(),
+        }))
+    }
+
+    async fn delete_shipping_option(
+        &self,
+        request: Request<DeleteShippingOptionRequest>,
+    ) -> Result<Response<DeleteShippingOptionResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let DeleteShippingOptionRequest {
+            shop_id,
+            shipping_option_id,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+        let shipping_option_uuid =
+            parse_uuid(&shipping_option_id, "shipping_option_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        if !ShippingOption::delete(
+            &self.pool,
+            &shipping_option_uuid,
+            &shop_uuid,
+        )
+        .await?
+        {
+            return Err(Status::not_found(shipping_option_id));
+        }
+
+        Ok(Response::new(DeleteShippingOptionResponse {}))
+    }
+
+    async fn create_shipping_zone(
+        &self,
+        request: Request<CreateShippingZoneRequest>,
+    ) -> Result<Response<CreateShippingZoneResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let CreateShippingZoneRequest {
+            shop_id,
+            name,
+            countries,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        Self::validate_zone_name(&name)?;
+        let countries = Self::validate_countries(countries)?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let created_shipping_zone =
+            ShippingZone::create(&self.pool, &shop_uuid, &name, &countries)
+                .await?;
+
+        Ok(Response::new(CreateShippingZoneResponse {
+            shipping_zone: Some(Self::to_zone_response(created_shipping_zone)),
+        }))
+    }
+
+    async fn update_shipping_zone(
+        &self,
+        request: Request<UpdateShippingZoneRequest>,
+    ) -> Result<Response<UpdateShippingZoneResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let UpdateShippingZoneRequest {
+            shop_id,
+            shipping_zone_id,
+            name,
+            countries,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+        let shipping_zone_uuid =
+            parse_uuid(&shipping_zone_id, "shipping_zone_id")?;
+
+        Self::validate_zone_name(&name)?;
+        let countries = Self::validate_countries(countries)?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let updated_shipping_zone = ShippingZone::update(
+            &self.pool,
+            &shipping_zone_uuid,
+            &shop_uuid,
+            &name,
+            &countries,
+        )
+        .await?
+        .ok_or_else(|| Status::not_found(shipping_zone_id))?;
+
+        Ok(Response::new(UpdateShippingZoneResponse {
+            shipping_zone: Some(Self::to_zone_response(updated_shipping_zone)),
+        }))
+    }
+
+    async fn list_shipping_zones(
+        &self,
+        request: Request<ListShippingZonesRequest>,
+    ) -> Result<Response<ListShippingZonesResponse>, Status> {
+        let ListShippingZonesRequest { shop_id } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let shipping_zones = ShippingZone::list(&self.pool, &shop_uuid).await?;
+
+        Ok(Response::new(ListShippingZonesResponse {
+            shipping_zones: shipping_zones
+                .into_iter()
+                .map(Self::to_zone_response)
+                .collect(),
+        }))
+    }
+
+    async fn delete_shipping_zone(
+        &self,
+        request: Request<DeleteShippingZoneRequest>,
+    ) -> Result<Response<DeleteShippingZoneResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let DeleteShippingZoneRequest {
+            shop_id,
+            shipping_zone_id,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+        let shipping_zone_uuid =
+            parse_uuid(&shipping_zone_id, "shipping_zone_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        if !ShippingZone::delete(&self.pool, &shipping_zone_uuid, &shop_uuid)
+            .await?
+        {
+            return Err(Status::not_found(shipping_zone_id));
+        }
+
+        Ok(Response::new(DeleteShippingZoneResponse {}))
+    }
+}
@@ -0,0 +1,169 @@
+use deadpool_postgres::Pool;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use tonic::metadata::MetadataMap;
+use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
+
+use crate::api::sited_io::commerce::v1::OfferType;
+use crate::api::sited_io::payment::v1::stock_service_server::{
+    self, StockServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    DeleteOfferStockRequest, DeleteOfferStockResponse, GetOfferStockRequest,
+    GetOfferStockResponse, OfferStockResponse, PutOfferStockRequest,
+    PutOfferStockResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::OfferStock;
+use crate::{parse_uuid, CommerceService};
+
+pub struct StockService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    commerce_service: CommerceService,
+}
+
+impl StockService {
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> StockServiceServer<Self> {
+        StockServiceServer::new(Self::new(pool, verifier, commerce_service))
+    }
+
+    fn to_response(stock: OfferStock) -> OfferStockResponse {
+        OfferStockResponse {
+            offer_id: stock.offer_id.to_string(),
+            shop_id: stock.shop_id.to_string(),
+            quantity: stock.quantity,
+            reserved: stock.reserved,
+            available: stock.available(),
+            created_at: stock.created_at.timestamp(),
+            updated_at: stock.updated_at.timestamp(),
+        }
+    }
+
+    /// Checks that the user owns the shop and the offer is a physical offer
+    /// of the shop.
+    async fn get_offer_for_owner(
+        &self,
+        shop_id: &String,
+        offer_id: &String,
+        user_id: &String,
+        metadata: &MetadataMap,
+    ) -> Result<(Uuid, Uuid), Status> {
+        let shop_uuid = parse_uuid(shop_id, "shop_id")?;
+        let offer_uuid = parse_uuid(offer_id, "offer_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(shop_id, user_id, metadata)
+            .await?;
+
+        let offer = self.commerce_service.get_offer(offer_id).await?;
+        if offer.shop_id != *shop_id {
+            return Err(Status::not_found("offer"));
+        }
+        if offer.r#type() != OfferType::Physical {
+            return Err(Status::failed_precondition(
+                "stock is only supported for physical offers",
+            ));
+        }
+
+        Ok((shop_uuid, offer_uuid))
+    }
+}
+
+#[async_trait]
+impl stock_service_server::StockService for StockService {
+    async fn put_offer_stock(
+        &self,
+        request: Request<PutOfferStockRequest>,
+    ) -> Result<Response<PutOfferStockResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let PutOfferStockRequest {
+            shop_id,
+            offer_id,
+            quantity,
+        } = request.into_inner();
+
+        if quantity < 0 {
+            return Err(Status::invalid_argument(
+                "field quantity must not be negative",
+            ));
+        }
+
+        let (shop_uuid, offer_uuid) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        let stock =
+            OfferStock::put(&self.pool, &offer_uuid, &shop_uuid, quantity)
+                .await?;
+
+        Ok(Response::new(PutOfferStockResponse {
+            stock: Some(Self::to_response(stock)),
+        }))
+    }
+
+    async fn get_offer_stock(
+        &self,
+        request: Request<GetOfferStockRequest>,
+    ) -> Result<Response<GetOfferStockResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let GetOfferStockRequest { shop_id, offer_id } = request.into_inner();
+
+        let (_, offer_uuid) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        let stock = OfferStock::get(&self.pool, &offer_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("offer stock"))?;
+
+        Ok(Response::new(GetOfferStockResponse {
+            stock: Some(Self::to_response(stock)),
+        }))
+    }
+
+    async fn delete_offer_stock(
+        &self,
+        request: Request<DeleteOfferStockRequest>,
+    ) -> Result<Response<DeleteOfferStockResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let DeleteOfferStockRequest { shop_id, offer_id } =
+            request.into_inner();
+
+        let (shop_uuid, offer_uuid) = self
+            .get_offer_for_owner(&shop_id, &offer_id, &user_id, &metadata)
+            .await?;
+
+        if !OfferStock::delete(&self.pool, &offer_uuid, &shop_uuid).await? {
+            return Err(Status::not_found("offer stock"));
+        }
+
+        Ok(Response::new(DeleteOfferStockResponse {}))
+    }
+}
@@ -2,59 +2,140 @@ use deadpool_postgres::Pool;
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use chrono::{Duration, Utc};
 use jwtk::jwk::RemoteJwksVerifier;
+use serde::Serialize;
+use stripe::generated::billing::subscription::{
+    PlanInterval, SubscriptionItemPriceData,
+    SubscriptionItemPriceDataRecurring, SubscriptionItemPriceDataTaxBehavior,
+    SubscriptionProrationBehavior,
+};
 use stripe::{
     Account, AccountId, AccountLink, AccountLinkType, AccountType,
-    CheckoutSession, CheckoutSessionMode, Client, CreateAccount,
-    CreateAccountLink, CreateCheckoutSession, CreateCheckoutSessionLineItems,
+    CancelSubscription, CancellationDetails, CheckoutSession,
+    CheckoutSessionCustomerCreation, CheckoutSessionMode,
+    CheckoutSessionPaymentMethodCollection, CheckoutSessionPaymentStatus,
+    CheckoutSessionStatus, Client, Coupon, CreateAccount, CreateAccountLink,
+    CreateCheckoutSession, CreateCheckoutSessionAfterExpiration,
+    CreateCheckoutSessionAfterExpirationRecovery,
+    CreateCheckoutSessionAutomaticTax, CreateCheckoutSessionConsentCollection,
+    CreateCheckoutSessionConsentCollectionPromotions,
+    CreateCheckoutSessionCustomerUpdate,
+    CreateCheckoutSessionCustomerUpdateAddress,
+    CreateCheckoutSessionCustomerUpdateName,
+    CreateCheckoutSessionCustomerUpdateShipping,
+    CreateCheckoutSessionDiscounts, CreateCheckoutSessionLineItems,
     CreateCheckoutSessionLineItemsAdjustableQuantity,
     CreateCheckoutSessionLineItemsPriceData,
     CreateCheckoutSessionLineItemsPriceDataProductData,
     CreateCheckoutSessionLineItemsPriceDataRecurring,
     CreateCheckoutSessionLineItemsPriceDataRecurringInterval,
+    CreateCheckoutSessionLineItemsPriceDataTaxBehavior,
     CreateCheckoutSessionPaymentIntentData,
+    CreateCheckoutSessionPaymentIntentDataCaptureMethod,
     CreateCheckoutSessionShippingAddressCollection,
     CreateCheckoutSessionShippingAddressCollectionAllowedCountries,
     CreateCheckoutSessionShippingOptions,
     CreateCheckoutSessionShippingOptionsShippingRateData,
+    CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimate,
+    CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMaximum,
+    CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMaximumUnit,
+    CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMinimum,
+    CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMinimumUnit,
     CreateCheckoutSessionShippingOptionsShippingRateDataFixedAmount,
+    CreateCheckoutSessionShippingOptionsShippingRateDataTaxBehavior,
     CreateCheckoutSessionShippingOptionsShippingRateDataType,
-    CreateCheckoutSessionSubscriptionData, Currency as StripeCurrency,
-    Subscription as StripeSubscription, SubscriptionId, UpdateSubscription,
+    CreateCheckoutSessionSubscriptionData,
+    CreateCheckoutSessionSubscriptionDataTrialSettings,
+    CreateCheckoutSessionSubscriptionDataTrialSettingsEndBehavior,
+    CreateCheckoutSessionSubscriptionDataTrialSettingsEndBehaviorMissingPaymentMethod,
+    CreateCheckoutSessionTaxIdCollection, CreateCustomer, CreateRefund,
+    Currency as StripeCurrency, Customer as StripeCustomer, CustomerId,
+    Invoice, ListCheckoutSessions, ListPromotionCodes, PriceTaxBehavior,
+    PromotionCode, Refund, RequestStrategy, Subscription as StripeSubscription,
+    SubscriptionId, SubscriptionStatus, UpdateSubscription,
+    UpdateSubscriptionCancellationDetails, UpdateSubscriptionItems,
+    UpdateSubscriptionPauseCollection,
+    UpdateSubscriptionPauseCollectionBehavior,
 };
+use tonic::metadata::MetadataMap;
 use tonic::{async_trait, Request, Response, Status};
+use uuid::Uuid;
 
 use crate::api::sited_io::commerce::v1::{
-    Currency, OfferType, PriceType, RecurringInterval,
+    CustomUnitAmount, OfferType, PriceBillingScheme, PriceTier, PriceTiersMode,
+    PriceType, RecurringInterval,
 };
 use crate::api::sited_io::payment::v1::stripe_service_server::{
     self, StripeServiceServer,
 };
 use crate::api::sited_io::payment::v1::{
-    CancelSubscriptionRequest, CancelSubscriptionResponse,
+    CancelSubscriptionRequest, CancelSubscriptionResponse, CancellationMode,
+    ChangeSubscriptionPlanRequest, ChangeSubscriptionPlanResponse,
     CreateAccountLinkRequest, CreateAccountLinkResponse, CreateAccountRequest,
     CreateAccountResponse, CreateCheckoutSessionRequest,
     CreateCheckoutSessionResponse, GetAccountDetailsRequest,
     GetAccountDetailsResponse, GetAccountRequest, GetAccountResponse,
+    PauseBehavior, PauseSubscriptionRequest, PauseSubscriptionResponse,
+    PreviewSubscriptionPlanChangeRequest,
+    PreviewSubscriptionPlanChangeResponse, ProrationBehavior, RefundPolicy,
     ResumeSubscriptionRequest, ResumeSubscriptionResponse,
+    ShippingAddress as ShippingAddressRequest,
     StripeAccount as StripeAccountMsg, StripeAccountDetails,
 };
-use crate::auth::{get_user_id, verify_service_user};
-use crate::countries::{to_stripe_country, ALL_STRIPE_COUNTRIES};
-use crate::model::StripeAccount;
+use crate::auth::{get_user_id, get_user_id_and_email, verify_service_user};
+use crate::countries::{
+    parse_country_code, to_stripe_country, ALL_STRIPE_COUNTRIES,
+};
+use crate::currencies::{
+    check_minimum_charge_amount, minimum_charge_amount, minimum_fee_amount,
+    to_stripe_currency,
+};
+use crate::model::{
+    Customer, Entitlement, OfferSettings, OfferStock, Payment, PaymentAmounts,
+    PurchaseReservation, ShippingAddress, ShippingOption, ShippingZone,
+    ShippingZoneRate, ShopTaxSettings, StripeAccount, SubscriptionCancellation,
+};
+use crate::products::{
+    put_offer_custom_unit_amount_price, put_offer_product,
+    put_offer_tiered_price,
+};
 use crate::{
-    parse_id_error_to_status, parse_uuid, stripe_error_to_status,
-    CommerceService,
+    connected_stripe_client, parse_id_error_to_status, parse_uuid,
+    stripe_error_to_status, CommerceService, LicenseKeyService, OrderService,
 };
 
+/// Parameters of resuming subscriptions, because async-stripe cannot unset
+/// the `pause_collection` of a subscription. An empty value unsets it.
+#[derive(Serialize)]
+struct ResumeSubscriptionParams {
+    cancel_at_period_end: bool,
+    pause_collection: &'static str,
+}
+
+/// Parameters of upcoming invoice previews with changed subscription items,
+/// which are not covered by async-stripe.
+#[derive(Serialize)]
+struct UpcomingInvoiceParams {
+    customer: String,
+    subscription: String,
+    subscription_items: Vec<UpdateSubscriptionItems>,
+    subscription_proration_behavior: SubscriptionProrationBehavior,
+    subscription_proration_date: i64,
+}
+
 pub struct StripeService {
     pool: Pool,
     verifier: RemoteJwksVerifier,
     stripe_client: Client,
     commerce_service: CommerceService,
+    collect_promotions_consent: bool,
 }
 
 impl StripeService {
+    const MAX_ADJUSTABLE_QUANTITY: i64 = 99;
+    const STOCK_RESERVATION_MINUTES: i64 = 60;
+
     fn metadata_key_user_id() -> String {
         String::from("user_id")
     }
@@ -67,6 +148,22 @@ impl StripeService {
         String::from("offer_id")
     }
 
+    fn metadata_key_offer_type() -> String {
+        String::from("offer_type")
+    }
+
+    fn metadata_key_billing_cycles() -> String {
+        String::from("billing_cycles")
+    }
+
+    fn metadata_key_platform_fee_percent() -> String {
+        String::from("platform_fee_percent")
+    }
+
+    fn metadata_key_minimum_platform_fee() -> String {
+        String::from("minimum_platform_fee")
+    }
+
     fn shipping_rate_key() -> String {
         String::from("SHIPPING")
     }
@@ -76,12 +173,14 @@ impl StripeService {
         verifier: RemoteJwksVerifier,
         stripe_client: Client,
         commerce_service: CommerceService,
+        collect_promotions_consent: bool,
     ) -> Self {
         Self {
             pool,
             verifier,
             stripe_client,
             commerce_service,
+            collect_promotions_consent,
         }
     }
 
@@ -90,12 +189,14 @@ impl StripeService {
         verifier: RemoteJwksVerifier,
         stripe_client: Client,
         commerce_service: CommerceService,
+        collect_promotions_consent: bool,
     ) -> StripeServiceServer<Self> {
         StripeServiceServer::new(Self::new(
             pool,
             verifier,
             stripe_client,
             commerce_service,
+            collect_promotions_consent,
         ))
     }
 
@@ -110,38 +211,297 @@ impl StripeService {
         }
     }
 
-    fn get_currency(currency: i32) -> Result<StripeCurrency, Status> {
-        let currency: Currency =
-            Currency::try_from(currency).map_err(|_| Status::internal(""))?;
-
-        match currency {
-            Currency::Unspecified => Err(Status::internal("")),
-            Currency::Eur => Ok(StripeCurrency::EUR),
-        }
-    }
-
+    /// The minimum platform fee of shops is configured in euro cents and
+    /// replaced by the minimum platform fee of other currencies.
     fn calculate_fee_amount(
         unit_amount: u32,
         fee_pct: u32,
-        min_fee_amount: u32,
-    ) -> i64 {
-        i64::from(((unit_amount * fee_pct) / 100).max(min_fee_amount))
+        min_fee_cent: u32,
+        currency: StripeCurrency,
+    ) -> Result<i64, Status> {
+        let min_fee_amount =
+            minimum_fee_amount(min_fee_cent, &currency.to_string())?;
+        let fee_amount = (u64::from(unit_amount) * u64::from(fee_pct)) / 100;
+        Ok(i64::try_from(fee_amount.max(u64::from(min_fee_amount)))
+            .unwrap_or(i64::MAX))
     }
 
     fn calculate_fee_percent(
         unit_amount: u32,
         fee_pct: u32,
-        min_fee_amount: u32,
-    ) -> f64 {
-        let fee_amount = (unit_amount * fee_pct) / 100;
-        if fee_amount < min_fee_amount {
+        min_fee_cent: u32,
+        currency: StripeCurrency,
+    ) -> Result<f64, Status> {
+        let min_fee_amount =
+            minimum_fee_amount(min_fee_cent, &currency.to_string())?;
+        let fee_amount = (u64::from(unit_amount) * u64::from(fee_pct)) / 100;
+        if fee_amount < u64::from(min_fee_amount) {
             let fee_percent =
-                f64::from(min_fee_amount * 100) / f64::from(unit_amount);
+                f64::from(min_fee_amount) * 100.0 / f64::from(unit_amount);
             // rouds f64 to second decimal
-            (fee_percent * 100.0).round() / 100.0
+            Ok((fee_percent * 100.0).round() / 100.0)
+        } else {
+            Ok(f64::from(fee_pct))
+        }
+    }
+
+    /// The fee percent of subscriptions applies to whole invoices, so it is
+    /// calculated on the total of the first invoice, which includes the setup
+    /// fee.
+    fn calculate_subscription_fee_percent(
+        recurring_amount: u32,
+        setup_fee: Option<u32>,
+        fee_pct: u32,
+        min_fee_cent: u32,
+        currency: StripeCurrency,
+    ) -> Result<f64, Status> {
+        let first_invoice_amount =
+            recurring_amount.saturating_add(setup_fee.unwrap_or(0));
+        Ok(Self::calculate_fee_percent(
+            first_invoice_amount,
+            fee_pct,
+            min_fee_cent,
+            currency,
+        )?
+        .min(100.0))
+    }
+
+    fn calculate_discounted_amount(unit_amount: u32, coupon: &Coupon) -> u32 {
+        if let Some(percent_off) = coupon.percent_off {
+            let discount =
+                (f64::from(unit_amount) * percent_off / 100.0).round();
+            unit_amount.saturating_sub(discount as u32)
+        } else if let Some(amount_off) = coupon.amount_off {
+            unit_amount
+                .saturating_sub(u32::try_from(amount_off).unwrap_or(u32::MAX))
         } else {
-            f64::from(fee_pct)
+            unit_amount
+        }
+    }
+
+    /// Returns the lowest amount buyers can choose in Stripe Checkout for
+    /// prices with a custom unit amount, which is at least the minimum charge
+    /// amount of the currency.
+    fn get_lowest_custom_unit_amount(
+        custom_unit_amount: &CustomUnitAmount,
+        currency: &str,
+    ) -> u32 {
+        custom_unit_amount
+            .minimum
+            .unwrap_or(0)
+            .max(minimum_charge_amount(currency).unwrap_or(0))
+    }
+
+    /// Validates the tiers of a tiered price. Tiers must be ordered by their
+    /// upper bound and only the last tier may be unbounded.
+    fn validate_price_tiers(tiers: &[PriceTier]) -> Result<(), Status> {
+        let Some((last, bounded)) = tiers.split_last() else {
+            return Err(Status::failed_precondition(
+                "offer.price.tiers missing",
+            ));
+        };
+
+        let mut lower = 0;
+        for tier in bounded {
+            match tier.up_to {
+                Some(up_to) if up_to > lower => lower = up_to,
+                _ => {
+                    return Err(Status::failed_precondition(
+                        "offer.price.tiers are not ordered",
+                    ))
+                }
+            }
+        }
+
+        if last.up_to.is_some() {
+            return Err(Status::failed_precondition(
+                "last tier of offer.price.tiers must be unbounded",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the lowest of the configured quantity limits, if any.
+    fn get_maximum_quantity(limits: &[Option<i64>]) -> Option<i64> {
+        limits.iter().flatten().copied().min()
+    }
+
+    /// Calculates the amount of `quantity` items of a tiered price. Graduated
+    /// tiers price each item by the tier it falls into, volume tiers price all
+    /// items by the tier the total quantity falls into. Flat amounts are added
+    /// once for every tier used.
+    fn calculate_tiered_amount(
+        tiers_mode: PriceTiersMode,
+        tiers: &[PriceTier],
+        quantity: u32,
+    ) -> Result<u32, Status> {
+        Self::validate_price_tiers(tiers)?;
+
+        let tier_amount = |tier: &PriceTier, quantity: u32| {
+            u64::from(quantity) * u64::from(tier.unit_amount)
+                + u64::from(tier.flat_amount.unwrap_or(0))
+        };
+
+        let amount = match tiers_mode {
+            PriceTiersMode::Unspecified => {
+                return Err(Status::internal("price.tiers_mode unspecified"))
+            }
+            PriceTiersMode::Graduated => {
+                let mut amount = 0;
+                let mut lower = 0;
+                for tier in tiers {
+                    if quantity <= lower {
+                        break;
+                    }
+                    let upper = tier.up_to.unwrap_or(quantity).min(quantity);
+                    amount += tier_amount(tier, upper - lower);
+                    lower = upper;
+                }
+                amount
+            }
+            PriceTiersMode::Volume => tiers
+                .iter()
+                .find(|t| t.up_to.is_none_or(|up_to| quantity <= up_to))
+                .map(|t| tier_amount(t, quantity))
+                .unwrap_or_default(),
+        };
+
+        u32::try_from(amount)
+            .map_err(|_| Status::invalid_argument("quantity is too large"))
+    }
+
+    fn coupon_applies_to_offer(coupon: &Coupon, offer_id: &str) -> bool {
+        coupon.valid.unwrap_or(true)
+            && coupon.applies_to.as_ref().is_none_or(|a| {
+                a.products.iter().any(|product| product == offer_id)
+            })
+    }
+
+    async fn list_active_promotion_codes(
+        stripe_client: &Client,
+        code: Option<&str>,
+    ) -> Result<Vec<PromotionCode>, Status> {
+        let mut list_promotion_codes = ListPromotionCodes::new();
+        list_promotion_codes.active = Some(true);
+        list_promotion_codes.code = code;
+        list_promotion_codes.limit = Some(100);
+        list_promotion_codes.expand = &["data.coupon.applies_to"];
+
+        Ok(PromotionCode::list(stripe_client, &list_promotion_codes)
+            .await
+            .map_err(stripe_error_to_status)?
+            .data)
+    }
+
+    /// Returns the Stripe customer of the user on the connected account,
+    /// creating it on first checkout.
+    async fn get_or_create_customer(
+        &self,
+        stripe_client: &Client,
+        stripe_account_id: &String,
+        user_id: &String,
+        email: Option<&str>,
+    ) -> Result<CustomerId, Status> {
+        let stripe_customer_id = match Customer::get(
+            &self.pool,
+            user_id,
+            stripe_account_id,
+        )
+        .await?
+        {
+            Some(customer) => customer.stripe_customer_id,
+            None => {
+                let mut create_customer = CreateCustomer::new();
+                create_customer.email = email;
+                create_customer.metadata = Some(HashMap::from([(
+                    Self::metadata_key_user_id(),
+                    user_id.to_owned(),
+                )]));
+                let stripe_customer =
+                    StripeCustomer::create(stripe_client, create_customer)
+                        .await
+                        .map_err(stripe_error_to_status)?;
+
+                // Another checkout of the user may have stored a customer
+                // in the meantime, which is reused instead of the customer
+                // created here
+                match Customer::create(
+                    &self.pool,
+                    user_id,
+                    stripe_account_id,
+                    &stripe_customer.id.to_string(),
+                )
+                .await?
+                {
+                    Some(customer) => customer.stripe_customer_id,
+                    None => {
+                        if let Err(err) = StripeCustomer::delete(
+                            stripe_client,
+                            &stripe_customer.id,
+                        )
+                        .await
+                        {
+                            tracing::log::warn!("{err}");
+                        }
+                        Customer::get(&self.pool, user_id, stripe_account_id)
+                            .await?
+                            .ok_or_else(|| Status::internal("customer"))?
+                            .stripe_customer_id
+                    }
+                }
+            }
+        };
+
+        CustomerId::from_str(&stripe_customer_id)
+            .map_err(parse_id_error_to_status)
+    }
+
+    /// Returns an active subscription of the user to the offer, including
+    /// subscriptions changed to the offer from another offer.
+    async fn find_active_subscription(
+        &self,
+        offer_id: &Uuid,
+        user_id: &String,
+    ) -> Result<Option<String>, Status> {
+        Ok(
+            Entitlement::get_valid_subscription(&self.pool, user_id, offer_id)
+                .await?
+                .and_then(|e| e.stripe_subscription_id),
+        )
+    }
+
+    /// Expires the open checkout sessions of the customer for the offer, so
+    /// the buyer can not subscribe twice by completing an older session.
+    async fn expire_open_checkout_sessions(
+        stripe_client: &Client,
+        customer_id: &CustomerId,
+        offer_id: &Uuid,
+    ) -> Result<(), Status> {
+        let mut list_checkout_sessions = ListCheckoutSessions::new();
+        list_checkout_sessions.customer = Some(customer_id.clone());
+        list_checkout_sessions.status = Some(CheckoutSessionStatus::Open);
+        list_checkout_sessions.limit = Some(100);
+
+        for checkout_session in
+            CheckoutSession::list(stripe_client, &list_checkout_sessions)
+                .await
+                .map_err(stripe_error_to_status)?
+                .data
+                .into_iter()
+                .filter(|c| {
+                    c.metadata.as_ref().is_some_and(|m| {
+                        m.get(&Self::metadata_key_offer_id())
+                            .is_some_and(|o| *o == offer_id.to_string())
+                    })
+                })
+        {
+            CheckoutSession::expire(stripe_client, &checkout_session.id)
+                .await
+                .map_err(stripe_error_to_status)?;
         }
+
+        Ok(())
     }
 
     fn get_recurring_interval(
@@ -159,6 +519,377 @@ impl StripeService {
         }
     }
 
+    fn get_plan_interval(
+        interval: RecurringInterval,
+    ) -> Result<PlanInterval, Status> {
+        match interval {
+            RecurringInterval::Unspecified => Err(Status::internal("")),
+            RecurringInterval::Day => Ok(PlanInterval::Day),
+            RecurringInterval::Week => Ok(PlanInterval::Week),
+            RecurringInterval::Month => Ok(PlanInterval::Month),
+            RecurringInterval::Year => Ok(PlanInterval::Year),
+        }
+    }
+
+    fn get_subscription_item_tax_behavior(
+        tax_behavior: &str,
+    ) -> SubscriptionItemPriceDataTaxBehavior {
+        use SubscriptionItemPriceDataTaxBehavior::*;
+
+        match tax_behavior {
+            "inclusive" => Inclusive,
+            "exclusive" => Exclusive,
+            _ => Unspecified,
+        }
+    }
+
+    /// Returns the part of `amount_paid` that covers the billing period from
+    /// `now` until its end.
+    fn calculate_unused_amount(
+        amount_paid: i64,
+        period_start: i64,
+        period_end: i64,
+        now: i64,
+    ) -> i64 {
+        let period = period_end - period_start;
+        if period <= 0 {
+            return 0;
+        }
+        let unused = (period_end - now).clamp(0, period);
+
+        i64::try_from(
+            i128::from(amount_paid) * i128::from(unused) / i128::from(period),
+        )
+        .unwrap_or(amount_paid)
+    }
+
+    fn get_pause_behavior(
+        behavior: PauseBehavior,
+    ) -> Result<UpdateSubscriptionPauseCollectionBehavior, Status> {
+        match behavior {
+            PauseBehavior::Unspecified => {
+                Err(Status::invalid_argument("field behavior is required"))
+            }
+            PauseBehavior::Void => {
+                Ok(UpdateSubscriptionPauseCollectionBehavior::Void)
+            }
+            PauseBehavior::KeepAsDraft => {
+                Ok(UpdateSubscriptionPauseCollectionBehavior::KeepAsDraft)
+            }
+            PauseBehavior::MarkUncollectible => {
+                Ok(UpdateSubscriptionPauseCollectionBehavior::MarkUncollectible)
+            }
+        }
+    }
+
+    fn get_proration_behavior(
+        proration_behavior: ProrationBehavior,
+    ) -> SubscriptionProrationBehavior {
+        match proration_behavior {
+            ProrationBehavior::Unspecified
+            | ProrationBehavior::CreateProrations => {
+                SubscriptionProrationBehavior::CreateProrations
+            }
+            ProrationBehavior::AlwaysInvoice => {
+                SubscriptionProrationBehavior::AlwaysInvoice
+            }
+            ProrationBehavior::None => SubscriptionProrationBehavior::None,
+        }
+    }
+
+    /// Returns the amounts of the upcoming invoice of a previewed plan change.
+    /// The invoice currency falls back to the currency of the subscription.
+    fn to_plan_change_preview(
+        upcoming_invoice: Invoice,
+        subscription_currency: StripeCurrency,
+        proration_date: i64,
+    ) -> PreviewSubscriptionPlanChangeResponse {
+        PreviewSubscriptionPlanChangeResponse {
+            currency: upcoming_invoice
+                .currency
+                .unwrap_or(subscription_currency)
+                .to_string(),
+            amount_due: upcoming_invoice.amount_due.unwrap_or(0),
+            total: upcoming_invoice.total.unwrap_or(0),
+            proration_date,
+        }
+    }
+
+    /// Returns the subscription item that replaces the plan of the
+    /// subscription with the price of the offer, together with the platform
+    /// fee percent of the new plan.
+    async fn get_plan_change(
+        &self,
+        stripe_client: &Client,
+        subscription: &StripeSubscription,
+        shop_id: &String,
+        offer_id: &String,
+        metadata: &MetadataMap,
+    ) -> Result<(UpdateSubscriptionItems, f64), Status> {
+        let [item] = subscription.items.data.as_slice() else {
+            return Err(Status::failed_precondition(
+                "subscription must have exactly one item",
+            ));
+        };
+        let quantity = u32::try_from(item.quantity.unwrap_or(1))
+            .map_err(|_| Status::internal("subscription item quantity"))?;
+
+        let found_offer = self.commerce_service.get_offer(offer_id).await?;
+        if found_offer.shop_id != *shop_id {
+            return Err(Status::not_found("offer"));
+        }
+
+        let price = found_offer
+            .price
+            .as_ref()
+            .ok_or_else(|| Status::internal("offer.price missing"))?;
+        let recurring = match price.price_type() {
+            PriceType::Recurring => price
+                .recurring
+                .as_ref()
+                .ok_or_else(|| Status::internal("offer.price.recurring"))?,
+            _ => {
+                return Err(Status::failed_precondition(
+                    "offer does not have a recurring price",
+                ))
+            }
+        };
+        if price.custom_unit_amount.is_some() {
+            return Err(Status::failed_precondition(
+                "custom unit amounts are not supported for plan changes",
+            ));
+        }
+
+        let currency = to_stripe_currency(&price.currency)?;
+        if currency != subscription.currency {
+            return Err(Status::failed_precondition(
+                "offer currency does not match subscription currency",
+            ));
+        }
+
+        let shop_uuid = parse_uuid(shop_id, "shop_id")?;
+        let tax_settings = ShopTaxSettings::get(&self.pool, &shop_uuid).await?;
+        let tax_behavior =
+            tax_settings.as_ref().map(|t| t.tax_behavior.as_str());
+        let tax_code = tax_settings
+            .as_ref()
+            .and_then(|t| Self::get_tax_code(t, found_offer.r#type()));
+        let product_id =
+            put_offer_product(stripe_client, &found_offer, tax_code).await?;
+
+        let mut plan_item = UpdateSubscriptionItems {
+            id: Some(item.id.to_string()),
+            quantity: Some(u64::from(quantity)),
+            ..Default::default()
+        };
+
+        let plan_amount = match price.billing_scheme() {
+            PriceBillingScheme::Tiered => {
+                let price_id = put_offer_tiered_price(
+                    stripe_client,
+                    &product_id,
+                    price,
+                    currency,
+                    tax_behavior.map(Self::get_stripe_price_tax_behavior),
+                )
+                .await?;
+                plan_item.price = Some(price_id.to_string());
+
+                Self::calculate_tiered_amount(
+                    price.tiers_mode(),
+                    &price.tiers,
+                    quantity,
+                )?
+            }
+            PriceBillingScheme::Unspecified | PriceBillingScheme::PerUnit => {
+                plan_item.price_data = Some(SubscriptionItemPriceData {
+                    currency,
+                    product: product_id.to_string(),
+                    recurring: SubscriptionItemPriceDataRecurring {
+                        interval: Self::get_plan_interval(
+                            recurring.interval(),
+                        )?,
+                        interval_count: Some(u64::from(
+                            recurring.interval_count,
+                        )),
+                    },
+                    tax_behavior: tax_behavior
+                        .map(Self::get_subscription_item_tax_behavior),
+                    unit_amount: Some(i64::from(price.unit_amount)),
+                    ..Default::default()
+                });
+
+                price.unit_amount.checked_mul(quantity).ok_or_else(|| {
+                    Status::invalid_argument("quantity is too large")
+                })?
+            }
+        };
+
+        let found_shop =
+            self.commerce_service.get_shop(shop_id, metadata).await?;
+        let fee_percent = Self::calculate_fee_percent(
+            plan_amount,
+            found_shop.platform_fee_percent,
+            found_shop.minimum_platform_fee_cent,
+            currency,
+        )?
+        .min(100.0);
+
+        Ok((plan_item, fee_percent))
+    }
+
+    fn get_tax_code(
+        tax_settings: &ShopTaxSettings,
+        offer_type: OfferType,
+    ) -> Option<&str> {
+        match offer_type {
+            OfferType::Unspecified => None,
+            OfferType::Physical => tax_settings.physical_tax_code.as_deref(),
+            OfferType::Digital => tax_settings.digital_tax_code.as_deref(),
+            OfferType::Service => tax_settings.service_tax_code.as_deref(),
+        }
+    }
+
+    fn get_price_tax_behavior(
+        tax_behavior: &str,
+    ) -> CreateCheckoutSessionLineItemsPriceDataTaxBehavior {
+        use CreateCheckoutSessionLineItemsPriceDataTaxBehavior::*;
+
+        match tax_behavior {
+            "inclusive" => Inclusive,
+            "exclusive" => Exclusive,
+            _ => Unspecified,
+        }
+    }
+
+    fn get_stripe_price_tax_behavior(tax_behavior: &str) -> PriceTaxBehavior {
+        match tax_behavior {
+            "inclusive" => PriceTaxBehavior::Inclusive,
+            "exclusive" => PriceTaxBehavior::Exclusive,
+            _ => PriceTaxBehavior::Unspecified,
+        }
+    }
+
+    fn get_shipping_tax_behavior(
+        tax_behavior: &str,
+    ) -> CreateCheckoutSessionShippingOptionsShippingRateDataTaxBehavior {
+        use CreateCheckoutSessionShippingOptionsShippingRateDataTaxBehavior::*;
+
+        match tax_behavior {
+            "inclusive" => Inclusive,
+            "exclusive" => Exclusive,
+            _ => Unspecified,
+        }
+    }
+
+    /// Resolves the shipping amount of a shipping option. Shipping is free if
+    /// the subtotal of the order before discounts reaches the free shipping
+    /// threshold.
+    ///
+    /// The rate of the shipping zone containing the destination country takes
+    /// precedence over domestic and international amounts. Destinations
+    /// outside of all shipping zones of the option use those amounts.
+    /// Returns `None` if the amount depends on the destination country, which
+    /// is not known.
+    fn get_shipping_amount(
+        shipping_option: &ShippingOption,
+        zone_rates: &[ShippingZoneRate],
+        shipping_zone_id: Option<Uuid>,
+        shipping_country: Option<&str>,
+        order_subtotal: u32,
+    ) -> Option<i64> {
+        if shipping_option
+            .free_shipping_threshold
+            .is_some_and(|t| i64::from(order_subtotal) >= t)
+        {
+            return Some(0);
+        }
+
+        let mut option_zone_rates = zone_rates
+            .iter()
+            .filter(|r| {
+                r.shipping_option_id == shipping_option.shipping_option_id
+            })
+            .peekable();
+
+        if option_zone_rates.peek().is_some() {
+            shipping_country?;
+            if let Some(zone_rate) = option_zone_rates
+                .find(|r| Some(r.shipping_zone_id) == shipping_zone_id)
+            {
+                return Some(zone_rate.amount);
+            }
+        }
+
+        match (
+            shipping_option.domestic_country.as_ref(),
+            shipping_option.international_amount,
+        ) {
+            (Some(domestic_country), Some(international_amount)) => {
+                shipping_country.map(|c| {
+                    if c.eq_ignore_ascii_case(domestic_country) {
+                        shipping_option.amount
+                    } else {
+                        international_amount
+                    }
+                })
+            }
+            _ => Some(shipping_option.amount),
+        }
+    }
+
+    fn get_delivery_estimate(
+        min_delivery_days: Option<i64>,
+        max_delivery_days: Option<i64>,
+    ) -> Option<
+        CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimate,
+    > {
+        if min_delivery_days.is_none() && max_delivery_days.is_none() {
+            return None;
+        }
+
+        Some(CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimate {
+            minimum: min_delivery_days.map(|value| {
+                CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMinimum {
+                    unit: CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMinimumUnit::BusinessDay,
+                    value,
+                }
+            }),
+            maximum: max_delivery_days.map(|value| {
+                CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMaximum {
+                    unit: CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimateMaximumUnit::BusinessDay,
+                    value,
+                }
+            }),
+        })
+    }
+
+    fn get_shipping_option(
+        display_name: String,
+        amount: i64,
+        currency: StripeCurrency,
+        delivery_estimate: Option<
+            CreateCheckoutSessionShippingOptionsShippingRateDataDeliveryEstimate,
+        >,
+        tax_behavior: Option<&str>,
+    ) -> CreateCheckoutSessionShippingOptions {
+        CreateCheckoutSessionShippingOptions {
+            shipping_rate_data: Some(CreateCheckoutSessionShippingOptionsShippingRateData {
+                type_: Some(CreateCheckoutSessionShippingOptionsShippingRateDataType::FixedAmount),
+                display_name,
+                delivery_estimate,
+                fixed_amount: Some(CreateCheckoutSessionShippingOptionsShippingRateDataFixedAmount {
+                    amount,
+                    currency,
+                    ..Default::default()
+                }),
+                tax_behavior: tax_behavior.map(Self::get_shipping_tax_behavior),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
     fn get_shipping_address_countries(
         all_countries: bool,
         countries: Vec<i32>,
@@ -170,6 +901,46 @@ impl StripeService {
             countries.iter().map(to_stripe_country).collect()
         }
     }
+
+    /// Free physical offers are not checked out with Stripe, so buyers
+    /// provide the shipping address with the request.
+    fn get_free_shipping_address(
+        shipping_address: Option<ShippingAddressRequest>,
+    ) -> Result<ShippingAddress, Status> {
+        let shipping_address = shipping_address.ok_or_else(|| {
+            Status::invalid_argument(
+                "field shipping_address is required for free physical offers",
+            )
+        })?;
+
+        let optional = |value: Option<String>| {
+            value.map(|v| v.trim().to_owned()).filter(|v| !v.is_empty())
+        };
+        let required = |value: Option<String>, field: &str| {
+            optional(value).ok_or_else(|| {
+                Status::invalid_argument(format!(
+                    "field shipping_address.{field} is required"
+                ))
+            })
+        };
+
+        let country = required(shipping_address.country, "country")?;
+        if parse_country_code(&country).is_none() {
+            return Err(Status::invalid_argument(
+                "field shipping_address.country is not a valid country code",
+            ));
+        }
+
+        Ok(ShippingAddress {
+            name: Some(required(shipping_address.name, "name")?),
+            line1: Some(required(shipping_address.line1, "line1")?),
+            line2: optional(shipping_address.line2),
+            postal_code: optional(shipping_address.postal_code),
+            city: Some(required(shipping_address.city, "city")?),
+            state: optional(shipping_address.state),
+            country: Some(country.to_uppercase()),
+        })
+    }
 }
 
 #[async_trait]
@@ -353,14 +1124,39 @@ impl stripe_service_server::StripeService for StripeService {
     ) -> Result<Response<CreateCheckoutSessionResponse>, Status> {
         let metadata = request.metadata().clone();
 
-        let user_id = get_user_id(&metadata, &self.verifier).await.ok();
+        let (user_id, email) =
+            match get_user_id_and_email(&metadata, &self.verifier).await {
+                Ok((user_id, email)) => (Some(user_id), email),
+                Err(_) => (None, None),
+            };
 
         let CreateCheckoutSessionRequest {
             offer_id,
             success_url,
             cancel_url,
+            allow_promotion_codes,
+            promotion_code,
+            shipping_country,
+            quantity,
+            buyer_email,
+            shipping_address,
         } = request.into_inner();
 
+        let quantity = quantity.unwrap_or(1);
+        if quantity == 0 {
+            return Err(Status::invalid_argument(
+                "field quantity must be greater than 0",
+            ));
+        }
+
+        let allow_promotion_codes = allow_promotion_codes.unwrap_or(false);
+
+        if allow_promotion_codes && promotion_code.is_some() {
+            return Err(Status::invalid_argument(
+                "allow_promotion_codes and promotion_code are exclusive",
+            ));
+        }
+
         let found_offer = self.commerce_service.get_offer(&offer_id).await?;
 
         let price = found_offer
@@ -368,56 +1164,573 @@ impl stripe_service_server::StripeService for StripeService {
             .as_ref()
             .ok_or_else(|| Status::internal("offer.price missing"))?;
 
-        let shop_uuid = parse_uuid(&found_offer.shop_id, "offer.shop_id")?;
+        let currency = to_stripe_currency(&price.currency)?;
+
+        // Buyers choose the amount of pay-what-you-want prices in Stripe
+        // Checkout, which supports custom unit amounts for one-time prices
+        // only. The amount is not known before the checkout session
+        // completes, so the platform fee is set when the payment is captured.
+        let buyer_chooses_amount = price.custom_unit_amount.is_some();
+        let unit_amount = match price.custom_unit_amount.as_ref() {
+            Some(_) if price.billing_scheme() == PriceBillingScheme::Tiered => {
+                return Err(Status::failed_precondition(
+                    "custom unit amounts are not supported for tiered prices",
+                ))
+            }
+            Some(_) if price.price_type() != PriceType::OneTime => {
+                return Err(Status::failed_precondition(
+                    "custom unit amounts are not supported for recurring prices",
+                ))
+            }
+            Some(c) => {
+                if quantity != 1 {
+                    return Err(Status::invalid_argument(
+                        "field quantity must be 1 for offers with a custom unit amount",
+                    ));
+                }
+                Self::get_lowest_custom_unit_amount(c, &currency.to_string())
+            }
+            None => price.unit_amount,
+        };
 
-        let stripe_account = StripeAccount::get(&self.pool, &shop_uuid)
-            .await
-            .map_err(|_| {
-                Status::not_found(format!("stripe account for '{}'", shop_uuid))
-            })?
-            .ok_or_else(|| {
-                Status::not_found(format!("stripe account for '{}'", shop_uuid))
-            })?;
+        let order_amount = match price.billing_scheme() {
+            PriceBillingScheme::Unspecified | PriceBillingScheme::PerUnit => {
+                unit_amount.checked_mul(quantity).ok_or_else(|| {
+                    Status::invalid_argument("quantity is too large")
+                })?
+            }
+            PriceBillingScheme::Tiered => Self::calculate_tiered_amount(
+                price.tiers_mode(),
+                &price.tiers,
+                quantity,
+            )?,
+        };
 
-        let found_shop = self
-            .commerce_service
-            .get_shop(&found_offer.shop_id, &metadata)
-            .await?;
+        let shop_uuid = parse_uuid(&found_offer.shop_id, "offer.shop_id")?;
+        let offer_uuid = parse_uuid(&found_offer.offer_id, "offer.offer_id")?;
+
+        // Buyers are identified by user_id or email address to limit the
+        // quantity they can purchase over all their purchases. Guests have
+        // to provide the email address they check out with.
+        let buyer_email = match email.clone() {
+            Some(email) => Some(email),
+            None => buyer_email
+                .map(|e| e.trim().to_owned())
+                .filter(|e| !e.is_empty()),
+        };
 
-        let stripe_account_id =
-            AccountId::from_str(&stripe_account.stripe_account_id)
-                .map_err(parse_id_error_to_status)?;
+        // Free purchases are recorded once per buyer and offer, repeated
+        // requests grant the purchase recorded before again
+        let setup_fee = price.recurring.as_ref().and_then(|r| r.setup_fee);
+        let is_free = order_amount == 0
+            && setup_fee.unwrap_or(0) == 0
+            && !buyer_chooses_amount;
+        if is_free && price.price_type() != PriceType::OneTime {
+            return Err(Status::failed_precondition(
+                "free recurring prices are not supported",
+            ));
+        }
+        let free_payment = if is_free {
+            Payment::get_free(
+                &self.pool,
+                &offer_uuid,
+                user_id.as_ref(),
+                buyer_email.as_deref(),
+            )
+            .await?
+        } else {
+            None
+        };
 
-        // Create checkout session request
-        let mut checkout_session = CreateCheckoutSession::new();
-        checkout_session.success_url = Some(&success_url);
-        checkout_session.cancel_url = Some(&cancel_url);
+        let offer_stock = OfferStock::get(&self.pool, &offer_uuid).await?;
+        if free_payment.is_none()
+            && offer_stock
+                .as_ref()
+                .is_some_and(|s| s.available() < i64::from(quantity))
+        {
+            return Err(Status::failed_precondition("offer is out of stock"));
+        }
 
-        // Add shop_id and offer_id to metadata of stripe checkout session
-        // this is used in stripe webhook handler to assign offers to payments
-        let mut metadata = HashMap::from([
-            (Self::metadata_key_shop_id(), shop_uuid.to_string()),
-            (
-                Self::metadata_key_offer_id(),
-                found_offer.offer_id.to_string(),
-            ),
-        ]);
+        let offer_settings =
+            OfferSettings::get(&self.pool, &offer_uuid).await?;
 
-        match price.price_type() {
-            PriceType::Unspecified => {
-                return Err(Status::internal("price_type unspecified"))
-            }
-            PriceType::OneTime => {
-                checkout_session.mode = Some(CheckoutSessionMode::Payment);
-                checkout_session.payment_intent_data =
+        let remaining_per_buyer = match offer_settings
+            .as_ref()
+            .and_then(|s| s.max_quantity_per_buyer)
+        {
+            Some(max_quantity_per_buyer) => {
+                if user_id.is_none() && buyer_email.is_none() {
+                    return Err(Status::invalid_argument(
+                        "field buyer_email is required for offers with a purchase limit per buyer",
+                    ));
+                }
+                let purchased = Payment::sum_purchased_quantity(
+                    &self.pool,
+                    &offer_uuid,
+                    user_id.as_ref(),
+                    buyer_email.as_deref(),
+                )
+                .await?;
+                let reserved = PurchaseReservation::sum_reserved_quantity(
+                    &self.pool,
+                    &offer_uuid,
+                    user_id.as_ref(),
+                    buyer_email.as_deref(),
+                )
+                .await?;
+                Some(max_quantity_per_buyer - purchased - reserved)
+            }
+            None => None,
+        };
+        let purchase_limit = Self::get_maximum_quantity(&[
+            offer_settings
+                .as_ref()
+                .and_then(|s| s.max_quantity_per_session),
+            remaining_per_buyer,
+        ]);
+        if let (Some(purchase_limit), None) =
+            (purchase_limit, free_payment.as_ref())
+        {
+            if purchase_limit < 1 {
+                return Err(Status::failed_precondition(
+                    "purchase limit of offer reached",
+                ));
+            }
+            if i64::from(quantity) > purchase_limit {
+                return Err(Status::failed_precondition(format!(
+                    "at most {purchase_limit} of offer can be purchased"
+                )));
+            }
+        }
+
+        if free_payment.is_none() && found_offer.r#type() == OfferType::Digital
+        {
+            LicenseKeyService::check_license_key_available(
+                &self.pool,
+                &offer_uuid,
+            )
+            .await?;
+        }
+
+        // Free offers bypass Stripe, the purchase is recorded directly
+        if is_free {
+            if found_offer.r#type() == OfferType::Digital && user_id.is_none() {
+                return Err(Status::unauthenticated(""));
+            }
+            if found_offer.r#type() == OfferType::Physical
+                && buyer_email.is_none()
+            {
+                return Err(Status::invalid_argument(
+                    "field buyer_email is required for free physical offers",
+                ));
+            }
+
+            let shipping_address =
+                if found_offer.r#type() == OfferType::Physical {
+                    let shipping_address =
+                        Self::get_free_shipping_address(shipping_address)?;
+                    if let Some(shipping_rate) =
+                        self.commerce_service.get_shipping_rate(&offer_id).await
+                    {
+                        let country = shipping_address
+                            .country
+                            .as_deref()
+                            .and_then(parse_country_code)
+                            .ok_or_else(|| {
+                                Status::internal("shipping_address.country")
+                            })?;
+                        if !Self::get_shipping_address_countries(
+                            shipping_rate.all_countries,
+                            shipping_rate.specific_countries,
+                        )
+                        .contains(&to_stripe_country(&country))
+                        {
+                            return Err(Status::failed_precondition(
+                            "offer is not shipped to shipping_address.country",
+                        ));
+                        }
+                    }
+                    Some(shipping_address)
+                } else {
+                    None
+                };
+
+            let payment = match free_payment {
+                Some(payment) => payment,
+                None => {
+                    if offer_stock.is_some()
+                        && OfferStock::take(
+                            &self.pool,
+                            &offer_uuid,
+                            quantity.into(),
+                        )
+                        .await?
+                        .is_none()
+                    {
+                        return Err(Status::failed_precondition(
+                            "offer is out of stock",
+                        ));
+                    }
+
+                    let created = Payment::create(
+                        &self.pool,
+                        &shop_uuid,
+                        &offer_uuid,
+                        user_id.clone(),
+                        None,
+                        None,
+                        None,
+                        None,
+                        CheckoutSessionPaymentStatus::NoPaymentRequired
+                            .as_str(),
+                        &currency.to_string(),
+                        &PaymentAmounts {
+                            subtotal: 0,
+                            discount: 0,
+                            shipping: 0,
+                            tax: 0,
+                            total: 0,
+                        },
+                        None,
+                        None,
+                        buyer_email.clone(),
+                        quantity.into(),
+                    )
+                    .await?;
+
+                    match created {
+                        Some(payment) => payment,
+                        // A concurrent request recorded the free purchase
+                        // first, the stock taken for this one is returned
+                        None => {
+                            if offer_stock.is_some() {
+                                OfferStock::restock(
+                                    &self.pool,
+                                    &offer_uuid,
+                                    quantity.into(),
+                                )
+                                .await?;
+                            }
+                            Payment::get_free(
+                                &self.pool,
+                                &offer_uuid,
+                                user_id.as_ref(),
+                                buyer_email.as_deref(),
+                            )
+                            .await?
+                            .ok_or_else(|| Status::internal("payment"))?
+                        }
+                    }
+                }
+            };
+
+            if found_offer.r#type() == OfferType::Digital {
+                if let Some(user_id) = user_id.as_ref() {
+                    Entitlement::create(
+                        &self.pool,
+                        user_id,
+                        &offer_uuid,
+                        &shop_uuid,
+                        &payment.payment_id,
+                        None,
+                        None,
+                    )
+                    .await?;
+                }
+
+                LicenseKeyService::assign_license_key(
+                    &self.pool,
+                    &offer_uuid,
+                    &shop_uuid,
+                    &payment.payment_id,
+                    user_id,
+                )
+                .await?;
+            }
+
+            if let Some(shipping_address) = shipping_address {
+                OrderService::create_order(
+                    &self.pool,
+                    &payment.payment_id,
+                    &shop_uuid,
+                    &offer_uuid,
+                    shipping_address,
+                )
+                .await?;
+            }
+
+            return Ok(Response::new(CreateCheckoutSessionResponse {
+                link: success_url,
+            }));
+        }
+
+        check_minimum_charge_amount(order_amount, &currency.to_string())?;
+
+        let stripe_account = StripeAccount::get(&self.pool, &shop_uuid)
+            .await
+            .map_err(|_| {
+                Status::not_found(format!("stripe account for '{}'", shop_uuid))
+            })?
+            .ok_or_else(|| {
+                Status::not_found(format!("stripe account for '{}'", shop_uuid))
+            })?;
+
+        let found_shop = self
+            .commerce_service
+            .get_shop(&found_offer.shop_id, &metadata)
+            .await?;
+
+        let stripe_account_id =
+            AccountId::from_str(&stripe_account.stripe_account_id)
+                .map_err(parse_id_error_to_status)?;
+
+        let stripe_client = self.stripe_client.clone();
+        let stripe_client =
+            stripe_client.with_stripe_account(stripe_account_id);
+
+        let tax_settings = ShopTaxSettings::get(&self.pool, &shop_uuid).await?;
+        let tax_behavior =
+            tax_settings.as_ref().map(|t| t.tax_behavior.as_str());
+
+        // Create checkout session request
+        let mut checkout_session = CreateCheckoutSession::new();
+        checkout_session.success_url = Some(&success_url);
+        checkout_session.cancel_url = Some(&cancel_url);
+
+        // Expired checkout sessions can be recovered. Buyers who consent to
+        // promotional emails can be reminded of their abandoned checkout.
+        // Recovered checkout sessions are created by Stripe without the
+        // checks above, so offers with stock, purchase limits or a single
+        // subscription per buyer are not recovered.
+        let recovery_enabled = offer_stock.is_none()
+            && purchase_limit.is_none()
+            && !(price.price_type() == PriceType::Recurring
+                && found_offer.r#type() == OfferType::Digital
+                && !offer_settings
+                    .as_ref()
+                    .is_some_and(|s| s.allow_multiple_subscriptions));
+        if recovery_enabled {
+            checkout_session.after_expiration =
+                Some(CreateCheckoutSessionAfterExpiration {
+                    recovery: Some(
+                        CreateCheckoutSessionAfterExpirationRecovery {
+                            allow_promotion_codes: Some(allow_promotion_codes),
+                            enabled: true,
+                        },
+                    ),
+                });
+        }
+        if recovery_enabled && self.collect_promotions_consent {
+            checkout_session.consent_collection =
+                Some(CreateCheckoutSessionConsentCollection {
+                    promotions: Some(
+                        CreateCheckoutSessionConsentCollectionPromotions::Auto,
+                    ),
+                    ..Default::default()
+                });
+        }
+
+        // Authenticated buyers check out as the same customer on every
+        // purchase from the shop, so their details are prefilled
+        if let Some(user_id) = user_id.as_ref() {
+            checkout_session.customer = Some(
+                self.get_or_create_customer(
+                    &stripe_client,
+                    &stripe_account.stripe_account_id,
+                    user_id,
+                    email.as_deref(),
+                )
+                .await?,
+            );
+            checkout_session.customer_update =
+                Some(CreateCheckoutSessionCustomerUpdate {
+                    address: Some(
+                        CreateCheckoutSessionCustomerUpdateAddress::Auto,
+                    ),
+                    name: Some(CreateCheckoutSessionCustomerUpdateName::Auto),
+                    shipping: Some(
+                        CreateCheckoutSessionCustomerUpdateShipping::Auto,
+                    ),
+                });
+        } else if remaining_per_buyer.is_some() {
+            // Guests can not change the email address their purchase limit
+            // was checked with
+            checkout_session.customer_email = buyer_email.as_deref();
+        }
+
+        // Let Stripe calculate taxes based on the buyer's address and
+        // optionally collect tax IDs of business customers
+        if let Some(tax_settings) = tax_settings.as_ref() {
+            if tax_settings.automatic_tax_enabled {
+                checkout_session.automatic_tax =
+                    Some(CreateCheckoutSessionAutomaticTax {
+                        enabled: true,
+                        ..Default::default()
+                    });
+            }
+            if tax_settings.tax_id_collection_enabled {
+                checkout_session.tax_id_collection =
+                    Some(CreateCheckoutSessionTaxIdCollection {
+                        enabled: true,
+                    });
+            }
+        }
+
+        // The platform fee is calculated on the amount the buyer pays after
+        // discounts. Promotion codes entered by the buyer are not known yet,
+        // so the fee is calculated on the undiscounted amount and capped at
+        // the lowest amount the buyer can pay with a promotion code.
+        let (fee_base_amount, max_fee_amount) = if let Some(code) =
+            promotion_code
+        {
+            let found_promotion_code =
+                Self::list_active_promotion_codes(&stripe_client, Some(&code))
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| Status::not_found("promotion_code"))?;
+
+            if !Self::coupon_applies_to_offer(
+                &found_promotion_code.coupon,
+                &found_offer.offer_id,
+            ) {
+                return Err(Status::failed_precondition(
+                    "promotion_code does not apply to offer",
+                ));
+            }
+
+            checkout_session.discounts =
+                Some(vec![CreateCheckoutSessionDiscounts {
+                    promotion_code: Some(found_promotion_code.id.to_string()),
+                    ..Default::default()
+                }]);
+
+            let discounted_amount = Self::calculate_discounted_amount(
+                order_amount,
+                &found_promotion_code.coupon,
+            );
+            (discounted_amount, discounted_amount)
+        } else if allow_promotion_codes {
+            checkout_session.allow_promotion_codes = Some(true);
+
+            let lowest_amount =
+                Self::list_active_promotion_codes(&stripe_client, None)
+                    .await?
+                    .iter()
+                    .filter(|p| {
+                        Self::coupon_applies_to_offer(
+                            &p.coupon,
+                            &found_offer.offer_id,
+                        )
+                    })
+                    .map(|p| {
+                        Self::calculate_discounted_amount(
+                            order_amount,
+                            &p.coupon,
+                        )
+                    })
+                    .min()
+                    .unwrap_or(order_amount);
+            (order_amount, lowest_amount)
+        } else {
+            (order_amount, order_amount)
+        };
+
+        // Add shop_id and offer_id to metadata of stripe checkout session
+        // this is used in stripe webhook handler to assign offers to payments
+        let mut metadata = HashMap::from([
+            (Self::metadata_key_shop_id(), shop_uuid.to_string()),
+            (
+                Self::metadata_key_offer_id(),
+                found_offer.offer_id.to_string(),
+            ),
+            (
+                Self::metadata_key_offer_type(),
+                found_offer.r#type().as_str_name().to_string(),
+            ),
+        ]);
+
+        match price.price_type() {
+            PriceType::Unspecified => {
+                return Err(Status::internal("price_type unspecified"))
+            }
+            PriceType::OneTime => {
+                // Digital and physical purchases of authenticated buyers are
+                // assigned to the buyer, who is granted an entitlement for
+                // digital offers and can follow orders of physical offers
+                if matches!(
+                    found_offer.r#type(),
+                    OfferType::Digital | OfferType::Physical
+                ) {
+                    if let Some(user_id) = user_id.clone() {
+                        metadata.insert(Self::metadata_key_user_id(), user_id);
+                    }
+                }
+
+                checkout_session.mode = Some(CheckoutSessionMode::Payment);
+                // Tax IDs are stored on the customer, which is not created
+                // by default in payment mode
+                if checkout_session.tax_id_collection.is_some()
+                    && checkout_session.customer.is_none()
+                {
+                    checkout_session.customer_creation =
+                        Some(CheckoutSessionCustomerCreation::Always);
+                }
+
+                // Service offers can be authorized at booking and captured
+                // once the seller accepts or delivers
+                let manual_capture = found_offer.r#type() == OfferType::Service
+                    && offer_settings
+                        .as_ref()
+                        .is_some_and(|s| s.manual_capture);
+
+                // Amounts chosen by the buyer are authorized and captured
+                // with the platform fee of the amount paid once the checkout
+                // session completes
+                let application_fee_amount = if buyer_chooses_amount {
+                    if manual_capture {
+                        return Err(Status::failed_precondition(
+                            "custom unit amounts are not supported for offers with manual capture",
+                        ));
+                    }
+                    let minimum_fee = minimum_fee_amount(
+                        found_shop.minimum_platform_fee_cent,
+                        &currency.to_string(),
+                    )?;
+                    if minimum_fee >= order_amount {
+                        return Err(Status::failed_precondition(
+                            "minimum of the custom unit amount must exceed the minimum platform fee",
+                        ));
+                    }
+                    metadata.insert(
+                        Self::metadata_key_platform_fee_percent(),
+                        found_shop.platform_fee_percent.to_string(),
+                    );
+                    metadata.insert(
+                        Self::metadata_key_minimum_platform_fee(),
+                        minimum_fee.to_string(),
+                    );
+                    None
+                } else {
+                    Some(
+                        Self::calculate_fee_amount(
+                            fee_base_amount,
+                            found_shop.platform_fee_percent,
+                            found_shop.minimum_platform_fee_cent,
+                            currency,
+                        )?
+                        .min(i64::from(max_fee_amount)),
+                    )
+                };
+
+                checkout_session.payment_intent_data =
                     Some(CreateCheckoutSessionPaymentIntentData {
-                        application_fee_amount: Some(
-                            Self::calculate_fee_amount(
-                                price.unit_amount,
-                                found_shop.platform_fee_percent,
-                                found_shop.minimum_platform_fee_cent,
+                        application_fee_amount,
+                        capture_method: (manual_capture
+                            || buyer_chooses_amount)
+                            .then_some(
+                                CreateCheckoutSessionPaymentIntentDataCaptureMethod::Manual,
                             ),
-                        ),
                         ..Default::default()
                     });
 
@@ -425,34 +1738,96 @@ impl stripe_service_server::StripeService for StripeService {
                 let found_shipping_rate =
                     self.commerce_service.get_shipping_rate(&offer_id).await;
 
-                checkout_session.shipping_address_collection =
-                    found_shipping_rate.to_owned().map(|s| {
-                        CreateCheckoutSessionShippingAddressCollection {
-                            allowed_countries:
-                                Self::get_shipping_address_countries(
-                                    s.all_countries,
-                                    s.specific_countries,
-                                ),
+                if let Some(shipping_rate) = found_shipping_rate {
+                    let mut allowed_countries =
+                        Self::get_shipping_address_countries(
+                            shipping_rate.all_countries,
+                            shipping_rate.specific_countries.to_owned(),
+                        );
+
+                    // Restrict the shipping address to the destination
+                    // country the shipping prices were resolved for
+                    if let Some(shipping_country) = shipping_country.as_ref() {
+                        let country = to_stripe_country(
+                            &parse_country_code(shipping_country).ok_or_else(
+                                || {
+                                    Status::invalid_argument(
+                                        "field shipping_country is not a valid country code",
+                                    )
+                                },
+                            )?,
+                        );
+                        if !allowed_countries.contains(&country) {
+                            return Err(Status::failed_precondition(
+                                "offer is not shipped to shipping_country",
+                            ));
                         }
-                    });
+                        allowed_countries = vec![country];
+                    }
 
-                // Add shipping rate to checkout session
-                if let Some(shipping_rate) = found_shipping_rate {
-                    checkout_session.shipping_options = Some(vec![
-                        CreateCheckoutSessionShippingOptions {
-                            shipping_rate_data: Some(CreateCheckoutSessionShippingOptionsShippingRateData {
-                                type_: Some(CreateCheckoutSessionShippingOptionsShippingRateDataType::FixedAmount),
-                                display_name: Self::shipping_rate_key(),
-                                fixed_amount: Some(CreateCheckoutSessionShippingOptionsShippingRateDataFixedAmount {
-                                    amount: shipping_rate.amount.into(),
-                                    currency: Self::get_currency(shipping_rate.currency)?,
-                                    ..Default::default()
-                                }),
-                                ..Default::default()
-                            }),
-                            ..Default::default()
+                    checkout_session.shipping_address_collection =
+                        Some(CreateCheckoutSessionShippingAddressCollection {
+                            allowed_countries,
+                        });
+
+                    // Add shipping options of the shop or fall back to the
+                    // shipping rate of the offer. Options priced by
+                    // destination are left out if buyers did not choose a
+                    // shipping country before checkout, their address is
+                    // collected in Stripe Checkout.
+                    let zone_rates =
+                        ShippingZoneRate::list(&self.pool, &shop_uuid).await?;
+                    let shipping_zone_id = match shipping_country.as_ref() {
+                        Some(shipping_country) if !zone_rates.is_empty() => {
+                            ShippingZone::get_id_for_country(
+                                &self.pool,
+                                &shop_uuid,
+                                shipping_country,
+                            )
+                            .await?
+                        }
+                        _ => None,
+                    };
+
+                    let mut shipping_options = Vec::new();
+                    for shipping_option in
+                        ShippingOption::list(&self.pool, &shop_uuid).await?
+                    {
+                        if shipping_option.currency != currency.to_string() {
+                            continue;
                         }
-                    ]);
+                        let Some(shipping_amount) = Self::get_shipping_amount(
+                            &shipping_option,
+                            &zone_rates,
+                            shipping_zone_id,
+                            shipping_country.as_deref(),
+                            order_amount,
+                        ) else {
+                            continue;
+                        };
+                        shipping_options.push(Self::get_shipping_option(
+                            shipping_option.display_name.to_owned(),
+                            shipping_amount,
+                            currency,
+                            Self::get_delivery_estimate(
+                                shipping_option.min_delivery_days,
+                                shipping_option.max_delivery_days,
+                            ),
+                            tax_behavior,
+                        ));
+                    }
+
+                    if shipping_options.is_empty() {
+                        shipping_options.push(Self::get_shipping_option(
+                            Self::shipping_rate_key(),
+                            shipping_rate.amount.into(),
+                            to_stripe_currency(&shipping_rate.currency)?,
+                            None,
+                            tax_behavior,
+                        ));
+                    }
+
+                    checkout_session.shipping_options = Some(shipping_options);
                 }
             }
             PriceType::Recurring => {
@@ -460,29 +1835,132 @@ impl stripe_service_server::StripeService for StripeService {
                 // in order to assing ownership of the subscription to the buyer.
                 // In other cases customers should be able buy without authentication.
                 if found_offer.r#type() == OfferType::Digital {
-                    if let Some(user_id) = user_id {
-                        metadata.insert(Self::metadata_key_user_id(), user_id);
-                    } else {
+                    let Some(user_id) = user_id.clone() else {
                         return Err(Status::unauthenticated(""));
+                    };
+
+                    // Buyers subscribe to digital offers once, unless the
+                    // seller allows multiple subscriptions
+                    if !offer_settings
+                        .as_ref()
+                        .is_some_and(|s| s.allow_multiple_subscriptions)
+                    {
+                        if self
+                            .find_active_subscription(&offer_uuid, &user_id)
+                            .await?
+                            .is_some()
+                        {
+                            return Err(Status::already_exists(
+                                "subscription already exists",
+                            ));
+                        }
+
+                        // Buyers of digital subscriptions are signed in and
+                        // always check out as their customer, so their open
+                        // checkout sessions of the offer can be found
+                        let customer_id = checkout_session
+                            .customer
+                            .as_ref()
+                            .ok_or_else(|| {
+                                Status::internal("checkout_session.customer")
+                            })?;
+                        Self::expire_open_checkout_sessions(
+                            &stripe_client,
+                            customer_id,
+                            &offer_uuid,
+                        )
+                        .await?;
                     }
+
+                    metadata.insert(Self::metadata_key_user_id(), user_id);
                 }
 
+                // Subscriptions may be switched to other prices in the
+                // customer portal, the platform fee is calculated again from
+                // the metadata of the subscription
+                let subscription_metadata = HashMap::from([
+                    (
+                        Self::metadata_key_offer_id(),
+                        found_offer.offer_id.to_owned(),
+                    ),
+                    (
+                        Self::metadata_key_platform_fee_percent(),
+                        found_shop.platform_fee_percent.to_string(),
+                    ),
+                    (
+                        Self::metadata_key_minimum_platform_fee(),
+                        minimum_fee_amount(
+                            found_shop.minimum_platform_fee_cent,
+                            &currency.to_string(),
+                        )?
+                        .to_string(),
+                    ),
+                ]);
+
                 checkout_session.mode = Some(CheckoutSessionMode::Subscription);
                 checkout_session.subscription_data =
                     Some(CreateCheckoutSessionSubscriptionData {
                         application_fee_percent: Some(
-                            Self::calculate_fee_percent(
-                                price.unit_amount,
+                            Self::calculate_subscription_fee_percent(
+                                fee_base_amount,
+                                setup_fee,
                                 found_shop.platform_fee_percent,
                                 found_shop.minimum_platform_fee_cent,
-                            ),
+                                currency,
+                            )?,
                         ),
+                        metadata: Some(subscription_metadata),
                         trial_period_days: price
                             .recurring
                             .as_ref()
                             .and_then(|r| r.trial_period_days),
                         ..Default::default()
                     });
+
+                // Trials may start without a payment method, in which case
+                // the subscription is canceled at the end of the trial if
+                // the buyer has not added one
+                if price
+                    .recurring
+                    .as_ref()
+                    .and_then(|r| r.trial_period_days)
+                    .is_some_and(|d| d > 0)
+                    && offer_settings
+                        .as_ref()
+                        .is_some_and(|s| s.trial_without_payment_method)
+                {
+                    checkout_session.payment_method_collection = Some(
+                        CheckoutSessionPaymentMethodCollection::IfRequired,
+                    );
+                    if let Some(subscription_data) =
+                        checkout_session.subscription_data.as_mut()
+                    {
+                        subscription_data.trial_settings = Some(
+                            CreateCheckoutSessionSubscriptionDataTrialSettings {
+                                end_behavior: CreateCheckoutSessionSubscriptionDataTrialSettingsEndBehavior {
+                                    missing_payment_method: CreateCheckoutSessionSubscriptionDataTrialSettingsEndBehaviorMissingPaymentMethod::Cancel,
+                                },
+                            },
+                        );
+                    }
+                }
+
+                // Installment plans and fixed-term subscriptions end after a
+                // number of billing cycles. Checkout cannot end subscriptions,
+                // so the end is set once the subscription was created.
+                if let Some(billing_cycles) =
+                    price.recurring.as_ref().and_then(|r| r.billing_cycles)
+                {
+                    if billing_cycles == 0 {
+                        return Err(Status::failed_precondition(
+                            "offer.price.recurring.billing_cycles must be greater than 0",
+                        ));
+                    }
+                    metadata.insert(
+                        Self::metadata_key_billing_cycles(),
+                        billing_cycles.to_string(),
+                    );
+                }
             }
         }
 
@@ -490,64 +1968,196 @@ impl stripe_service_server::StripeService for StripeService {
         checkout_session.metadata = Some(metadata);
 
         // Add line items to checkout session
-        let product = CreateCheckoutSessionLineItemsPriceDataProductData {
-            name: found_offer.name,
-            description: (!found_offer.description.is_empty())
-                .then_some(found_offer.description),
-            images: (!found_offer.images.is_empty()).then_some(
-                found_offer
-                    .images
-                    .into_iter()
-                    .map(|i| i.image_url)
-                    .collect(),
-            ),
-            ..Default::default()
-        };
-
-        let recurring = match price.recurring.as_ref() {
-            Some(r) => Some(CreateCheckoutSessionLineItemsPriceDataRecurring {
-                interval: Self::get_recurring_interval(r.interval())?,
-                interval_count: Some(u64::from(r.interval_count)),
-            }),
-            None => None,
-        };
+        let tax_code = tax_settings
+            .as_ref()
+            .and_then(|t| Self::get_tax_code(t, found_offer.r#type()));
+        let product_id =
+            put_offer_product(&stripe_client, &found_offer, tax_code).await?;
+
+        let mut line_items = vec![match price.billing_scheme() {
+            // One-time pay-what-you-want prices are created as Stripe prices
+            // with a custom unit amount, which requires a quantity of 1
+            _ if buyer_chooses_amount => {
+                let price_id = put_offer_custom_unit_amount_price(
+                    &stripe_client,
+                    &product_id,
+                    price,
+                    currency,
+                    tax_behavior.map(Self::get_stripe_price_tax_behavior),
+                )
+                .await?;
 
-        let price_data = CreateCheckoutSessionLineItemsPriceData {
-            currency: Self::get_currency(price.currency)?,
-            product_data: Some(product),
-            unit_amount: Some(i64::from(price.unit_amount)),
-            recurring,
-            ..Default::default()
-        };
+                CreateCheckoutSessionLineItems {
+                    quantity: Some(1),
+                    price: Some(price_id.to_string()),
+                    ..Default::default()
+                }
+            }
+            // Tiered prices are created as Stripe prices. The quantity is
+            // fixed, because the platform fee depends on the tier-resolved
+            // total of the requested quantity.
+            PriceBillingScheme::Tiered => {
+                let price_id = put_offer_tiered_price(
+                    &stripe_client,
+                    &product_id,
+                    price,
+                    currency,
+                    tax_behavior.map(Self::get_stripe_price_tax_behavior),
+                )
+                .await?;
 
-        let adjustable_quantity =
-            CreateCheckoutSessionLineItemsAdjustableQuantity {
-                enabled: true,
-                minimum: Some(1),
-                ..Default::default()
-            };
+                CreateCheckoutSessionLineItems {
+                    quantity: Some(u64::from(quantity)),
+                    price: Some(price_id.to_string()),
+                    ..Default::default()
+                }
+            }
+            PriceBillingScheme::Unspecified | PriceBillingScheme::PerUnit => {
+                let recurring = match price.recurring.as_ref() {
+                    Some(r) => {
+                        Some(CreateCheckoutSessionLineItemsPriceDataRecurring {
+                            interval: Self::get_recurring_interval(
+                                r.interval(),
+                            )?,
+                            interval_count: Some(u64::from(r.interval_count)),
+                        })
+                    }
+                    None => None,
+                };
+
+                let price_data = CreateCheckoutSessionLineItemsPriceData {
+                    currency,
+                    product: Some(product_id.to_string()),
+                    unit_amount: Some(i64::from(unit_amount)),
+                    recurring,
+                    tax_behavior: tax_behavior
+                        .map(Self::get_price_tax_behavior),
+                    ..Default::default()
+                };
+
+                // Buyers can not check out more than the purchase limit. Stock
+                // is reserved for the requested quantity only, so the
+                // quantity of offers with stock or a purchase limit per buyer
+                // can not be increased. The platform fee of one-time prices
+                // is a fixed amount for the requested quantity, so only
+                // subscriptions, charged with a fee percentage, have an
+                // adjustable quantity.
+                let adjustable_quantity =
+                    CreateCheckoutSessionLineItemsAdjustableQuantity {
+                        enabled: true,
+                        minimum: Some(1),
+                        maximum: Self::get_maximum_quantity(&[
+                            offer_stock.as_ref().map(|_| i64::from(quantity)),
+                            purchase_limit,
+                        ])
+                        .map(|m| m.min(Self::MAX_ADJUSTABLE_QUANTITY)),
+                    };
+
+                CreateCheckoutSessionLineItems {
+                    quantity: Some(u64::from(quantity)),
+                    adjustable_quantity: (price.price_type()
+                        == PriceType::Recurring
+                        && remaining_per_buyer.is_none())
+                    .then_some(adjustable_quantity),
+                    price_data: Some(price_data),
+                    ..Default::default()
+                }
+            }
+        }];
 
-        checkout_session.line_items =
-            Some(vec![CreateCheckoutSessionLineItems {
+        // Charge the setup fee of recurring prices once with the first invoice
+        if let Some(setup_fee) = price
+            .recurring
+            .as_ref()
+            .and_then(|r| r.setup_fee)
+            .filter(|f| *f > 0)
+        {
+            line_items.push(CreateCheckoutSessionLineItems {
                 quantity: Some(1),
-                adjustable_quantity: Some(adjustable_quantity),
-                price_data: Some(price_data),
+                price_data: Some(CreateCheckoutSessionLineItemsPriceData {
+                    currency,
+                    product_data: Some(
+                        CreateCheckoutSessionLineItemsPriceDataProductData {
+                            name: format!("{} - Setup fee", found_offer.name),
+                            tax_code: tax_code.map(String::from),
+                            ..Default::default()
+                        },
+                    ),
+                    unit_amount: Some(i64::from(setup_fee)),
+                    tax_behavior: tax_behavior
+                        .map(Self::get_price_tax_behavior),
+                    ..Default::default()
+                }),
                 ..Default::default()
-            }]);
+            });
+        }
 
-        let stripe_client = self.stripe_client.clone();
+        checkout_session.line_items = Some(line_items);
 
-        let link = CheckoutSession::create(
-            &stripe_client.with_stripe_account(stripe_account_id),
-            checkout_session,
-        )
-        .await
-        .map_err(|err| {
-            tracing::log::error!("{err}");
-            Status::internal("")
-        })?
-        .url
-        .ok_or_else(|| Status::internal(""))?;
+        // Stock and the purchase limit per buyer are reserved until the
+        // checkout session completes or expires
+        let reservation_expires_at =
+            Utc::now() + Duration::minutes(Self::STOCK_RESERVATION_MINUTES);
+        if remaining_per_buyer.is_some() {
+            checkout_session.expires_at =
+                Some(reservation_expires_at.timestamp());
+        }
+        if offer_stock.is_some() {
+            if OfferStock::reserve(&self.pool, &offer_uuid, quantity.into())
+                .await?
+                .is_none()
+            {
+                return Err(Status::failed_precondition(
+                    "offer is out of stock",
+                ));
+            }
+            checkout_session.expires_at =
+                Some(reservation_expires_at.timestamp());
+        }
+
+        let created_session =
+            match CheckoutSession::create(&stripe_client, checkout_session)
+                .await
+            {
+                Ok(created_session) => created_session,
+                Err(err) => {
+                    tracing::log::error!("{err}");
+                    if offer_stock.is_some() {
+                        OfferStock::release(
+                            &self.pool,
+                            &offer_uuid,
+                            quantity.into(),
+                        )
+                        .await?;
+                    }
+                    return Err(Status::internal(""));
+                }
+            };
+
+        if offer_stock.is_some() {
+            OfferStock::create_reservation(
+                &self.pool,
+                &created_session.id.to_string(),
+                &offer_uuid,
+                quantity.into(),
+                reservation_expires_at,
+            )
+            .await?;
+        }
+        if remaining_per_buyer.is_some() {
+            PurchaseReservation::create(
+                &self.pool,
+                &created_session.id.to_string(),
+                &offer_uuid,
+                user_id.as_ref(),
+                buyer_email.as_deref(),
+                quantity.into(),
+                reservation_expires_at,
+            )
+            .await?;
+        }
+
+        let link = created_session.url.ok_or_else(|| Status::internal(""))?;
 
         Ok(Response::new(CreateCheckoutSessionResponse { link }))
     }
@@ -561,8 +2171,32 @@ impl stripe_service_server::StripeService for StripeService {
         let CancelSubscriptionRequest {
             stripe_subscription_id,
             shop_id,
+            cancellation_mode,
+            refund_policy,
+            reason,
         } = request.into_inner();
 
+        let cancellation_mode =
+            CancellationMode::try_from(cancellation_mode).unwrap_or_default();
+        let refund_policy =
+            RefundPolicy::try_from(refund_policy).unwrap_or_default();
+
+        if cancellation_mode != CancellationMode::Immediately
+            && !matches!(
+                refund_policy,
+                RefundPolicy::Unspecified | RefundPolicy::None
+            )
+        {
+            return Err(Status::invalid_argument(
+                "refunds require cancellation_mode immediately",
+            ));
+        }
+        if reason.as_ref().is_some_and(|r| r.len() > 500) {
+            return Err(Status::invalid_argument(
+                "field reason must not be longer than 500 characters",
+            ));
+        }
+
         let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
 
         let stripe_account = StripeAccount::get(&self.pool, &shop_uuid)
@@ -581,9 +2215,180 @@ impl stripe_service_server::StripeService for StripeService {
         let stripe_client =
             stripe_client.with_stripe_account(stripe_account_id);
 
+        let mut amount_refunded = 0;
+
+        match cancellation_mode {
+            CancellationMode::Unspecified | CancellationMode::AtPeriodEnd => {
+                let mut update_subscription = UpdateSubscription::new();
+
+                update_subscription.cancel_at_period_end = Some(true);
+                update_subscription.cancellation_details =
+                    Some(UpdateSubscriptionCancellationDetails {
+                        comment: reason.clone(),
+                        ..Default::default()
+                    });
+
+                StripeSubscription::update(
+                    &stripe_client,
+                    &subscription_id,
+                    update_subscription,
+                )
+                .await
+                .map_err(|err| {
+                    tracing::log::error!("{err}");
+                    Status::internal("")
+                })?;
+            }
+            CancellationMode::Immediately => {
+                let subscription = StripeSubscription::retrieve(
+                    &stripe_client,
+                    &subscription_id,
+                    &["latest_invoice"],
+                )
+                .await
+                .map_err(stripe_error_to_status)?;
+
+                // Cancellations are retried if a later step failed, so a
+                // subscription canceled before is not canceled again
+                let canceled_at = match subscription.canceled_at {
+                    Some(canceled_at)
+                        if subscription.status
+                            == SubscriptionStatus::Canceled =>
+                    {
+                        canceled_at
+                    }
+                    _ => StripeSubscription::cancel(
+                        &stripe_client,
+                        &subscription_id,
+                        CancelSubscription {
+                            cancellation_details: Some(CancellationDetails {
+                                comment: reason.clone(),
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .map_err(stripe_error_to_status)?
+                    .canceled_at
+                    .unwrap_or_else(|| Utc::now().timestamp()),
+                };
+
+                // The latest invoice paid for the current billing period
+                let latest_invoice = subscription
+                    .latest_invoice
+                    .as_ref()
+                    .and_then(|i| i.as_object());
+                let payment_intent_id = latest_invoice
+                    .and_then(|i| i.payment_intent.as_ref())
+                    .map(|p| p.id());
+                let amount_paid =
+                    latest_invoice.and_then(|i| i.amount_paid).unwrap_or(0);
+
+                amount_refunded = match refund_policy {
+                    RefundPolicy::Unspecified | RefundPolicy::None => 0,
+                    RefundPolicy::Prorated => Self::calculate_unused_amount(
+                        amount_paid,
+                        subscription.current_period_start,
+                        subscription.current_period_end,
+                        canceled_at,
+                    ),
+                    RefundPolicy::Full => amount_paid,
+                };
+
+                if let Some(payment_intent_id) =
+                    payment_intent_id.filter(|_| amount_refunded > 0)
+                {
+                    // The platform fee of the refunded amount is refunded to
+                    // the seller. Retried cancellations refund the
+                    // subscription once.
+                    let mut create_refund = CreateRefund::new();
+                    create_refund.payment_intent = Some(payment_intent_id);
+                    create_refund.amount = Some(amount_refunded);
+                    create_refund.refund_application_fee = Some(true);
+
+                    Refund::create(
+                        &stripe_client.clone().with_strategy(
+                            RequestStrategy::Idempotent(format!(
+                                "cancel-subscription-refund-{subscription_id}"
+                            )),
+                        ),
+                        create_refund,
+                    )
+                    .await
+                    .map_err(stripe_error_to_status)?;
+                } else {
+                    amount_refunded = 0;
+                }
+            }
+        }
+
+        SubscriptionCancellation::create(
+            &self.pool,
+            &shop_uuid,
+            &stripe_subscription_id,
+            match cancellation_mode {
+                CancellationMode::Unspecified
+                | CancellationMode::AtPeriodEnd => "at_period_end",
+                CancellationMode::Immediately => "immediately",
+            },
+            match refund_policy {
+                RefundPolicy::Unspecified | RefundPolicy::None => "none",
+                RefundPolicy::Prorated => "prorated",
+                RefundPolicy::Full => "full",
+            },
+            amount_refunded,
+            reason,
+        )
+        .await?;
+
+        Ok(Response::new(CancelSubscriptionResponse {
+            amount_refunded,
+        }))
+    }
+
+    async fn pause_subscription(
+        &self,
+        request: Request<PauseSubscriptionRequest>,
+    ) -> Result<Response<PauseSubscriptionResponse>, Status> {
+        verify_service_user(request.metadata(), &self.verifier).await?;
+
+        let PauseSubscriptionRequest {
+            stripe_subscription_id,
+            shop_id,
+            behavior,
+            resumes_at,
+        } = request.into_inner();
+
+        let behavior = Self::get_pause_behavior(
+            PauseBehavior::try_from(behavior).unwrap_or_default(),
+        )?;
+
+        if resumes_at <= Utc::now().timestamp() {
+            return Err(Status::invalid_argument(
+                "field resumes_at must be in the future",
+            ));
+        }
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let stripe_account = StripeAccount::get(&self.pool, &shop_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("stripe account"))?;
+
+        let subscription_id = SubscriptionId::from_str(&stripe_subscription_id)
+            .map_err(parse_id_error_to_status)?;
+
+        let stripe_client =
+            connected_stripe_client(&self.stripe_client, &stripe_account)?;
+
         let mut update_subscription = UpdateSubscription::new();
 
-        update_subscription.cancel_at_period_end = Some(true);
+        update_subscription.pause_collection =
+            Some(UpdateSubscriptionPauseCollection {
+                behavior,
+                resumes_at: Some(resumes_at),
+            });
 
         StripeSubscription::update(
             &stripe_client,
@@ -591,12 +2396,9 @@ impl stripe_service_server::StripeService for StripeService {
             update_subscription,
         )
         .await
-        .map_err(|err| {
-            tracing::log::error!("{err}");
-            Status::internal("")
-        })?;
+        .map_err(stripe_error_to_status)?;
 
-        Ok(Response::new(CancelSubscriptionResponse {}))
+        Ok(Response::new(PauseSubscriptionResponse {}))
     }
 
     async fn resume_subscription(
@@ -628,9 +2430,82 @@ impl stripe_service_server::StripeService for StripeService {
         let stripe_client =
             stripe_client.with_stripe_account(stripe_account_id);
 
-        let mut update_subscription = UpdateSubscription::new();
+        // Resumes canceled as well as paused subscriptions
+        stripe_client
+            .post_form::<StripeSubscription, _>(
+                &format!("/subscriptions/{}", subscription_id),
+                ResumeSubscriptionParams {
+                    cancel_at_period_end: false,
+                    pause_collection: "",
+                },
+            )
+            .await
+            .map_err(|err| {
+                tracing::log::error!("{err}");
+                Status::internal("")
+            })?;
+
+        Ok(Response::new(ResumeSubscriptionResponse {}))
+    }
+
+    async fn change_subscription_plan(
+        &self,
+        request: Request<ChangeSubscriptionPlanRequest>,
+    ) -> Result<Response<ChangeSubscriptionPlanResponse>, Status> {
+        verify_service_user(request.metadata(), &self.verifier).await?;
+
+        let metadata = request.metadata().clone();
+
+        let ChangeSubscriptionPlanRequest {
+            stripe_subscription_id,
+            shop_id,
+            offer_id,
+            proration_behavior,
+            proration_date,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let stripe_account = StripeAccount::get(&self.pool, &shop_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("stripe account"))?;
+
+        let subscription_id = SubscriptionId::from_str(&stripe_subscription_id)
+            .map_err(parse_id_error_to_status)?;
 
-        update_subscription.cancel_at_period_end = Some(false);
+        let stripe_client =
+            connected_stripe_client(&self.stripe_client, &stripe_account)?;
+
+        let subscription =
+            StripeSubscription::retrieve(&stripe_client, &subscription_id, &[])
+                .await
+                .map_err(stripe_error_to_status)?;
+
+        let (plan_item, fee_percent) = self
+            .get_plan_change(
+                &stripe_client,
+                &subscription,
+                &shop_id,
+                &offer_id,
+                &metadata,
+            )
+            .await?;
+
+        // The offer_id in the metadata assigns the subscription to the new
+        // offer
+        let mut subscription_metadata = subscription.metadata;
+        subscription_metadata.insert(Self::metadata_key_offer_id(), offer_id);
+
+        let mut update_subscription = UpdateSubscription::new();
+        update_subscription.items = Some(vec![plan_item]);
+        update_subscription.application_fee_percent = Some(fee_percent);
+        update_subscription.metadata = Some(subscription_metadata);
+        update_subscription.proration_behavior =
+            Some(Self::get_proration_behavior(
+                ProrationBehavior::try_from(proration_behavior)
+                    .unwrap_or_default(),
+            ));
+        update_subscription.proration_date = proration_date;
 
         StripeSubscription::update(
             &stripe_client,
@@ -638,12 +2513,80 @@ impl stripe_service_server::StripeService for StripeService {
             update_subscription,
         )
         .await
-        .map_err(|err| {
-            tracing::log::error!("{err}");
-            Status::internal("")
-        })?;
+        .map_err(stripe_error_to_status)?;
 
-        Ok(Response::new(ResumeSubscriptionResponse {}))
+        Ok(Response::new(ChangeSubscriptionPlanResponse {}))
+    }
+
+    async fn preview_subscription_plan_change(
+        &self,
+        request: Request<PreviewSubscriptionPlanChangeRequest>,
+    ) -> Result<Response<PreviewSubscriptionPlanChangeResponse>, Status> {
+        verify_service_user(request.metadata(), &self.verifier).await?;
+
+        let metadata = request.metadata().clone();
+
+        let PreviewSubscriptionPlanChangeRequest {
+            stripe_subscription_id,
+            shop_id,
+            offer_id,
+            proration_behavior,
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let stripe_account = StripeAccount::get(&self.pool, &shop_uuid)
+            .await?
+            .ok_or_else(|| Status::not_found("stripe account"))?;
+
+        let subscription_id = SubscriptionId::from_str(&stripe_subscription_id)
+            .map_err(parse_id_error_to_status)?;
+
+        let stripe_client =
+            connected_stripe_client(&self.stripe_client, &stripe_account)?;
+
+        let subscription =
+            StripeSubscription::retrieve(&stripe_client, &subscription_id, &[])
+                .await
+                .map_err(stripe_error_to_status)?;
+
+        let (plan_item, _) = self
+            .get_plan_change(
+                &stripe_client,
+                &subscription,
+                &shop_id,
+                &offer_id,
+                &metadata,
+            )
+            .await?;
+
+        // The proration date is returned, so the change can be confirmed
+        // with the previewed amounts
+        let proration_date = Utc::now().timestamp();
+
+        let upcoming_invoice: Invoice = stripe_client
+            .get_query(
+                "/invoices/upcoming",
+                UpcomingInvoiceParams {
+                    customer: subscription.customer.id().to_string(),
+                    subscription: subscription.id.to_string(),
+                    subscription_items: vec![plan_item],
+                    subscription_proration_behavior:
+                        Self::get_proration_behavior(
+                            ProrationBehavior::try_from(proration_behavior)
+                                .unwrap_or_default(),
+                        ),
+                    subscription_proration_date: proration_date,
+                },
+            )
+            .await
+            .map_err(stripe_error_to_status)?;
+
+        Ok(Response::new(Self::to_plan_change_preview(
+            upcoming_invoice,
+            subscription.currency,
+            proration_date,
+        )))
     }
 }
 
@@ -651,21 +2594,482 @@ impl stripe_service_server::StripeService for StripeService {
 mod tests {
     use super::*;
 
+    use stripe::Currency::{EUR, JPY};
+
     #[test]
     fn test_calculate_fee_amount() {
-        assert_eq!(StripeService::calculate_fee_amount(588, 2, 50), 50);
-        assert_eq!(StripeService::calculate_fee_amount(1499, 2, 50), 50);
-        assert_eq!(StripeService::calculate_fee_amount(5000, 2, 50), 100);
-        assert_eq!(StripeService::calculate_fee_amount(4444, 2, 50), 88);
-        assert_eq!(StripeService::calculate_fee_amount(4444, 3, 50), 133);
+        assert_eq!(
+            StripeService::calculate_fee_amount(588, 2, 50, EUR).unwrap(),
+            50
+        );
+        assert_eq!(
+            StripeService::calculate_fee_amount(1499, 2, 50, EUR).unwrap(),
+            50
+        );
+        assert_eq!(
+            StripeService::calculate_fee_amount(5000, 2, 50, EUR).unwrap(),
+            100
+        );
+        assert_eq!(
+            StripeService::calculate_fee_amount(4444, 2, 50, EUR).unwrap(),
+            88
+        );
+        assert_eq!(
+            StripeService::calculate_fee_amount(4444, 3, 50, EUR).unwrap(),
+            133
+        );
+        assert_eq!(
+            StripeService::calculate_fee_amount(100, 2, 50, JPY).unwrap(),
+            80
+        );
+        assert_eq!(
+            StripeService::calculate_fee_amount(5000, 2, 50, JPY).unwrap(),
+            100
+        );
     }
 
     #[test]
     fn test_calculate_fee_percent() {
-        assert_eq!(StripeService::calculate_fee_percent(588, 2, 50), 8.50);
-        assert_eq!(StripeService::calculate_fee_percent(1499, 2, 50), 3.34);
-        assert_eq!(StripeService::calculate_fee_percent(5000, 2, 50), 2.00);
-        assert_eq!(StripeService::calculate_fee_percent(4444, 2, 50), 2.00);
-        assert_eq!(StripeService::calculate_fee_percent(4444, 3, 50), 3.00);
+        assert_eq!(
+            StripeService::calculate_fee_percent(588, 2, 50, EUR).unwrap(),
+            8.50
+        );
+        assert_eq!(
+            StripeService::calculate_fee_percent(1499, 2, 50, EUR).unwrap(),
+            3.34
+        );
+        assert_eq!(
+            StripeService::calculate_fee_percent(5000, 2, 50, EUR).unwrap(),
+            2.00
+        );
+        assert_eq!(
+            StripeService::calculate_fee_percent(4444, 2, 50, EUR).unwrap(),
+            2.00
+        );
+        assert_eq!(
+            StripeService::calculate_fee_percent(4444, 3, 50, EUR).unwrap(),
+            3.00
+        );
+        assert_eq!(
+            StripeService::calculate_fee_percent(1500, 2, 50, JPY).unwrap(),
+            5.33
+        );
+    }
+
+    #[test]
+    fn test_calculate_subscription_fee_percent() {
+        assert_eq!(
+            StripeService::calculate_subscription_fee_percent(
+                500, None, 2, 50, EUR
+            )
+            .unwrap(),
+            10.00
+        );
+        assert_eq!(
+            StripeService::calculate_subscription_fee_percent(
+                500,
+                Some(1000),
+                2,
+                50,
+                EUR
+            )
+            .unwrap(),
+            3.33
+        );
+        assert_eq!(
+            StripeService::calculate_subscription_fee_percent(
+                1000,
+                Some(4000),
+                2,
+                50,
+                EUR
+            )
+            .unwrap(),
+            2.00
+        );
+        assert_eq!(
+            StripeService::calculate_subscription_fee_percent(
+                10,
+                Some(10),
+                2,
+                50,
+                EUR
+            )
+            .unwrap(),
+            100.00
+        );
+    }
+
+    #[test]
+    fn test_calculate_discounted_amount() {
+        let percent_off = Coupon {
+            percent_off: Some(25.0),
+            ..Default::default()
+        };
+        let amount_off = Coupon {
+            amount_off: Some(500),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            StripeService::calculate_discounted_amount(5000, &percent_off),
+            3750
+        );
+        assert_eq!(
+            StripeService::calculate_discounted_amount(1499, &percent_off),
+            1124
+        );
+        assert_eq!(
+            StripeService::calculate_discounted_amount(5000, &amount_off),
+            4500
+        );
+        assert_eq!(
+            StripeService::calculate_discounted_amount(300, &amount_off),
+            0
+        );
+    }
+
+    #[test]
+    fn test_get_lowest_custom_unit_amount() {
+        assert_eq!(
+            StripeService::get_lowest_custom_unit_amount(
+                &CustomUnitAmount {
+                    minimum: Some(500),
+                    maximum: None,
+                    preset: None,
+                },
+                "eur"
+            ),
+            500
+        );
+        assert_eq!(
+            StripeService::get_lowest_custom_unit_amount(
+                &CustomUnitAmount::default(),
+                "eur"
+            ),
+            50
+        );
+        assert_eq!(
+            StripeService::get_lowest_custom_unit_amount(
+                &CustomUnitAmount {
+                    minimum: Some(10),
+                    maximum: None,
+                    preset: None,
+                },
+                "eur"
+            ),
+            50
+        );
+    }
+
+    #[test]
+    fn test_calculate_tiered_amount() {
+        let tiers = [
+            PriceTier {
+                up_to: Some(9),
+                unit_amount: 1000,
+                flat_amount: None,
+            },
+            PriceTier {
+                up_to: None,
+                unit_amount: 800,
+                flat_amount: Some(500),
+            },
+        ];
+
+        assert_eq!(
+            StripeService::calculate_tiered_amount(
+                PriceTiersMode::Graduated,
+                &tiers,
+                5
+            )
+            .unwrap(),
+            5000
+        );
+        assert_eq!(
+            StripeService::calculate_tiered_amount(
+                PriceTiersMode::Graduated,
+                &tiers,
+                12
+            )
+            .unwrap(),
+            9000 + 2400 + 500
+        );
+        assert_eq!(
+            StripeService::calculate_tiered_amount(
+                PriceTiersMode::Volume,
+                &tiers,
+                9
+            )
+            .unwrap(),
+            9000
+        );
+        assert_eq!(
+            StripeService::calculate_tiered_amount(
+                PriceTiersMode::Volume,
+                &tiers,
+                12
+            )
+            .unwrap(),
+            9600 + 500
+        );
+        assert!(StripeService::calculate_tiered_amount(
+            PriceTiersMode::Volume,
+            &tiers[..1],
+            1
+        )
+        .is_err());
+        assert!(StripeService::calculate_tiered_amount(
+            PriceTiersMode::Volume,
+            &[],
+            1
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_get_shipping_amount() {
+        let shipping_option = ShippingOption {
+            shipping_option_id: Default::default(),
+            shop_id: Default::default(),
+            display_name: String::from("Standard"),
+            currency: String::from("eur"),
+            amount: 490,
+            domestic_country: Some(String::from("DE")),
+            international_amount: Some(1490),
+            min_delivery_days: None,
+            max_delivery_days: None,
+            free_shipping_threshold: Some(5000),
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        };
+
+        assert_eq!(
+            StripeService::get_shipping_amount(
+                &shipping_option,
+                &[],
+                None,
+                Some("de"),
+                1000
+            )
+            .unwrap(),
+            490
+        );
+        assert_eq!(
+            StripeService::get_shipping_amount(
+                &shipping_option,
+                &[],
+                None,
+                Some("AT"),
+                1000
+            )
+            .unwrap(),
+            1490
+        );
+        assert_eq!(
+            StripeService::get_shipping_amount(
+                &shipping_option,
+                &[],
+                None,
+                Some("AT"),
+                5000
+            )
+            .unwrap(),
+            0
+        );
+        assert!(StripeService::get_shipping_amount(
+            &shipping_option,
+            &[],
+            None,
+            None,
+            1000
+        )
+        .is_none());
+        assert_eq!(
+            StripeService::get_shipping_amount(
+                &shipping_option,
+                &[],
+                None,
+                None,
+                5000
+            )
+            .unwrap(),
+            0
+        );
+
+        let eu_zone_id = Uuid::new_v4();
+        let zone_rates = [ShippingZoneRate {
+            shipping_option_id: shipping_option.shipping_option_id,
+            shipping_zone_id: eu_zone_id,
+            amount: 990,
+        }];
+
+        assert_eq!(
+            StripeService::get_shipping_amount(
+                &shipping_option,
+                &zone_rates,
+                Some(eu_zone_id),
+                Some("AT"),
+                1000
+            )
+            .unwrap(),
+            990
+        );
+        assert_eq!(
+            StripeService::get_shipping_amount(
+                &shipping_option,
+                &zone_rates,
+                None,
+                Some("US"),
+                1000
+            )
+            .unwrap(),
+            1490
+        );
+    }
+
+    #[test]
+    fn test_calculate_unused_amount() {
+        assert_eq!(
+            StripeService::calculate_unused_amount(3000, 0, 30, 0),
+            3000
+        );
+        assert_eq!(
+            StripeService::calculate_unused_amount(3000, 0, 30, 10),
+            2000
+        );
+        assert_eq!(StripeService::calculate_unused_amount(999, 0, 30, 10), 666);
+        assert_eq!(StripeService::calculate_unused_amount(3000, 0, 30, 30), 0);
+        assert_eq!(StripeService::calculate_unused_amount(3000, 0, 30, 40), 0);
+        assert_eq!(
+            StripeService::calculate_unused_amount(3000, 10, 40, 0),
+            3000
+        );
+        assert_eq!(StripeService::calculate_unused_amount(3000, 30, 30, 0), 0);
+    }
+
+    #[test]
+    fn test_get_proration_behavior() {
+        assert_eq!(
+            StripeService::get_proration_behavior(
+                ProrationBehavior::Unspecified
+            ),
+            SubscriptionProrationBehavior::CreateProrations
+        );
+        assert_eq!(
+            StripeService::get_proration_behavior(
+                ProrationBehavior::CreateProrations
+            ),
+            SubscriptionProrationBehavior::CreateProrations
+        );
+        assert_eq!(
+            StripeService::get_proration_behavior(
+                ProrationBehavior::AlwaysInvoice
+            ),
+            SubscriptionProrationBehavior::AlwaysInvoice
+        );
+        assert_eq!(
+            StripeService::get_proration_behavior(ProrationBehavior::None),
+            SubscriptionProrationBehavior::None
+        );
+    }
+
+    #[test]
+    fn test_to_plan_change_preview() {
+        let preview = StripeService::to_plan_change_preview(
+            Invoice {
+                currency: Some(EUR),
+                amount_due: Some(1250),
+                total: Some(1500),
+                ..Default::default()
+            },
+            JPY,
+            1700000000,
+        );
+        assert_eq!(preview.currency, "eur");
+        assert_eq!(preview.amount_due, 1250);
+        assert_eq!(preview.total, 1500);
+        assert_eq!(preview.proration_date, 1700000000);
+
+        let preview = StripeService::to_plan_change_preview(
+            Invoice::default(),
+            EUR,
+            1700000000,
+        );
+        assert_eq!(preview.currency, "eur");
+        assert_eq!(preview.amount_due, 0);
+        assert_eq!(preview.total, 0);
+    }
+
+    #[test]
+    fn test_get_free_shipping_address() {
+        let shipping_address = ShippingAddressRequest {
+            name: Some(String::from("Jane Doe")),
+            line1: Some(String::from(" Main Street 1 ")),
+            line2: Some(String::new()),
+            postal_code: Some(String::from("10115")),
+            city: Some(String::from("Berlin")),
+            state: None,
+            country: Some(String::from("de")),
+        };
+
+        let address = StripeService::get_free_shipping_address(Some(
+            shipping_address.clone(),
+        ))
+        .unwrap();
+        assert_eq!(address.line1.as_deref(), Some("Main Street 1"));
+        assert_eq!(address.line2, None);
+        assert_eq!(address.country.as_deref(), Some("DE"));
+
+        assert!(StripeService::get_free_shipping_address(None).is_err());
+        assert!(StripeService::get_free_shipping_address(Some(
+            ShippingAddressRequest {
+                line1: Some(String::from(" ")),
+                ..shipping_address.clone()
+            }
+        ))
+        .is_err());
+        assert!(StripeService::get_free_shipping_address(Some(
+            ShippingAddressRequest {
+                country: Some(String::from("XX")),
+                ..shipping_address
+            }
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_get_pause_behavior() {
+        assert!(
+            StripeService::get_pause_behavior(PauseBehavior::Unspecified)
+                .is_err()
+        );
+        assert_eq!(
+            StripeService::get_pause_behavior(PauseBehavior::Void).unwrap(),
+            UpdateSubscriptionPauseCollectionBehavior::Void
+        );
+        assert_eq!(
+            StripeService::get_pause_behavior(PauseBehavior::KeepAsDraft)
+                .unwrap(),
+            UpdateSubscriptionPauseCollectionBehavior::KeepAsDraft
+        );
+        assert_eq!(
+            StripeService::get_pause_behavior(PauseBehavior::MarkUncollectible)
+                .unwrap(),
+            UpdateSubscriptionPauseCollectionBehavior::MarkUncollectible
+        );
+    }
+
+    #[test]
+    fn test_get_maximum_quantity() {
+        assert_eq!(StripeService::get_maximum_quantity(&[None, None]), None);
+        assert_eq!(
+            StripeService::get_maximum_quantity(&[Some(5), None]),
+            Some(5)
+        );
+        assert_eq!(
+            StripeService::get_maximum_quantity(&[Some(5), Some(2)]),
+            Some(2)
+        );
     }
 }
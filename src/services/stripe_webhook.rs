@@ -0,0 +1,1113 @@
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use chrono::{DateTime, Months, Utc};
+use deadpool_postgres::Pool;
+use std::str::FromStr;
+use stripe::generated::billing::subscription::SubscriptionProrationBehavior;
+use stripe::{
+    AccountId, CapturePaymentIntent, Charge, CheckoutSession,
+    CheckoutSessionId, CheckoutSessionPaymentStatus, Client, Event,
+    EventObject, EventType, Expandable, PaymentIntent, PaymentIntentId,
+    PaymentIntentStatus, PaymentPagesCheckoutSessionConsentPromotions,
+    RecurringInterval, Subscription, SubscriptionStatus, UpdateSubscription,
+    Webhook,
+};
+use tonic::Status;
+
+use crate::api::sited_io::commerce::v1::OfferType;
+use crate::model::{
+    AbandonedCheckout, Entitlement, LicenseKey, OfferStock, Payment,
+    PaymentAmounts, PurchaseReservation, ShippingAddress,
+};
+use crate::{
+    parse_id_error_to_status, parse_uuid, stripe_error_to_status,
+    LicenseKeyService, OrderService,
+};
+
+/// Receives events of connected accounts from Stripe.
+#[derive(Clone)]
+pub struct StripeWebhook {
+    pool: Pool,
+    stripe_client: Client,
+    webhook_secret: String,
+}
+
+impl StripeWebhook {
+    const PATH: &'static str = "/stripe/webhook";
+
+    fn metadata_key_user_id() -> String {
+        String::from("user_id")
+    }
+
+    fn metadata_key_shop_id() -> String {
+        String::from("shop_id")
+    }
+
+    fn metadata_key_offer_id() -> String {
+        String::from("offer_id")
+    }
+
+    fn metadata_key_offer_type() -> String {
+        String::from("offer_type")
+    }
+
+    fn metadata_key_billing_cycles() -> String {
+        String::from("billing_cycles")
+    }
+
+    fn metadata_key_platform_fee_percent() -> String {
+        String::from("platform_fee_percent")
+    }
+
+    fn metadata_key_minimum_platform_fee() -> String {
+        String::from("minimum_platform_fee")
+    }
+
+    fn new(pool: Pool, stripe_client: Client, webhook_secret: String) -> Self {
+        Self {
+            pool,
+            stripe_client,
+            webhook_secret,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        stripe_client: Client,
+        webhook_secret: String,
+    ) -> Router {
+        Router::new()
+            .route(Self::PATH, post(Self::handle_request))
+            .with_state(Self::new(pool, stripe_client, webhook_secret))
+    }
+
+    fn connected_stripe_client(
+        &self,
+        account: Option<&String>,
+    ) -> Result<Client, Status> {
+        let account = account
+            .ok_or_else(|| Status::invalid_argument("event.account missing"))?;
+        let account_id =
+            AccountId::from_str(account).map_err(parse_id_error_to_status)?;
+
+        Ok(self.stripe_client.clone().with_stripe_account(account_id))
+    }
+
+    async fn handle_request(
+        State(webhook): State<Self>,
+        headers: HeaderMap,
+        payload: String,
+    ) -> StatusCode {
+        let Some(signature) = headers
+            .get("stripe-signature")
+            .and_then(|s| s.to_str().ok())
+        else {
+            return StatusCode::BAD_REQUEST;
+        };
+
+        let event = match Webhook::construct_event(
+            &payload,
+            signature,
+            &webhook.webhook_secret,
+        ) {
+            Ok(event) => event,
+            Err(err) => {
+                tracing::log::warn!("{err:?}");
+                return StatusCode::BAD_REQUEST;
+            }
+        };
+
+        match webhook.handle_event(event).await {
+            Ok(()) => StatusCode::OK,
+            Err(err) => {
+                tracing::log::error!("{err:?}");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    async fn handle_event(&self, event: Event) -> Result<(), Status> {
+        match (event.type_, event.data.object) {
+            (
+                EventType::CheckoutSessionCompleted,
+                EventObject::CheckoutSession(checkout_session),
+            ) => {
+                self.record_payment(event.account.as_ref(), checkout_session)
+                    .await
+            }
+            (
+                EventType::CheckoutSessionAsyncPaymentSucceeded,
+                EventObject::CheckoutSession(checkout_session),
+            ) => {
+                self.complete_async_payment(
+                    event.account.as_ref(),
+                    checkout_session,
+                )
+                .await
+            }
+            (
+                EventType::CheckoutSessionAsyncPaymentFailed,
+                EventObject::CheckoutSession(checkout_session),
+            ) => {
+                self.fail_async_payment(
+                    event.account.as_ref(),
+                    checkout_session,
+                )
+                .await
+            }
+            (
+                EventType::CheckoutSessionExpired,
+                EventObject::CheckoutSession(checkout_session),
+            ) => self.record_abandoned_checkout(checkout_session).await,
+            (
+                EventType::CustomerSubscriptionTrialWillEnd,
+                EventObject::Subscription(subscription),
+            ) => self.update_trial_end(&subscription).await,
+            (
+                EventType::CustomerSubscriptionUpdated,
+                EventObject::Subscription(mut subscription),
+            ) => {
+                self.update_switched_plan(
+                    event.account.as_ref(),
+                    &mut subscription,
+                )
+                .await?;
+                self.update_trial_end(&subscription).await?;
+                self.update_subscription_entitlement(&subscription).await
+            }
+            (
+                EventType::CustomerSubscriptionDeleted,
+                EventObject::Subscription(subscription),
+            ) => self.update_subscription_entitlement(&subscription).await,
+            (EventType::ChargeRefunded, EventObject::Charge(charge)) => {
+                self.revoke_refunded_purchase(&charge).await
+            }
+            (
+                EventType::PaymentIntentSucceeded,
+                EventObject::PaymentIntent(payment_intent),
+            ) => {
+                self.update_authorized_payment(&payment_intent.id, "paid")
+                    .await
+            }
+            (
+                EventType::PaymentIntentCanceled,
+                EventObject::PaymentIntent(payment_intent),
+            ) => {
+                self.update_authorized_payment(&payment_intent.id, "canceled")
+                    .await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns until when a subscription grants access. Access of unpaid,
+    /// paused and incomplete subscriptions ends immediately.
+    fn get_subscription_access_end(
+        subscription: &Subscription,
+        now: i64,
+    ) -> Option<i64> {
+        match subscription.status {
+            SubscriptionStatus::Canceled
+            | SubscriptionStatus::IncompleteExpired => {
+                Some(subscription.ended_at.unwrap_or(now))
+            }
+            SubscriptionStatus::Incomplete
+            | SubscriptionStatus::Unpaid
+            | SubscriptionStatus::Paused => Some(now),
+            SubscriptionStatus::Active
+            | SubscriptionStatus::Trialing
+            | SubscriptionStatus::PastDue => subscription.cancel_at,
+        }
+    }
+
+    /// Entitlements of subscriptions follow the status of the subscription
+    /// and the offer it was changed to.
+    async fn update_subscription_entitlement(
+        &self,
+        subscription: &Subscription,
+    ) -> Result<(), Status> {
+        let offer_id = subscription
+            .metadata
+            .get(&Self::metadata_key_offer_id())
+            .map(|o| parse_uuid(o, "metadata.offer_id"))
+            .transpose()?;
+
+        Entitlement::update_for_subscription(
+            &self.pool,
+            &subscription.id.to_string(),
+            offer_id,
+            Self::get_subscription_access_end(
+                subscription,
+                Utc::now().timestamp(),
+            )
+            .and_then(|v| DateTime::from_timestamp(v, 0)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the fee percent of subscriptions, which charges at least the
+    /// minimum platform fee of each invoice.
+    fn calculate_fee_percent(
+        invoice_amount: i64,
+        fee_percent: u32,
+        minimum_fee_amount: u32,
+    ) -> f64 {
+        if invoice_amount <= 0 {
+            return 100.0;
+        }
+        let fee_amount =
+            invoice_amount.saturating_mul(i64::from(fee_percent)) / 100;
+        if fee_amount < i64::from(minimum_fee_amount) {
+            let fee_percent =
+                f64::from(minimum_fee_amount) * 100.0 / invoice_amount as f64;
+            ((fee_percent * 100.0).round() / 100.0).min(100.0)
+        } else {
+            f64::from(fee_percent)
+        }
+    }
+
+    /// Subscribers may switch to prices of other offers in the customer
+    /// portal. The offer and the platform fee of the subscription follow the
+    /// price it was switched to.
+    async fn update_switched_plan(
+        &self,
+        account: Option<&String>,
+        subscription: &mut Subscription,
+    ) -> Result<(), Status> {
+        let [item] = subscription.items.data.as_slice() else {
+            return Ok(());
+        };
+        let Some(price) = item.price.as_ref() else {
+            return Ok(());
+        };
+        let Some(offer_id) = price.product.as_ref().map(|p| p.id().to_string())
+        else {
+            return Ok(());
+        };
+
+        // Tiered prices have no unit amount, their fee is left unchanged
+        let fee_percent = subscription
+            .metadata
+            .get(&Self::metadata_key_platform_fee_percent())
+            .and_then(|p| p.parse::<u32>().ok())
+            .zip(
+                subscription
+                    .metadata
+                    .get(&Self::metadata_key_minimum_platform_fee())
+                    .and_then(|m| m.parse::<u32>().ok()),
+            )
+            .zip(price.unit_amount)
+            .map(|((fee_percent, minimum_fee_amount), unit_amount)| {
+                Self::calculate_fee_percent(
+                    unit_amount.saturating_mul(
+                        i64::try_from(item.quantity.unwrap_or(1))
+                            .unwrap_or(i64::MAX),
+                    ),
+                    fee_percent,
+                    minimum_fee_amount,
+                )
+            });
+
+        let offer_changed =
+            subscription.metadata.get(&Self::metadata_key_offer_id())
+                != Some(&offer_id);
+        let fee_changed = fee_percent
+            .is_some_and(|f| subscription.application_fee_percent != Some(f));
+        if !offer_changed && !fee_changed {
+            return Ok(());
+        }
+
+        let mut metadata = subscription.metadata.clone();
+        metadata.insert(Self::metadata_key_offer_id(), offer_id);
+
+        let mut update_subscription = UpdateSubscription::new();
+        update_subscription.metadata = Some(metadata.clone());
+        update_subscription.application_fee_percent = fee_percent;
+
+        let stripe_client = self.connected_stripe_client(account)?;
+        Subscription::update(
+            &stripe_client,
+            &subscription.id,
+            update_subscription,
+        )
+        .await
+        .map_err(stripe_error_to_status)?;
+
+        subscription.metadata = metadata;
+
+        Ok(())
+    }
+
+    /// Fully refunded payments no longer grant access and their license
+    /// keys are revoked. Refunded guest purchases can not be claimed.
+    async fn revoke_refunded_purchase(
+        &self,
+        charge: &Charge,
+    ) -> Result<(), Status> {
+        let Some(payment_intent) =
+            charge.payment_intent.as_ref().filter(|_| charge.refunded)
+        else {
+            return Ok(());
+        };
+
+        let payment_intent_id = payment_intent.id().to_string();
+
+        Payment::update_status(
+            &self.pool,
+            &payment_intent_id,
+            "paid",
+            "refunded",
+        )
+        .await?;
+
+        Entitlement::revoke_for_payment_intent(&self.pool, &payment_intent_id)
+            .await?;
+        LicenseKey::revoke_for_payment_intent(&self.pool, &payment_intent_id)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Trials may be extended or ended early, so the trial end of payments
+    /// follows the subscription whenever it is updated and when Stripe
+    /// announces the end of the trial.
+    async fn update_trial_end(
+        &self,
+        subscription: &Subscription,
+    ) -> Result<(), Status> {
+        Payment::update_trial_end(
+            &self.pool,
+            &subscription.id.to_string(),
+            subscription
+                .trial_end
+                .and_then(|t| DateTime::from_timestamp(t, 0)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Authorizations may be captured or canceled in the Stripe dashboard or
+    /// expire, so authorized payments follow their payment intent.
+    async fn update_authorized_payment(
+        &self,
+        payment_intent_id: &PaymentIntentId,
+        payment_status: &str,
+    ) -> Result<(), Status> {
+        Payment::update_status(
+            &self.pool,
+            &payment_intent_id.to_string(),
+            "authorized",
+            payment_status,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Pending payments of delayed payment methods are granted once their
+    /// payment succeeds. Stripe does not guarantee the order of events, so
+    /// the payment is recorded if the checkout session was not recorded yet.
+    async fn complete_async_payment(
+        &self,
+        account: Option<&String>,
+        checkout_session: CheckoutSession,
+    ) -> Result<(), Status> {
+        Payment::update_status_for_checkout_session(
+            &self.pool,
+            &checkout_session.id.to_string(),
+            "pending",
+            "paid",
+        )
+        .await?;
+
+        self.record_payment(account, checkout_session).await
+    }
+
+    async fn fail_async_payment(
+        &self,
+        account: Option<&String>,
+        checkout_session: CheckoutSession,
+    ) -> Result<(), Status> {
+        let stripe_checkout_session_id = checkout_session.id.to_string();
+        let offer_type = checkout_session
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(&Self::metadata_key_offer_type()))
+            .and_then(|t| OfferType::from_str_name(t));
+
+        self.record_payment(account, checkout_session).await?;
+
+        // Subscriptions may have granted access before their first payment
+        // failed. The stock settled for the payment is returned.
+        if let Some(payment) = Payment::update_status_for_checkout_session(
+            &self.pool,
+            &stripe_checkout_session_id,
+            "pending",
+            "failed",
+        )
+        .await?
+        {
+            Entitlement::revoke_for_payment(&self.pool, &payment.payment_id)
+                .await?;
+            LicenseKey::revoke_for_payment(&self.pool, &payment.payment_id)
+                .await?;
+
+            if offer_type == Some(OfferType::Physical) {
+                OfferStock::restock(
+                    &self.pool,
+                    &payment.offer_id,
+                    payment.quantity.unwrap_or(1),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns when the authorization of a payment intent with manual
+    /// capture expires, or `None` if the payment intent is not authorized.
+    async fn get_authorization_expiry(
+        &self,
+        account: Option<&String>,
+        payment_intent_id: &PaymentIntentId,
+    ) -> Result<Option<DateTime<Utc>>, Status> {
+        let stripe_client = self.connected_stripe_client(account)?;
+
+        let payment_intent = PaymentIntent::retrieve(
+            &stripe_client,
+            payment_intent_id,
+            &["latest_charge"],
+        )
+        .await
+        .map_err(stripe_error_to_status)?;
+
+        if payment_intent.status != PaymentIntentStatus::RequiresCapture {
+            return Ok(None);
+        }
+
+        let capture_before = match payment_intent.latest_charge {
+            Some(Expandable::Object(charge)) => charge
+                .payment_method_details
+                .and_then(|p| p.card)
+                .and_then(|c| c.capture_before),
+            _ => None,
+        };
+
+        // Card authorizations are valid for 7 days if Stripe does not report
+        // a capture deadline
+        Ok(Some(
+            capture_before
+                .and_then(|c| DateTime::from_timestamp(c, 0))
+                .unwrap_or_else(|| Utc::now() + chrono::Duration::days(7)),
+        ))
+    }
+
+    /// Returns the platform fee of an amount chosen by the buyer, which is at
+    /// least the minimum platform fee and at most the amount paid.
+    fn calculate_fee_amount(
+        paid_amount: i64,
+        fee_percent: u32,
+        minimum_fee_amount: u32,
+    ) -> u64 {
+        let paid_amount = u64::try_from(paid_amount).unwrap_or(0);
+        (paid_amount.saturating_mul(u64::from(fee_percent)) / 100)
+            .max(u64::from(minimum_fee_amount))
+            .min(paid_amount)
+    }
+
+    /// Captures the authorized amount chosen by the buyer with the platform
+    /// fee of the amount paid. Returns whether the payment intent is paid,
+    /// which it already is if Stripe delivers the event again.
+    async fn capture_chosen_amount(
+        &self,
+        account: Option<&String>,
+        payment_intent_id: &PaymentIntentId,
+        application_fee_amount: u64,
+    ) -> Result<bool, Status> {
+        let stripe_client = self.connected_stripe_client(account)?;
+
+        let mut payment_intent =
+            PaymentIntent::retrieve(&stripe_client, payment_intent_id, &[])
+                .await
+                .map_err(stripe_error_to_status)?;
+
+        if payment_intent.status == PaymentIntentStatus::RequiresCapture {
+            payment_intent = PaymentIntent::capture(
+                &stripe_client,
+                payment_intent_id.as_str(),
+                CapturePaymentIntent {
+                    amount_to_capture: None,
+                    application_fee_amount: Some(application_fee_amount),
+                },
+            )
+            .await
+            .map_err(stripe_error_to_status)?;
+        }
+
+        Ok(payment_intent.status == PaymentIntentStatus::Succeeded)
+    }
+
+    /// Returns when a subscription ends after `billing_cycles` billing
+    /// periods starting at `billing_cycle_anchor`.
+    fn calculate_subscription_end(
+        billing_cycle_anchor: i64,
+        interval: RecurringInterval,
+        interval_count: u64,
+        billing_cycles: u32,
+    ) -> Option<i64> {
+        let anchor = DateTime::from_timestamp(billing_cycle_anchor, 0)?;
+        let intervals = u32::try_from(interval_count)
+            .ok()?
+            .checked_mul(billing_cycles)?;
+
+        let end = match interval {
+            RecurringInterval::Day => anchor
+                .checked_add_signed(chrono::Duration::days(intervals.into())),
+            RecurringInterval::Week => anchor
+                .checked_add_signed(chrono::Duration::weeks(intervals.into())),
+            RecurringInterval::Month => {
+                anchor.checked_add_months(Months::new(intervals))
+            }
+            RecurringInterval::Year => anchor
+                .checked_add_months(Months::new(intervals.checked_mul(12)?)),
+        }?;
+
+        Some(end.timestamp())
+    }
+
+    /// Lets a subscription end after a fixed number of billing cycles.
+    /// Trials are not counted, because the billing cycle anchor is set to
+    /// the end of the trial.
+    async fn set_subscription_end(
+        &self,
+        account: Option<&String>,
+        subscription: &Subscription,
+        billing_cycles: u32,
+    ) -> Result<(), Status> {
+        let stripe_client = self.connected_stripe_client(account)?;
+
+        let recurring = subscription
+            .items
+            .data
+            .first()
+            .and_then(|i| i.price.as_ref())
+            .and_then(|p| p.recurring.as_ref())
+            .ok_or_else(|| Status::internal("subscription price recurring"))?;
+
+        let cancel_at = Self::calculate_subscription_end(
+            subscription.billing_cycle_anchor,
+            recurring.interval,
+            recurring.interval_count,
+            billing_cycles,
+        )
+        .ok_or_else(|| Status::internal("subscription end out of range"))?;
+
+        let mut update_subscription = UpdateSubscription::new();
+        update_subscription.cancel_at = Some(cancel_at);
+        update_subscription.proration_behavior =
+            Some(SubscriptionProrationBehavior::None);
+
+        Subscription::update(
+            &stripe_client,
+            &subscription.id,
+            update_subscription,
+        )
+        .await
+        .map_err(stripe_error_to_status)?;
+
+        Ok(())
+    }
+
+    /// Releases the stock and purchase limit reserved for an expired checkout
+    /// session and stores it for recovery if the buyer consented to be
+    /// contacted.
+    async fn record_abandoned_checkout(
+        &self,
+        checkout_session: CheckoutSession,
+    ) -> Result<(), Status> {
+        let stripe_checkout_session_id = checkout_session.id.to_string();
+
+        OfferStock::settle_reservation(
+            &self.pool,
+            &stripe_checkout_session_id,
+            0,
+        )
+        .await?;
+        PurchaseReservation::delete(&self.pool, &stripe_checkout_session_id)
+            .await?;
+
+        let metadata = checkout_session.metadata.unwrap_or_default();
+
+        // Checkout sessions not created by this service are ignored
+        let (Some(shop_id), Some(offer_id)) = (
+            metadata.get(&Self::metadata_key_shop_id()),
+            metadata.get(&Self::metadata_key_offer_id()),
+        ) else {
+            return Ok(());
+        };
+
+        let consented = checkout_session
+            .consent
+            .and_then(|c| c.promotions)
+            .is_some_and(|p| {
+                p == PaymentPagesCheckoutSessionConsentPromotions::OptIn
+            });
+        let buyer_email = checkout_session
+            .customer_details
+            .and_then(|c| c.email)
+            .or(checkout_session.customer_email);
+        let recovery = checkout_session
+            .after_expiration
+            .and_then(|a| a.recovery)
+            .filter(|r| r.enabled);
+
+        let (true, Some(buyer_email), Some(recovery)) =
+            (consented, buyer_email, recovery)
+        else {
+            return Ok(());
+        };
+        let Some(recovery_url) = recovery.url else {
+            return Ok(());
+        };
+
+        AbandonedCheckout::create(
+            &self.pool,
+            &stripe_checkout_session_id,
+            &parse_uuid(shop_id, "metadata.shop_id")?,
+            &parse_uuid(offer_id, "metadata.offer_id")?,
+            &buyer_email,
+            &recovery_url,
+            recovery
+                .expires_at
+                .and_then(|e| DateTime::from_timestamp(e, 0)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_purchased_quantity(
+        &self,
+        account: Option<&String>,
+        checkout_session_id: &CheckoutSessionId,
+    ) -> Result<i64, Status> {
+        let checkout_session = CheckoutSession::retrieve(
+            &self.connected_stripe_client(account)?,
+            checkout_session_id,
+            &["line_items"],
+        )
+        .await
+        .map_err(stripe_error_to_status)?;
+
+        Ok(checkout_session
+            .line_items
+            .and_then(|l| l.data.into_iter().next())
+            .and_then(|l| l.quantity)
+            .and_then(|q| i64::try_from(q).ok())
+            .unwrap_or(1))
+    }
+
+    async fn record_payment(
+        &self,
+        account: Option<&String>,
+        checkout_session: CheckoutSession,
+    ) -> Result<(), Status> {
+        let mut metadata = checkout_session.metadata.unwrap_or_default();
+
+        // Checkout sessions not created by this service are ignored
+        let (Some(shop_id), Some(offer_id)) = (
+            metadata.get(&Self::metadata_key_shop_id()),
+            metadata.get(&Self::metadata_key_offer_id()),
+        ) else {
+            return Ok(());
+        };
+
+        let shop_id = parse_uuid(shop_id, "metadata.shop_id")?;
+        let offer_id = parse_uuid(offer_id, "metadata.offer_id")?;
+        let user_id = metadata.remove(&Self::metadata_key_user_id());
+        let offer_type = metadata
+            .get(&Self::metadata_key_offer_type())
+            .and_then(|t| OfferType::from_str_name(t));
+        let billing_cycles = metadata
+            .get(&Self::metadata_key_billing_cycles())
+            .and_then(|b| b.parse::<u32>().ok());
+        let platform_fee = metadata
+            .get(&Self::metadata_key_platform_fee_percent())
+            .and_then(|p| p.parse::<u32>().ok())
+            .zip(
+                metadata
+                    .get(&Self::metadata_key_minimum_platform_fee())
+                    .and_then(|m| m.parse::<u32>().ok()),
+            );
+
+        let checkout_session_id = checkout_session.id.to_string();
+
+        // The buyer may have adjusted the quantity during checkout. Stock
+        // reserved for physical offers is settled with the final quantity.
+        let quantity = self
+            .get_purchased_quantity(account, &checkout_session.id)
+            .await?;
+        let recovered_from = checkout_session.recovered_from;
+        let settled_stock = match offer_type {
+            Some(OfferType::Physical) => {
+                OfferStock::settle_reservation(
+                    &self.pool,
+                    &checkout_session_id,
+                    quantity,
+                )
+                .await?
+            }
+            _ => None,
+        };
+        if let Some(stock) = settled_stock.as_ref().filter(|s| s.is_oversold())
+        {
+            tracing::log::warn!(
+                "Offer {offer_id} is oversold by {} after checkout session {checkout_session_id}",
+                stock.reserved - stock.quantity
+            );
+        }
+        let reserved = settled_stock.is_some();
+
+        let total_details = checkout_session.total_details.unwrap_or_default();
+        let amounts = PaymentAmounts {
+            subtotal: checkout_session.amount_subtotal.unwrap_or(0),
+            discount: total_details.amount_discount,
+            shipping: total_details.amount_shipping.unwrap_or(0),
+            tax: total_details.amount_tax,
+            total: checkout_session.amount_total.unwrap_or(0),
+        };
+
+        let buyer_email =
+            checkout_session.customer_details.and_then(|c| c.email);
+        let shipping_address =
+            checkout_session.shipping_details.map(|shipping| {
+                let address = shipping.address.unwrap_or_default();
+                ShippingAddress {
+                    name: shipping.name,
+                    line1: address.line1,
+                    line2: address.line2,
+                    postal_code: address.postal_code,
+                    city: address.city,
+                    state: address.state,
+                    country: address.country,
+                }
+            });
+
+        let currency = checkout_session
+            .currency
+            .map(|c| c.to_string())
+            .unwrap_or_default();
+
+        let payment_intent_id = checkout_session.payment_intent.map(|p| p.id());
+
+        // Amounts chosen by the buyer complete unpaid with an authorized
+        // payment intent, which is captured right away
+        let chosen_amount_paid =
+            match (payment_intent_id.as_ref(), platform_fee) {
+                (Some(payment_intent_id), Some((fee_percent, minimum_fee))) => {
+                    self.capture_chosen_amount(
+                        account,
+                        payment_intent_id,
+                        Self::calculate_fee_amount(
+                            amounts.subtotal - amounts.discount,
+                            fee_percent,
+                            minimum_fee,
+                        ),
+                    )
+                    .await?
+                }
+                _ => false,
+            };
+
+        // Payments of service offers with manual capture complete unpaid
+        // with an authorized payment intent
+        let authorization_expires_at = match payment_intent_id.as_ref() {
+            Some(payment_intent_id)
+                if !chosen_amount_paid
+                    && checkout_session.payment_status
+                        == CheckoutSessionPaymentStatus::Unpaid =>
+            {
+                self.get_authorization_expiry(account, payment_intent_id)
+                    .await?
+            }
+            _ => None,
+        };
+        // Delayed payment methods like SEPA Direct Debit complete unpaid as
+        // well. Their payments are pending until the payment succeeds or fails.
+        let payment_status = match authorization_expires_at {
+            Some(_) => "authorized",
+            None if chosen_amount_paid => "paid",
+            None => match checkout_session.payment_status {
+                CheckoutSessionPaymentStatus::Unpaid => "pending",
+                payment_status => payment_status.as_str(),
+            },
+        };
+
+        let subscription = match checkout_session.subscription.as_ref() {
+            Some(subscription) => Some(
+                Subscription::retrieve(
+                    &self.connected_stripe_client(account)?,
+                    &subscription.id(),
+                    &[],
+                )
+                .await
+                .map_err(stripe_error_to_status)?,
+            ),
+            None => None,
+        };
+        let trial_ends_at = subscription
+            .as_ref()
+            .and_then(|s| s.trial_end)
+            .and_then(|t| DateTime::from_timestamp(t, 0));
+
+        let created = Payment::create(
+            &self.pool,
+            &shop_id,
+            &offer_id,
+            user_id.clone(),
+            Some(checkout_session_id.clone()),
+            payment_intent_id.map(|p| p.to_string()),
+            subscription.as_ref().map(|s| s.id.to_string()),
+            checkout_session.customer.map(|c| c.id().to_string()),
+            payment_status,
+            &currency,
+            &amounts,
+            authorization_expires_at,
+            trial_ends_at,
+            buyer_email,
+            quantity,
+        )
+        .await?;
+
+        // Stripe may deliver events more than once. The payment recorded
+        // before is granted again, so steps which failed before are retried.
+        let (payment, created) = match created {
+            Some(payment) => (payment, true),
+            None => (
+                Payment::get_for_checkout_session(
+                    &self.pool,
+                    &checkout_session_id,
+                )
+                .await?
+                .ok_or_else(|| Status::not_found("payment"))?,
+                false,
+            ),
+        };
+
+        // Recovered checkout sessions have no reservation, so the quantity is
+        // taken from the stock directly
+        if created
+            && !reserved
+            && recovered_from.is_some()
+            && offer_type == Some(OfferType::Physical)
+            && OfferStock::get(&self.pool, &offer_id).await?.is_some()
+            && OfferStock::take(&self.pool, &offer_id, quantity)
+                .await?
+                .is_none()
+        {
+            tracing::log::warn!(
+                "Stock of offer {offer_id} is not sufficient for checkout session {checkout_session_id}"
+            );
+        }
+
+        if let Some(recovered_from) = recovered_from.as_ref() {
+            AbandonedCheckout::mark_recovered(&self.pool, recovered_from)
+                .await?;
+        }
+
+        // Pending payments complete once their payment succeeds, only
+        // subscriptions in good standing grant access before
+        let completed = matches!(
+            payment.payment_status.as_str(),
+            "paid" | "no_payment_required"
+        ) || subscription.as_ref().is_some_and(|s| {
+            matches!(
+                s.status,
+                SubscriptionStatus::Active | SubscriptionStatus::Trialing
+            )
+        });
+
+        if completed {
+            // Digital offers grant the buyer an entitlement. Checkout
+            // sessions without offer type only carry the user_id for digital
+            // offers.
+            if let Some(user_id) = user_id
+                .as_ref()
+                .filter(|_| offer_type != Some(OfferType::Physical))
+            {
+                let valid_until = subscription.as_ref().and_then(|s| {
+                    Self::get_subscription_access_end(s, Utc::now().timestamp())
+                });
+
+                Entitlement::create(
+                    &self.pool,
+                    user_id,
+                    &offer_id,
+                    &shop_id,
+                    &payment.payment_id,
+                    subscription.as_ref().map(|s| s.id.to_string()),
+                    valid_until.and_then(|v| DateTime::from_timestamp(v, 0)),
+                )
+                .await?;
+            }
+
+            LicenseKeyService::assign_license_key(
+                &self.pool,
+                &offer_id,
+                &shop_id,
+                &payment.payment_id,
+                user_id,
+            )
+            .await?;
+
+            if offer_type == Some(OfferType::Physical) {
+                OrderService::create_order(
+                    &self.pool,
+                    &payment.payment_id,
+                    &shop_id,
+                    &offer_id,
+                    shipping_address.unwrap_or_default(),
+                )
+                .await?;
+            }
+        }
+
+        if let (Some(subscription), Some(billing_cycles)) =
+            (subscription.as_ref(), billing_cycles)
+        {
+            self.set_subscription_end(account, subscription, billing_cycles)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_subscription_end() {
+        // 2024-01-31T00:00:00Z
+        let anchor = 1706659200;
+
+        assert_eq!(
+            StripeWebhook::calculate_subscription_end(
+                anchor,
+                RecurringInterval::Day,
+                1,
+                3
+            ),
+            Some(anchor + 3 * 86400)
+        );
+        assert_eq!(
+            StripeWebhook::calculate_subscription_end(
+                anchor,
+                RecurringInterval::Week,
+                2,
+                2
+            ),
+            Some(anchor + 4 * 7 * 86400)
+        );
+        // 2024-04-30T00:00:00Z
+        assert_eq!(
+            StripeWebhook::calculate_subscription_end(
+                anchor,
+                RecurringInterval::Month,
+                1,
+                3
+            ),
+            Some(1714435200)
+        );
+        // 2025-01-31T00:00:00Z
+        assert_eq!(
+            StripeWebhook::calculate_subscription_end(
+                anchor,
+                RecurringInterval::Year,
+                1,
+                1
+            ),
+            Some(1738281600)
+        );
+        assert_eq!(
+            StripeWebhook::calculate_subscription_end(
+                anchor,
+                RecurringInterval::Month,
+                u64::MAX,
+                2
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_calculate_fee_amount() {
+        assert_eq!(StripeWebhook::calculate_fee_amount(5000, 5, 50), 250);
+        assert_eq!(StripeWebhook::calculate_fee_amount(600, 5, 50), 50);
+        assert_eq!(StripeWebhook::calculate_fee_amount(40, 5, 50), 40);
+        assert_eq!(StripeWebhook::calculate_fee_amount(-100, 5, 50), 0);
+    }
+
+    #[test]
+    fn test_calculate_fee_percent() {
+        assert_eq!(StripeWebhook::calculate_fee_percent(5000, 5, 50), 5.0);
+        assert_eq!(StripeWebhook::calculate_fee_percent(600, 5, 50), 8.33);
+        assert_eq!(StripeWebhook::calculate_fee_percent(40, 5, 50), 100.0);
+        assert_eq!(StripeWebhook::calculate_fee_percent(0, 5, 50), 100.0);
+    }
+
+    #[test]
+    fn test_get_subscription_access_end() {
+        let now = 1706659200;
+        let subscription = |status, ended_at, cancel_at| Subscription {
+            status,
+            ended_at,
+            cancel_at,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            StripeWebhook::get_subscription_access_end(
+                &subscription(SubscriptionStatus::Active, None, None),
+                now
+            ),
+            None
+        );
+        assert_eq!(
+            StripeWebhook::get_subscription_access_end(
+                &subscription(
+                    SubscriptionStatus::Trialing,
+                    None,
+                    Some(now + 100)
+                ),
+                now
+            ),
+            Some(now + 100)
+        );
+        assert_eq!(
+            StripeWebhook::get_subscription_access_end(
+                &subscription(
+                    SubscriptionStatus::Canceled,
+                    Some(now - 100),
+                    None
+                ),
+                now
+            ),
+            Some(now - 100)
+        );
+        assert_eq!(
+            StripeWebhook::get_subscription_access_end(
+                &subscription(SubscriptionStatus::Unpaid, None, None),
+                now
+            ),
+            Some(now)
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use deadpool_postgres::Pool;
+use std::str::FromStr;
+
+use jwtk::jwk::RemoteJwksVerifier;
+use stripe::TaxCodeId;
+use tonic::{async_trait, Request, Response, Status};
+
+use crate::api::sited_io::payment::v1::tax_service_server::{
+    self, TaxServiceServer,
+};
+use crate::api::sited_io::payment::v1::{
+    GetTaxSettingsRequest, GetTaxSettingsResponse, PutTaxSettingsRequest,
+    PutTaxSettingsResponse, TaxBehavior, TaxSettingsResponse,
+};
+use crate::auth::get_user_id;
+use crate::model::ShopTaxSettings;
+use crate::{parse_uuid, CommerceService};
+
+pub struct TaxService {
+    pool: Pool,
+    verifier: RemoteJwksVerifier,
+    commerce_service: CommerceService,
+}
+
+impl TaxService {
+    fn new(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> Self {
+        Self {
+            pool,
+            verifier,
+            commerce_service,
+        }
+    }
+
+    pub fn build(
+        pool: Pool,
+        verifier: RemoteJwksVerifier,
+        commerce_service: CommerceService,
+    ) -> TaxServiceServer<Self> {
+        TaxServiceServer::new(Self::new(pool, verifier, commerce_service))
+    }
+
+    fn to_response(tax_settings: ShopTaxSettings) -> TaxSettingsResponse {
+        let tax_behavior = match tax_settings.tax_behavior.as_str() {
+            "inclusive" => TaxBehavior::Inclusive,
+            "exclusive" => TaxBehavior::Exclusive,
+            _ => TaxBehavior::Unspecified,
+        };
+
+        TaxSettingsResponse {
+            shop_id: tax_settings.shop_id.to_string(),
+            automatic_tax_enabled: tax_settings.automatic_tax_enabled,
+            tax_behavior: tax_behavior.into(),
+            physical_tax_code: tax_settings.physical_tax_code,
+            digital_tax_code: tax_settings.digital_tax_code,
+            service_tax_code: tax_settings.service_tax_code,
+            tax_id_collection_enabled: tax_settings.tax_id_collection_enabled,
+            created_at: tax_settings.created_at.timestamp(),
+            updated_at: tax_settings.updated_at.timestamp(),
+        }
+    }
+
+    fn default_response(shop_id: String) -> TaxSettingsResponse {
+        TaxSettingsResponse {
+            shop_id,
+            ..Default::default()
+        }
+    }
+
+    fn validate_tax_code(
+        tax_code: Option<String>,
+        field: &str,
+    ) -> Result<Option<String>, Status> {
+        match tax_code {
+            Some(tax_code) => TaxCodeId::from_str(&tax_code)
+                .map(|_| Some(tax_code))
+                .map_err(|_| {
+                    Status::invalid_argument(format!(
+                        "field {field} is not a valid Stripe tax code"
+                    ))
+                }),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl tax_service_server::TaxService for TaxService {
+    async fn put_tax_settings(
+        &self,
+        request: Request<PutTaxSettingsRequest>,
+    ) -> Result<Response<PutTaxSettingsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let tax_behavior = match request.get_ref().tax_behavior() {
+            TaxBehavior::Unspecified => "unspecified",
+            TaxBehavior::Inclusive => "inclusive",
+            TaxBehavior::Exclusive => "exclusive",
+        };
+
+        let PutTaxSettingsRequest {
+            shop_id,
+            automatic_tax_enabled,
+            physical_tax_code,
+            digital_tax_code,
+            service_tax_code,
+            tax_id_collection_enabled,
+            ..
+        } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        let physical_tax_code =
+            Self::validate_tax_code(physical_tax_code, "physical_tax_code")?;
+        let digital_tax_code =
+            Self::validate_tax_code(digital_tax_code, "digital_tax_code")?;
+        let service_tax_code =
+            Self::validate_tax_code(service_tax_code, "service_tax_code")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let tax_settings = ShopTaxSettings::put(
+            &self.pool,
+            &shop_uuid,
+            automatic_tax_enabled,
+            tax_behavior,
+            physical_tax_code,
+            digital_tax_code,
+            service_tax_code,
+            tax_id_collection_enabled,
+        )
+        .await?;
+
+        Ok(Response::new(PutTaxSettingsResponse {
+            tax_settings: Some(Self::to_response(tax_settings)),
+        }))
+    }
+
+    async fn get_tax_settings(
+        &self,
+        request: Request<GetTaxSettingsRequest>,
+    ) -> Result<Response<GetTaxSettingsResponse>, Status> {
+        let metadata = request.metadata().clone();
+
+        let user_id = get_user_id(&metadata, &self.verifier).await?;
+
+        let GetTaxSettingsRequest { shop_id } = request.into_inner();
+
+        let shop_uuid = parse_uuid(&shop_id, "shop_id")?;
+
+        self.commerce_service
+            .check_shop_and_owner(&shop_id, &user_id, &metadata)
+            .await?;
+
+        let tax_settings =
+            match ShopTaxSettings::get(&self.pool, &shop_uuid).await? {
+                Some(tax_settings) => Self::to_response(tax_settings),
+                None => Self::default_response(shop_id),
+            };
+
+        Ok(Response::new(GetTaxSettingsResponse {
+            tax_settings: Some(tax_settings),
+        }))
+    }
+}